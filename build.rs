@@ -4,4 +4,12 @@ fn main() {
     // Rust 2024 edition requires explicit check-cfg declarations
     println!("cargo::rustc-check-cfg=cfg(wasm)");
     println!("cargo::rustc-check-cfg=cfg(native)");
+
+    // Split the crate into the WASM client and the native server
+    let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    if target_arch == "wasm32" {
+        println!("cargo::rustc-cfg=wasm");
+    } else {
+        println!("cargo::rustc-cfg=native");
+    }
 }
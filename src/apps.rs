@@ -2,6 +2,25 @@
 //!
 //! This module contains all application modules.
 
+pub mod accounts;
+pub mod cards;
 pub mod categories;
 pub mod transactions;
+pub mod reconciliations;
+pub mod installments;
+pub mod loans;
+pub mod assets;
+pub mod investments;
+pub mod forecast;
+pub mod goals;
 pub mod reports;
+pub mod attachments;
+pub mod medical;
+pub mod donations;
+pub mod idempotency;
+pub mod audit;
+pub mod periods;
+pub mod sync;
+pub mod trash;
+pub mod errors;
+pub mod frontend;
@@ -0,0 +1,15 @@
+//! Accounts app for bank accounts, cash and credit cards
+//!
+//! Transactions can be assigned to an account, which gives each account a
+//! running balance and makes it possible to reconcile it against bank
+//! statements (see the reconciliations app). Credit card statements and
+//! their payments are handled by the cards app.
+
+pub mod models;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
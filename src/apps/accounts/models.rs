@@ -0,0 +1,118 @@
+//! Account model definitions
+
+use chrono::{DateTime, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Kind of money account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountType {
+    /// Bank account (普通預金 etc.)
+    Bank,
+    /// Cash on hand (財布)
+    Cash,
+    /// Credit card, paid off from a bank account every month
+    CreditCard,
+    /// Securities account (特定口座, NISA, iDeCo) holding investments
+    Investment,
+}
+
+impl std::fmt::Display for AccountType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountType::Bank => write!(f, "bank"),
+            AccountType::Cash => write!(f, "cash"),
+            AccountType::CreditCard => write!(f, "credit_card"),
+            AccountType::Investment => write!(f, "investment"),
+        }
+    }
+}
+
+impl std::str::FromStr for AccountType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bank" => Ok(AccountType::Bank),
+            "cash" => Ok(AccountType::Cash),
+            "credit_card" => Ok(AccountType::CreditCard),
+            "investment" => Ok(AccountType::Investment),
+            _ => Err(format!("Invalid account type: {}", s)),
+        }
+    }
+}
+
+/// A bank account, cash or credit card the household pays with
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "accounts", table_name = "accounts")]
+pub struct Account {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Account name (e.g., "ゆうちょ銀行", "財布")
+    #[field(max_length = 100)]
+    pub name: String,
+
+    /// Type of account - stored as string
+    #[field(max_length = 20)]
+    pub account_type: String,
+
+    /// Balance before the first recorded transaction
+    pub opening_balance: i64,
+
+    /// Credit cards: day of month the statement closes (31 = end of month)
+    #[field(null = true)]
+    pub closing_day: Option<i32>,
+
+    /// Credit cards: day of the following month the statement is withdrawn
+    #[field(null = true)]
+    pub payment_day: Option<i32>,
+
+    /// Credit cards: bank account the statement is withdrawn from
+    #[field(null = true)]
+    pub payment_account_id: Option<i64>,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Account {
+    /// Gets the account type as enum
+    pub fn get_account_type(&self) -> AccountType {
+        self.account_type.parse().unwrap_or(AccountType::Bank)
+    }
+
+    /// Whether the account is a credit card
+    pub fn is_credit_card(&self) -> bool {
+        self.get_account_type() == AccountType::CreditCard
+    }
+
+    /// Closing and payment day of a credit card
+    pub fn billing_days(&self) -> Option<(u32, u32)> {
+        if !self.is_credit_card() {
+            return None;
+        }
+        let closing_day = u32::try_from(self.closing_day?).ok()?;
+        let payment_day = u32::try_from(self.payment_day?).ok()?;
+        Some((closing_day, payment_day))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_account_type_wire_values() {
+        assert_wire_values(&[
+            AccountType::Bank,
+            AccountType::Cash,
+            AccountType::CreditCard,
+            AccountType::Investment,
+        ]);
+    }
+}
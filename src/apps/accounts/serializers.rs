@@ -0,0 +1,137 @@
+//! Account serializers for request/response handling
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::models::{Account, AccountType};
+use crate::apps::cards::models::CardPayment;
+use crate::apps::installments::Schedules;
+use crate::apps::transactions::models::Transaction;
+
+/// Request payload for creating an account
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateAccountRequest {
+    /// Account name (1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    pub account_type: AccountType,
+    /// Balance before the first recorded transaction
+    #[serde(default)]
+    pub opening_balance: i64,
+    /// Credit cards only: day of month the statement closes (締め日,
+    /// 31 = end of month)
+    #[serde(default)]
+    #[validate(range(min = 1, max = 31, message = "Day must be between 1 and 31"))]
+    pub closing_day: Option<u32>,
+    /// Credit cards only: day of the following month the statement is
+    /// withdrawn (支払日, 31 = end of month)
+    #[serde(default)]
+    #[validate(range(min = 1, max = 31, message = "Day must be between 1 and 31"))]
+    pub payment_day: Option<u32>,
+    /// Credit cards only: bank account the statement is withdrawn from
+    #[serde(default)]
+    pub payment_account_id: Option<i64>,
+}
+
+/// Request payload for updating an account
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateAccountRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    pub opening_balance: Option<i64>,
+    #[validate(range(min = 1, max = 31, message = "Day must be between 1 and 31"))]
+    pub closing_day: Option<u32>,
+    #[validate(range(min = 1, max = 31, message = "Day must be between 1 and 31"))]
+    pub payment_day: Option<u32>,
+    pub payment_account_id: Option<i64>,
+}
+
+/// Response payload for an account
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountResponse {
+    pub id: i64,
+    pub name: String,
+    pub account_type: AccountType,
+    pub opening_balance: i64,
+    /// Opening balance plus income minus expenses of its transactions,
+    /// plus card payments into it and minus card payments out of it
+    pub balance: i64,
+    /// Closing day of a credit card (`None` for other accounts)
+    #[serde(default)]
+    pub closing_day: Option<u32>,
+    /// Payment day of a credit card (`None` for other accounts)
+    #[serde(default)]
+    pub payment_day: Option<u32>,
+    /// Bank account a credit card is paid from (`None` for other accounts)
+    #[serde(default)]
+    pub payment_account_id: Option<i64>,
+    pub created_at: String,
+}
+
+/// Response wrapper for list of accounts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountListResponse {
+    pub count: usize,
+    pub results: Vec<AccountResponse>,
+}
+
+impl AccountResponse {
+    /// Builds the response with the balance over `transactions`, which
+    /// should be the active transactions (of any account), the card
+    /// `payments` and the installment fees due so far
+    pub fn new(
+        account: Account,
+        transactions: &[Transaction],
+        payments: &[CardPayment],
+        schedules: &Schedules,
+    ) -> Self {
+        let id = account.id.unwrap_or(0);
+        let today = Utc::now().date_naive();
+        let own: Vec<&Transaction> = transactions
+            .iter()
+            .filter(|t| t.account_id == Some(id))
+            .collect();
+        let fees_due: i64 = own
+            .iter()
+            .filter_map(|t| t.id.and_then(|id| schedules.get(&id)))
+            .flatten()
+            .filter(|p| p.due_date <= today)
+            .map(|p| p.fee)
+            .sum();
+        let balance = account.opening_balance
+            + own.iter().map(|t| t.signed_amount()).sum::<i64>()
+            + payments.iter().map(|p| p.balance_change(id)).sum::<i64>()
+            - fees_due;
+
+        Self {
+            id,
+            account_type: account.get_account_type(),
+            name: account.name,
+            opening_balance: account.opening_balance,
+            balance,
+            closing_day: account.closing_day.and_then(|d| u32::try_from(d).ok()),
+            payment_day: account.payment_day.and_then(|d| u32::try_from(d).ok()),
+            payment_account_id: account.payment_account_id,
+            created_at: account.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl AccountListResponse {
+    pub fn new(
+        accounts: Vec<Account>,
+        transactions: &[Transaction],
+        payments: &[CardPayment],
+        schedules: &Schedules,
+    ) -> Self {
+        let results: Vec<AccountResponse> = accounts
+            .into_iter()
+            .map(|a| AccountResponse::new(a, transactions, payments, schedules))
+            .collect();
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
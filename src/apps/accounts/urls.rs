@@ -0,0 +1,27 @@
+//! URL routing for accounts app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+use crate::apps::cards::views as card_views;
+use crate::apps::reconciliations::views as reconciliation_views;
+
+/// Returns URL patterns for the accounts app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_accounts)
+        .endpoint(card_views::upcoming_payments)
+        .endpoint(card_views::settle_card_payments_view)
+        .endpoint(views::get_account)
+        .endpoint(views::create_account_view)
+        .endpoint(views::update_account_view)
+        .endpoint(views::delete_account_view)
+        // Statement reconciliation nested under an account
+        .endpoint(reconciliation_views::list_reconciliations)
+        .endpoint(reconciliation_views::create_reconciliation_view)
+        .endpoint(reconciliation_views::get_reconciliation)
+        .endpoint(reconciliation_views::finalize_reconciliation_view)
+        .endpoint(reconciliation_views::delete_reconciliation_view)
+        // Credit card statements
+        .endpoint(card_views::list_statements)
+}
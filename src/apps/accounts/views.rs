@@ -0,0 +1,271 @@
+//! Account views (API endpoints)
+
+use chrono::Utc;
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Request, Response, StatusCode};
+use validator::Validate;
+
+use crate::apps::audit::{self, AuditAction, AuditEntity};
+use crate::apps::cards::{card_payments, CardPayment};
+use crate::apps::idempotency::idempotent;
+use crate::apps::installments::schedules;
+use crate::apps::forecast::models::RecurringItem;
+use crate::apps::goals::models::Goal;
+use crate::apps::investments::models::InvestmentTransaction;
+use crate::apps::transactions::models::Transaction;
+use crate::apps::transactions::views::active_transactions;
+use super::models::Account;
+use super::serializers::{
+    AccountListResponse, AccountResponse, CreateAccountRequest, UpdateAccountRequest,
+};
+
+fn account_not_found(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+        format!(r#"{{"error": "Account with id {} not found"}}"#, id).into_bytes(),
+    ))
+}
+
+/// Returns a 400 response if the credit card settings of an account about
+/// to be saved are inconsistent
+///
+/// Credit cards need both days and may only be paid from an existing
+/// account that is not a card; other accounts must not have card settings.
+async fn reject_invalid_card_settings(account: &Account) -> ViewResult<Option<Response>> {
+    let has_card_settings = account.closing_day.is_some()
+        || account.payment_day.is_some()
+        || account.payment_account_id.is_some();
+
+    let error = if !account.is_credit_card() {
+        has_card_settings.then(|| {
+            "Only credit cards have closing_day, payment_day and payment_account_id".to_string()
+        })
+    } else if account.billing_days().is_none() {
+        Some("Credit cards need closing_day and payment_day".to_string())
+    } else if let Some(payment_account_id) = account.payment_account_id {
+        match Account::objects().get(payment_account_id).first().await? {
+            Some(payer) if !payer.is_credit_card() => None,
+            Some(_) => Some(format!(
+                "Account {} is a credit card and cannot pay another card",
+                payment_account_id
+            )),
+            None => Some(format!("Account with id {} not found", payment_account_id)),
+        }
+    } else {
+        None
+    };
+
+    Ok(error.map(|e| {
+        Response::new(StatusCode::BAD_REQUEST)
+            .with_body(format!(r#"{{"error": "{}"}}"#, e).into_bytes())
+    }))
+}
+
+/// List all accounts with their balances
+///
+/// GET /accounts/
+#[get("/", name = "accounts_list")]
+pub async fn list_accounts() -> ViewResult<Response> {
+    let payments = card_payments().await?;
+    let accounts = Account::objects().all().all().await?;
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+
+    let response = AccountListResponse::new(accounts, &transactions, &payments, &schedules);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get a single account by ID
+///
+/// GET /accounts/{id}/
+#[get("/{id}/", name = "accounts_get")]
+pub async fn get_account(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(account) = Account::objects().get(id).first().await? else {
+        return account_not_found(id);
+    };
+    let payments = card_payments().await?;
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+
+    let response = AccountResponse::new(account, &transactions, &payments, &schedules);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Create a new account
+///
+/// POST /accounts/
+#[post("/", name = "accounts_create")]
+pub async fn create_account_view(
+    request: Request,
+    Json(create_req): Json<CreateAccountRequest>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, create_account(create_req, actor)).await
+}
+
+async fn create_account(create_req: CreateAccountRequest, actor: String) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+
+    let account = Account {
+        id: None,
+        name: create_req.name,
+        account_type: create_req.account_type.to_string(),
+        opening_balance: create_req.opening_balance,
+        closing_day: create_req.closing_day.map(|d| d as i32),
+        payment_day: create_req.payment_day.map(|d| d as i32),
+        payment_account_id: create_req.payment_account_id,
+        created_at: Utc::now(),
+    };
+    if let Some(invalid) = reject_invalid_card_settings(&account).await? {
+        return Ok(invalid);
+    }
+
+    let created = Account::objects().create(&account).await?;
+    if let Some(id) = created.id {
+        let new = audit::snapshot(&created);
+        audit::record(
+            AuditEntity::Account,
+            id,
+            AuditAction::Create,
+            &actor,
+            None,
+            Some(&new),
+        )
+        .await?;
+    }
+
+    // A new account has no transactions yet
+    let response = AccountResponse::new(created, &[], &[], &Default::default());
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Update an existing account
+///
+/// PUT /accounts/{id}/
+#[put("/{id}/", name = "accounts_update")]
+pub async fn update_account_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateAccountRequest>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, update_account(id, update_req, actor)).await
+}
+
+async fn update_account(
+    id: i64,
+    update_req: UpdateAccountRequest,
+    actor: String,
+) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = Account::objects();
+    let Some(mut account) = manager.get(id).first().await? else {
+        return account_not_found(id);
+    };
+    let old = audit::snapshot(&account);
+
+    // Apply updates
+    if let Some(n) = update_req.name {
+        account.name = n;
+    }
+    if let Some(b) = update_req.opening_balance {
+        account.opening_balance = b;
+    }
+    if let Some(d) = update_req.closing_day {
+        account.closing_day = Some(d as i32);
+    }
+    if let Some(d) = update_req.payment_day {
+        account.payment_day = Some(d as i32);
+    }
+    if let Some(payment_account_id) = update_req.payment_account_id {
+        if payment_account_id == id {
+            return Ok(Response::new(StatusCode::BAD_REQUEST).with_body(
+                br#"{"error": "A credit card cannot pay itself"}"#.to_vec(),
+            ));
+        }
+        account.payment_account_id = Some(payment_account_id);
+    }
+    if let Some(invalid) = reject_invalid_card_settings(&account).await? {
+        return Ok(invalid);
+    }
+
+    let updated = manager.update(&account).await?;
+    let new = audit::snapshot(&updated);
+    audit::record(
+        AuditEntity::Account,
+        id,
+        AuditAction::Update,
+        &actor,
+        Some(&old),
+        Some(&new),
+    )
+    .await?;
+
+    let payments = card_payments().await?;
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+    let response = AccountResponse::new(updated, &transactions, &payments, &schedules);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Delete an account that no transaction, card, card payment, investment
+/// transaction, recurring item or savings goal uses
+///
+/// DELETE /accounts/{id}/
+#[delete("/{id}/", name = "accounts_delete")]
+pub async fn delete_account_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, delete_account(id, actor)).await
+}
+
+async fn delete_account(id: i64, actor: String) -> ViewResult<Response> {
+    let manager = Account::objects();
+    let Some(account) = manager.get(id).first().await? else {
+        return account_not_found(id);
+    };
+
+    // Trashed transactions count too, since they can be restored
+    let transactions = Transaction::objects().all().all().await?;
+    let accounts = manager.all().all().await?;
+    let payments = CardPayment::objects().all().all().await?;
+    let investments = InvestmentTransaction::objects().all().all().await?;
+    let recurring = RecurringItem::objects().all().all().await?;
+    let goals = Goal::objects().all().all().await?;
+    if transactions.iter().any(|t| t.account_id == Some(id))
+        || accounts.iter().any(|a| a.payment_account_id == Some(id))
+        || payments.iter().any(|p| p.involves(id))
+        || investments.iter().any(|t| t.account_id == id)
+        || recurring.iter().any(|i| i.account_id == id)
+        || goals.iter().any(|g| g.account_id == Some(id))
+    {
+        return Ok(Response::new(StatusCode::CONFLICT).with_body(
+            format!(
+                r#"{{"error": "Account {} is still used by transactions or credit cards"}}"#,
+                id
+            )
+            .into_bytes(),
+        ));
+    }
+
+    manager.delete(id).await?;
+    let old = audit::snapshot(&account);
+    audit::record(
+        AuditEntity::Account,
+        id,
+        AuditAction::Delete,
+        &actor,
+        Some(&old),
+        None,
+    )
+    .await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Returns whether an account exists
+pub async fn account_exists(id: i64) -> ViewResult<bool> {
+    Ok(Account::objects().get(id).first().await?.is_some())
+}
@@ -0,0 +1,15 @@
+//! Assets app for things valued by hand
+//!
+//! Houses, cars, pension estimates and debts that are neither loans nor
+//! cards have no transactions to derive a balance from, so their value is
+//! recorded as dated snapshots. The net worth report uses the latest
+//! valuation on or before each day.
+
+pub mod models;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
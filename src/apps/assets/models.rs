@@ -0,0 +1,114 @@
+//! Asset model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Whether a manually valued item adds to or subtracts from net worth
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetKind {
+    /// Something owned (house, car, pension estimate)
+    #[default]
+    Asset,
+    /// Something owed that is not a loan or card (e.g. money borrowed from family)
+    Liability,
+}
+
+impl std::fmt::Display for AssetKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetKind::Asset => write!(f, "asset"),
+            AssetKind::Liability => write!(f, "liability"),
+        }
+    }
+}
+
+impl std::str::FromStr for AssetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "asset" => Ok(AssetKind::Asset),
+            "liability" => Ok(AssetKind::Liability),
+            _ => Err(format!("Invalid asset kind: {}", s)),
+        }
+    }
+}
+
+/// A manually valued asset or liability
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "assets", table_name = "assets")]
+pub struct Asset {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Asset name (e.g., "自宅", "厚生年金の見込額")
+    #[field(max_length = 100)]
+    pub name: String,
+
+    /// Asset or liability - stored as string
+    #[field(max_length = 20)]
+    pub kind: String,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Asset {
+    /// Gets the kind as enum
+    pub fn get_kind(&self) -> AssetKind {
+        self.kind.parse().unwrap_or_default()
+    }
+
+    /// Value with liabilities negative
+    pub fn signed_value(&self, value: i64) -> i64 {
+        match self.get_kind() {
+            AssetKind::Asset => value,
+            AssetKind::Liability => -value,
+        }
+    }
+}
+
+/// The value of an asset on a day
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "assets", table_name = "asset_valuations")]
+pub struct AssetValuation {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Asset valued (foreign key)
+    pub asset_id: i64,
+
+    /// Day of the valuation (unique per asset)
+    pub valuation_date: NaiveDate,
+
+    /// Value in yen (amount owed for liabilities)
+    pub value: i64,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Returns the latest of `valuations` on or before `date`
+pub fn valuation_on(valuations: &[AssetValuation], date: NaiveDate) -> Option<&AssetValuation> {
+    valuations
+        .iter()
+        .filter(|v| v.valuation_date <= date)
+        .max_by_key(|v| v.valuation_date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_asset_kind_wire_values() {
+        assert_wire_values(&[AssetKind::Asset, AssetKind::Liability]);
+    }
+}
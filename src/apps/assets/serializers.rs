@@ -0,0 +1,118 @@
+//! Asset serializers for request/response handling
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::models::{Asset, AssetKind, AssetValuation};
+
+/// Request payload for creating a manually valued asset or liability
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateAssetRequest {
+    /// Asset name (1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    #[serde(default)]
+    pub kind: AssetKind,
+}
+
+/// Request payload for renaming an asset
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateAssetRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+}
+
+/// Request payload for recording the value of an asset on a day
+///
+/// A second valuation on the same day replaces the first.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateAssetValuationRequest {
+    pub valuation_date: NaiveDate,
+    /// Value in yen; liabilities are recorded as positive amounts owed
+    #[validate(range(min = 0, message = "Value must not be negative"))]
+    pub value: i64,
+}
+
+/// Response payload for an asset with its latest value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetResponse {
+    pub id: i64,
+    pub name: String,
+    pub kind: AssetKind,
+    /// Latest valuation, if any
+    pub value: Option<i64>,
+    /// Date of the latest valuation as `YYYY-MM-DD`
+    pub valued_on: Option<String>,
+    pub created_at: String,
+}
+
+/// A dated valuation of an asset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetValuationResponse {
+    pub id: i64,
+    pub asset_id: i64,
+    /// Date as `YYYY-MM-DD`
+    pub valuation_date: String,
+    pub value: i64,
+}
+
+/// Response payload for an asset with all its valuations, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetDetailResponse {
+    #[serde(flatten)]
+    pub asset: AssetResponse,
+    pub valuations: Vec<AssetValuationResponse>,
+}
+
+/// Response payload for asset list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetListResponse {
+    pub count: usize,
+    pub results: Vec<AssetResponse>,
+}
+
+impl AssetResponse {
+    /// Builds the response with the latest of the asset's `valuations`
+    pub fn new(asset: &Asset, valuations: &[AssetValuation]) -> Self {
+        let latest = valuations.iter().max_by_key(|v| v.valuation_date);
+        Self {
+            id: asset.id.unwrap_or(0),
+            name: asset.name.clone(),
+            kind: asset.get_kind(),
+            value: latest.map(|v| v.value),
+            valued_on: latest.map(|v| v.valuation_date.format("%Y-%m-%d").to_string()),
+            created_at: asset.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<&AssetValuation> for AssetValuationResponse {
+    fn from(valuation: &AssetValuation) -> Self {
+        Self {
+            id: valuation.id.unwrap_or(0),
+            asset_id: valuation.asset_id,
+            valuation_date: valuation.valuation_date.format("%Y-%m-%d").to_string(),
+            value: valuation.value,
+        }
+    }
+}
+
+impl AssetDetailResponse {
+    /// `valuations` must be the asset's valuations, oldest first
+    pub fn new(asset: &Asset, valuations: &[AssetValuation]) -> Self {
+        Self {
+            asset: AssetResponse::new(asset, valuations),
+            valuations: valuations.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl AssetListResponse {
+    pub fn new(results: Vec<AssetResponse>) -> Self {
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
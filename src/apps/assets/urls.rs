@@ -0,0 +1,17 @@
+//! URL routing for assets app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the assets app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_assets)
+        .endpoint(views::get_asset)
+        .endpoint(views::create_asset_view)
+        .endpoint(views::update_asset_view)
+        .endpoint(views::delete_asset_view)
+        .endpoint(views::create_valuation_view)
+        .endpoint(views::delete_valuation_view)
+}
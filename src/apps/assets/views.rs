@@ -0,0 +1,242 @@
+//! Asset views (API endpoints)
+
+use chrono::Utc;
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Request, Response, StatusCode};
+use std::collections::HashMap;
+use validator::Validate;
+
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use super::models::{Asset, AssetValuation};
+use super::serializers::{
+    AssetDetailResponse, AssetListResponse, AssetResponse, AssetValuationResponse,
+    CreateAssetRequest, CreateAssetValuationRequest, UpdateAssetRequest,
+};
+
+fn asset_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Asset with id {} not found", id),
+    )
+}
+
+/// Returns the valuations of every asset by asset ID, oldest first
+pub async fn valuations_by_asset() -> ViewResult<HashMap<i64, Vec<AssetValuation>>> {
+    let mut valuations: HashMap<i64, Vec<AssetValuation>> = HashMap::new();
+    for valuation in AssetValuation::objects().all().all().await? {
+        valuations
+            .entry(valuation.asset_id)
+            .or_default()
+            .push(valuation);
+    }
+    for list in valuations.values_mut() {
+        list.sort_by_key(|v| v.valuation_date);
+    }
+    Ok(valuations)
+}
+
+/// Returns the valuations of an asset, oldest first
+async fn valuations_of(asset_id: i64) -> ViewResult<Vec<AssetValuation>> {
+    Ok(valuations_by_asset()
+        .await?
+        .remove(&asset_id)
+        .unwrap_or_default())
+}
+
+/// List all assets and liabilities with their latest value
+///
+/// GET /assets/
+#[get("/", name = "assets_list")]
+pub async fn list_assets() -> ViewResult<Response> {
+    let assets = Asset::objects().all().all().await?;
+    let valuations = valuations_by_asset().await?;
+
+    let results: Vec<AssetResponse> = assets
+        .iter()
+        .map(|asset| {
+            let own = asset
+                .id
+                .and_then(|id| valuations.get(&id))
+                .map_or(&[][..], Vec::as_slice);
+            AssetResponse::new(asset, own)
+        })
+        .collect();
+
+    let response = AssetListResponse::new(results);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get an asset with its valuations
+///
+/// GET /assets/{id}/
+#[get("/{id}/", name = "assets_get")]
+pub async fn get_asset(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(asset) = Asset::objects().get(id).first().await? else {
+        return asset_not_found(id);
+    };
+
+    let response = AssetDetailResponse::new(&asset, &valuations_of(id).await?);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Create a new asset or liability
+///
+/// POST /assets/
+#[post("/", name = "assets_create")]
+pub async fn create_asset_view(
+    request: Request,
+    Json(create_req): Json<CreateAssetRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_asset(create_req)).await
+}
+
+async fn create_asset(create_req: CreateAssetRequest) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+
+    let asset = Asset {
+        id: None,
+        name: create_req.name,
+        kind: create_req.kind.to_string(),
+        created_at: Utc::now(),
+    };
+    let created = Asset::objects().create(&asset).await?;
+
+    // A new asset has no valuations yet
+    let response = AssetDetailResponse::new(&created, &[]);
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Rename an asset
+///
+/// PUT /assets/{id}/
+#[put("/{id}/", name = "assets_update")]
+pub async fn update_asset_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateAssetRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, update_asset(id, update_req)).await
+}
+
+async fn update_asset(id: i64, update_req: UpdateAssetRequest) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = Asset::objects();
+    let Some(mut asset) = manager.get(id).first().await? else {
+        return asset_not_found(id);
+    };
+
+    if let Some(n) = update_req.name {
+        asset.name = n;
+    }
+    let updated = manager.update(&asset).await?;
+
+    let response = AssetDetailResponse::new(&updated, &valuations_of(id).await?);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Delete an asset with its valuations
+///
+/// DELETE /assets/{id}/
+#[delete("/{id}/", name = "assets_delete")]
+pub async fn delete_asset_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    idempotent(&request, delete_asset(id)).await
+}
+
+async fn delete_asset(id: i64) -> ViewResult<Response> {
+    let manager = Asset::objects();
+    if manager.get(id).first().await?.is_none() {
+        return asset_not_found(id);
+    }
+
+    let valuation_manager = AssetValuation::objects();
+    for valuation in valuations_of(id).await? {
+        if let Some(valuation_id) = valuation.id {
+            valuation_manager.delete(valuation_id).await?;
+        }
+    }
+    manager.delete(id).await?;
+
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Record the value of an asset on a day
+///
+/// POST /assets/{id}/valuations/
+///
+/// Replaces an existing valuation on the same day (200) instead of adding
+/// one (201).
+#[post("/{id}/valuations/", name = "assets_valuations_create")]
+pub async fn create_valuation_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(create_req): Json<CreateAssetValuationRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_valuation(id, create_req)).await
+}
+
+async fn create_valuation(
+    asset_id: i64,
+    create_req: CreateAssetValuationRequest,
+) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+    if Asset::objects().get(asset_id).first().await?.is_none() {
+        return asset_not_found(asset_id);
+    }
+
+    let manager = AssetValuation::objects();
+    let existing = valuations_of(asset_id)
+        .await?
+        .into_iter()
+        .find(|v| v.valuation_date == create_req.valuation_date);
+    let (status, saved) = match existing {
+        Some(mut valuation) => {
+            valuation.value = create_req.value;
+            (StatusCode::OK, manager.update(&valuation).await?)
+        }
+        None => {
+            let valuation = AssetValuation {
+                id: None,
+                asset_id,
+                valuation_date: create_req.valuation_date,
+                value: create_req.value,
+                created_at: Utc::now(),
+            };
+            (StatusCode::CREATED, manager.create(&valuation).await?)
+        }
+    };
+
+    let response = AssetValuationResponse::from(&saved);
+    Ok(Response::new(status).with_body(json::to_vec(&response)?))
+}
+
+/// Delete a valuation of an asset
+///
+/// DELETE /assets/{id}/valuations/{valuation_id}/
+#[delete("/{id}/valuations/{valuation_id}/", name = "assets_valuations_delete")]
+pub async fn delete_valuation_view(
+    request: Request,
+    Path((asset_id, id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    idempotent(&request, delete_valuation(asset_id, id)).await
+}
+
+async fn delete_valuation(asset_id: i64, id: i64) -> ViewResult<Response> {
+    let manager = AssetValuation::objects();
+    let valuation = manager.get(id).first().await?;
+    if !valuation.is_some_and(|v| v.asset_id == asset_id) {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            &format!("Valuation with id {} not found", id),
+        );
+    }
+
+    manager.delete(id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
@@ -0,0 +1,14 @@
+//! Attachments app for receipts and documents
+//!
+//! Stores photos and PDFs attached to transactions on local disk under the
+//! configured `media_url` directory, deduplicated by content hash. The
+//! endpoints are nested under `/api/transactions/{id}/attachments/` and are
+//! registered by the transactions app.
+
+pub mod models;
+pub mod multipart;
+pub mod serializers;
+pub mod storage;
+pub mod views;
+
+pub use models::*;
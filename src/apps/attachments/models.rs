@@ -0,0 +1,36 @@
+//! Attachment model definitions
+
+use chrono::{DateTime, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Receipt or document file attached to a transaction
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "attachments", table_name = "attachments")]
+pub struct Attachment {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Transaction ID (foreign key)
+    pub transaction_id: i64,
+
+    /// Original file name as uploaded
+    #[field(max_length = 255)]
+    pub filename: String,
+
+    /// MIME type (e.g., "image/jpeg", "application/pdf")
+    #[field(max_length = 100)]
+    pub mime_type: String,
+
+    /// File size in bytes
+    pub size: i64,
+
+    /// Hex-encoded SHA-256 of the content, also the storage key
+    #[field(max_length = 64)]
+    pub sha256: String,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,172 @@
+//! Minimal `multipart/form-data` parsing for file uploads
+//!
+//! Only what the upload endpoint needs: locate the boundary, split the body
+//! into parts and read each part's `Content-Disposition` and `Content-Type`.
+
+/// A single part of a multipart body
+#[derive(Debug, Clone)]
+pub struct Part {
+    /// Form field name
+    pub name: String,
+    /// Client-supplied file name, if the part is a file
+    pub filename: Option<String>,
+    /// Part content type, if given
+    pub content_type: Option<String>,
+    /// Raw part content
+    pub data: Vec<u8>,
+}
+
+/// Extracts the boundary from a `multipart/form-data` content type header
+pub fn boundary(content_type: &str) -> Option<String> {
+    let mut params = content_type.split(';').map(str::trim);
+    let mime = params.next()?;
+    if !mime.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params
+        .filter_map(|p| p.split_once('='))
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, v)| v.trim().trim_matches('"').to_string())
+        .filter(|b| !b.is_empty())
+}
+
+/// Splits a multipart body into its parts
+pub fn parse(body: &[u8], boundary: &str) -> Result<Vec<Part>, String> {
+    let delimiter = format!("--{}", boundary);
+    let delimiter = delimiter.as_bytes();
+
+    let mut parts = Vec::new();
+    let mut pos = find(body, delimiter, 0).ok_or("Multipart boundary not found")?;
+
+    loop {
+        pos += delimiter.len();
+        // Closing delimiter is followed by "--"
+        if body[pos..].starts_with(b"--") {
+            break;
+        }
+        pos = skip_crlf(body, pos);
+
+        let headers_end = find(body, b"\r\n\r\n", pos).ok_or("Malformed multipart headers")?;
+        let headers = std::str::from_utf8(&body[pos..headers_end])
+            .map_err(|_| "Multipart headers are not valid UTF-8")?;
+        let data_start = headers_end + 4;
+
+        let next = find(body, delimiter, data_start).ok_or("Unterminated multipart body")?;
+        // Part data ends with CRLF before the next delimiter
+        let data_end = if next >= 2 && &body[next - 2..next] == b"\r\n" {
+            next - 2
+        } else {
+            next
+        };
+
+        let mut name = None;
+        let mut filename = None;
+        let mut content_type = None;
+        for line in headers.split("\r\n") {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim();
+            if key.eq_ignore_ascii_case("content-disposition") {
+                name = disposition_param(value, "name");
+                filename = disposition_param(value, "filename");
+            } else if key.eq_ignore_ascii_case("content-type") {
+                content_type = Some(value.trim().to_string());
+            }
+        }
+
+        parts.push(Part {
+            name: name.ok_or("Multipart part is missing a field name")?,
+            filename,
+            content_type,
+            data: body[data_start..data_end].to_vec(),
+        });
+
+        pos = next;
+    }
+
+    Ok(parts)
+}
+
+/// Reads a parameter such as `filename="receipt.jpg"` from a disposition value
+fn disposition_param(value: &str, key: &str) -> Option<String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter_map(|p| p.split_once('='))
+        .find(|(k, _)| k.trim().eq_ignore_ascii_case(key))
+        .map(|(_, v)| v.trim().trim_matches('"').to_string())
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    if from > haystack.len() {
+        return None;
+    }
+    haystack[from..]
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i + from)
+}
+
+fn skip_crlf(body: &[u8], pos: usize) -> usize {
+    if body[pos..].starts_with(b"\r\n") {
+        pos + 2
+    } else {
+        pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary() {
+        assert_eq!(
+            boundary("multipart/form-data; boundary=----abc123").as_deref(),
+            Some("----abc123")
+        );
+        assert_eq!(
+            boundary("Multipart/Form-Data; charset=utf-8; Boundary=\"x y\"").as_deref(),
+            Some("x y")
+        );
+        assert_eq!(boundary("multipart/form-data; boundary="), None);
+        assert_eq!(boundary("application/json"), None);
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        let body = b"--XYZ\r\n\
+            Content-Disposition: form-data; name=\"note\"\r\n\r\n\
+            lunch\r\n\
+            --XYZ\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"receipt.jpg\"\r\n\
+            Content-Type: image/jpeg\r\n\r\n\
+            \xff\xd8\r\n\xff\xd9\r\n\
+            --XYZ--\r\n";
+
+        let parts = parse(body, "XYZ").unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].name, "note");
+        assert_eq!(parts[0].filename, None);
+        assert_eq!(parts[0].data, b"lunch");
+        assert_eq!(parts[1].name, "file");
+        assert_eq!(parts[1].filename.as_deref(), Some("receipt.jpg"));
+        assert_eq!(parts[1].content_type.as_deref(), Some("image/jpeg"));
+        // CRLF inside the content is kept, only the one before the
+        // delimiter is dropped
+        assert_eq!(parts[1].data, b"\xff\xd8\r\n\xff\xd9");
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let unterminated = b"--XYZ\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nabc";
+        let nameless = b"--XYZ\r\nContent-Type: text/plain\r\n\r\nabc\r\n--XYZ--";
+        let headers_only = b"--XYZ\r\nContent-Disposition: form-data; name=\"a\"";
+
+        assert!(parse(b"no boundary here", "XYZ").is_err());
+        assert!(parse(unterminated, "XYZ").is_err());
+        assert!(parse(nameless, "XYZ").is_err());
+        assert!(parse(headers_only, "XYZ").is_err());
+    }
+}
@@ -0,0 +1,50 @@
+//! Attachment serializers for request/response handling
+
+use serde::{Deserialize, Serialize};
+
+use super::models::Attachment;
+
+/// Response payload for an attachment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentResponse {
+    pub id: i64,
+    pub transaction_id: i64,
+    pub filename: String,
+    pub mime_type: String,
+    pub size: i64,
+    pub sha256: String,
+    pub created_at: String,
+}
+
+impl From<Attachment> for AttachmentResponse {
+    fn from(attachment: Attachment) -> Self {
+        let created_at = attachment.created_at.to_rfc3339();
+
+        Self {
+            id: attachment.id.unwrap_or(0),
+            transaction_id: attachment.transaction_id,
+            filename: attachment.filename,
+            mime_type: attachment.mime_type,
+            size: attachment.size,
+            sha256: attachment.sha256,
+            created_at,
+        }
+    }
+}
+
+/// Response wrapper for list of attachments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentListResponse {
+    pub count: usize,
+    pub results: Vec<AttachmentResponse>,
+}
+
+impl AttachmentListResponse {
+    pub fn new(attachments: Vec<Attachment>) -> Self {
+        let results: Vec<AttachmentResponse> = attachments.into_iter().map(Into::into).collect();
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! Content-addressed file storage for attachments
+//!
+//! Files live at `<media dir>/attachments/<first two hex chars>/<sha256>`,
+//! so identical uploads share one file on disk. A file is removed only when
+//! no `Attachment` row references its hash any more and it was not written
+//! or reused by an upload in the last [`ORPHAN_GRACE_SECS`] seconds, which
+//! covers the gap between storing a file and inserting its row. Files kept
+//! back by the grace period are picked up later by [`sweep_orphans`].
+
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use crate::config::settings::get_settings;
+use super::models::Attachment;
+
+/// Files written or reused this recently are never treated as orphans
+pub const ORPHAN_GRACE_SECS: u64 = 300;
+
+/// Distinguishes temporary files written by this process
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the directory attachments are stored under
+pub fn storage_root() -> PathBuf {
+    let settings = get_settings();
+    let base_dir = std::env::current_dir().expect("Failed to get current directory");
+    base_dir
+        .join(settings.media_url.trim_matches('/'))
+        .join("attachments")
+}
+
+/// Returns the on-disk path for a content hash
+pub fn path_for(sha256: &str) -> PathBuf {
+    storage_root().join(&sha256[..2]).join(sha256)
+}
+
+/// Computes the hex-encoded SHA-256 of `data`
+pub fn hash(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Returns a temporary path next to `path` that no other write uses
+fn temp_path(path: &Path) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    let suffix = format!(
+        "{}-{}-{}.tmp",
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed),
+        nanos
+    );
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+/// Marks an existing file as just used, so orphan removal leaves it alone
+///
+/// Fails with `NotFound` if the file does not exist (or was just taken by
+/// orphan removal).
+async fn touch(path: &Path) -> std::io::Result<()> {
+    let file = tokio::fs::OpenOptions::new()
+        .append(true)
+        .open(path)
+        .await?;
+    file.into_std().await.set_modified(SystemTime::now())
+}
+
+/// Writes `data` to storage unless identical content is already stored
+///
+/// Returns the content hash used as the storage key.
+pub async fn store(data: &[u8]) -> std::io::Result<String> {
+    let sha256 = hash(data);
+    let path = path_for(&sha256);
+
+    match touch(&path).await {
+        Ok(()) => return Ok(sha256),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    // Write to a temporary name first so a crash never leaves a truncated
+    // file under a valid hash; the name is unique so concurrent uploads of
+    // the same content do not write into each other's file
+    let tmp = temp_path(&path);
+    if let Err(e) = tokio::fs::write(&tmp, data).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(e);
+    }
+    tokio::fs::rename(&tmp, &path).await?;
+
+    Ok(sha256)
+}
+
+/// Reads stored content by hash
+pub async fn load(sha256: &str) -> std::io::Result<Vec<u8>> {
+    tokio::fs::read(path_for(sha256)).await
+}
+
+/// Whether any attachment row references `sha256`
+async fn is_referenced(sha256: &str) -> ViewResult<bool> {
+    let attachments = Attachment::objects().all().all().await?;
+    Ok(attachments.iter().any(|a| a.sha256 == sha256))
+}
+
+/// Whether the file at `path` was written or reused within the grace period
+async fn is_recent(path: &Path) -> bool {
+    let modified = match tokio::fs::metadata(path).await {
+        Ok(metadata) => metadata.modified().ok(),
+        Err(_) => None,
+    };
+    modified
+        .and_then(|m| SystemTime::now().duration_since(m).ok())
+        .is_none_or(|age| age < Duration::from_secs(ORPHAN_GRACE_SECS))
+}
+
+/// Removes the stored files for `hashes` that no attachment references
+///
+/// A file is first moved aside, so an upload of the same content can no
+/// longer reuse it, and then checked again: if an upload reused or wrote it
+/// in the meantime, or a row references it again, it is put back. A file
+/// that cannot be moved or deleted stays on disk; it only costs space and
+/// is reused by the next upload of the same content.
+pub async fn remove_orphans(hashes: &[String]) -> ViewResult<()> {
+    for sha256 in hashes {
+        let path = path_for(sha256);
+        if is_referenced(sha256).await? || is_recent(&path).await {
+            continue;
+        }
+
+        let taken = temp_path(&path);
+        if tokio::fs::rename(&path, &taken).await.is_err() {
+            continue;
+        }
+        let keep = match is_referenced(sha256).await {
+            Ok(referenced) => referenced || is_recent(&taken).await,
+            Err(e) => {
+                let _ = tokio::fs::rename(&taken, &path).await;
+                return Err(e);
+            }
+        };
+        if keep {
+            // An upload may have written a fresh copy meanwhile; either
+            // holds the same content
+            let _ = tokio::fs::rename(&taken, &path).await;
+        } else {
+            let _ = tokio::fs::remove_file(&taken).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `name` is a content hash as used for stored file names
+fn is_hash(name: &str) -> bool {
+    name.len() == 64 && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Removes every stored file no attachment references
+///
+/// Orphan removal after a delete skips files still in their grace period,
+/// so they are collected here instead; `purge_trash` runs this after each
+/// purge. Temporary files left behind by an interrupted write or removal
+/// are deleted once they are past the grace period too. Entries that cannot
+/// be read are skipped until the next sweep.
+pub async fn sweep_orphans() -> ViewResult<()> {
+    let mut hashes = Vec::new();
+    let Ok(mut dirs) = tokio::fs::read_dir(storage_root()).await else {
+        // Nothing has been uploaded yet
+        return Ok(());
+    };
+    while let Ok(Some(dir)) = dirs.next_entry().await {
+        let Ok(mut files) = tokio::fs::read_dir(dir.path()).await else {
+            continue;
+        };
+        while let Ok(Some(file)) = files.next_entry().await {
+            let name = file.file_name().to_string_lossy().into_owned();
+            if is_hash(&name) {
+                hashes.push(name);
+            } else if name.ends_with(".tmp") && !is_recent(&file.path()).await {
+                let _ = tokio::fs::remove_file(file.path()).await;
+            }
+        }
+    }
+    remove_orphans(&hashes).await
+}
+
+/// Deletes all attachments of a transaction and their orphaned files
+pub async fn delete_for_transaction(transaction_id: i64) -> ViewResult<()> {
+    let manager = Attachment::objects();
+    let attachments: Vec<Attachment> = manager
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|a| a.transaction_id == transaction_id)
+        .collect();
+
+    let mut hashes = Vec::new();
+    for attachment in attachments {
+        if let Some(id) = attachment.id {
+            manager.delete(id).await?;
+        }
+        hashes.push(attachment.sha256);
+    }
+
+    remove_orphans(&hashes).await
+}
@@ -0,0 +1,271 @@
+//! Attachment views (API endpoints)
+//!
+//! Mounted under the transactions prefix, so paths are relative to
+//! `/transactions/`.
+
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, Path, Request, Response, StatusCode};
+
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::transactions::models::Transaction;
+use super::models::Attachment;
+use super::multipart;
+use super::serializers::{AttachmentListResponse, AttachmentResponse};
+use super::storage;
+
+/// Maximum accepted upload size (20 MiB)
+const MAX_UPLOAD_SIZE: usize = 20 * 1024 * 1024;
+
+/// Name of the form field carrying the file
+const FILE_FIELD: &str = "file";
+
+/// Receipt formats we accept: raster photos and PDFs
+///
+/// Kept to formats browsers cannot run scripts from; SVG and HTML are
+/// rejected because downloads are served from the API's own origin.
+const ALLOWED_MIME_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/webp",
+    "image/heic",
+    "application/pdf",
+];
+
+/// Returns the bare, lower-case media type of a `Content-Type` value
+fn essence(mime_type: &str) -> String {
+    mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Returns true for the receipt formats we accept
+fn is_allowed_mime_type(mime_type: &str) -> bool {
+    ALLOWED_MIME_TYPES.contains(&mime_type)
+}
+
+/// Returns whether the transaction exists and is not in the trash
+async fn transaction_exists(id: i64) -> ViewResult<bool> {
+    let transaction = Transaction::objects().get(id).first().await?;
+    Ok(transaction.is_some_and(|t| !t.is_deleted()))
+}
+
+fn transaction_not_found(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+        format!(r#"{{"error": "Transaction with id {} not found"}}"#, id).into_bytes(),
+    ))
+}
+
+fn attachment_not_found(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+        format!(r#"{{"error": "Attachment with id {} not found"}}"#, id).into_bytes(),
+    ))
+}
+
+/// Finds an attachment belonging to the given transaction
+async fn find_attachment(transaction_id: i64, attachment_id: i64) -> ViewResult<Option<Attachment>> {
+    let attachment = Attachment::objects().get(attachment_id).first().await?;
+    Ok(attachment.filter(|a| a.transaction_id == transaction_id))
+}
+
+/// List attachments of a transaction
+///
+/// GET /transactions/{id}/attachments/
+#[get("/{id}/attachments/", name = "attachments_list")]
+pub async fn list_attachments(Path(id): Path<i64>) -> ViewResult<Response> {
+    if !transaction_exists(id).await? {
+        return transaction_not_found(id);
+    }
+
+    let attachments: Vec<Attachment> = Attachment::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|a| a.transaction_id == id)
+        .collect();
+
+    let response = AttachmentListResponse::new(attachments);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Upload an attachment as `multipart/form-data` with a `file` field
+///
+/// POST /transactions/{id}/attachments/
+#[post("/{id}/attachments/", name = "attachments_upload")]
+pub async fn upload_attachment(Path(id): Path<i64>, request: Request) -> ViewResult<Response> {
+    idempotent(&request, store_attachment(id, &request)).await
+}
+
+async fn store_attachment(id: i64, request: &Request) -> ViewResult<Response> {
+    if !transaction_exists(id).await? {
+        return transaction_not_found(id);
+    }
+
+    let content_type = request
+        .headers
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let Some(boundary) = multipart::boundary(content_type) else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "Expected a multipart/form-data request body",
+        );
+    };
+
+    let body = request.body();
+    if body.len() > MAX_UPLOAD_SIZE {
+        return error_response(StatusCode::PAYLOAD_TOO_LARGE, "File is too large");
+    }
+
+    let parts = match multipart::parse(body, &boundary) {
+        Ok(parts) => parts,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e),
+    };
+    let Some(file) = parts
+        .into_iter()
+        .find(|p| p.name == FILE_FIELD && p.filename.is_some())
+    else {
+        return error_response(StatusCode::BAD_REQUEST, "Missing 'file' field");
+    };
+
+    let mime_type = essence(file.content_type.as_deref().unwrap_or_default());
+    if !is_allowed_mime_type(&mime_type) {
+        return error_response(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Only JPEG, PNG, WebP, HEIC and PDF files can be attached",
+        );
+    }
+    if file.data.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "File is empty");
+    }
+
+    let sha256 = match storage::store(&file.data).await {
+        Ok(sha256) => sha256,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("Failed to store file: {}", e.kind()),
+            );
+        }
+    };
+
+    // Keep only the final path component of the client-supplied name
+    let filename = file
+        .filename
+        .unwrap_or_default()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(255)
+        .collect::<String>();
+
+    let attachment = Attachment {
+        id: None,
+        transaction_id: id,
+        filename,
+        mime_type,
+        size: file.data.len() as i64,
+        sha256,
+        created_at: chrono::Utc::now(),
+    };
+
+    let created = Attachment::objects().create(&attachment).await?;
+    let response: AttachmentResponse = created.into();
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Download an attachment's content
+///
+/// GET /transactions/{id}/attachments/{attachment_id}/
+///
+/// Photos and PDFs are shown inline; anything else (e.g. stored before the
+/// upload allowlist was narrowed) is sent as a download. Content sniffing
+/// is always disabled.
+#[get("/{id}/attachments/{attachment_id}/", name = "attachments_download")]
+pub async fn download_attachment(
+    Path((id, attachment_id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    if !transaction_exists(id).await? {
+        return transaction_not_found(id);
+    }
+    let Some(attachment) = find_attachment(id, attachment_id).await? else {
+        return attachment_not_found(attachment_id);
+    };
+
+    let mime_type = essence(&attachment.mime_type);
+    let (content_type, disposition) = if is_allowed_mime_type(&mime_type) {
+        (mime_type.as_str(), "inline")
+    } else {
+        ("application/octet-stream", "attachment")
+    };
+
+    match storage::load(&attachment.sha256).await {
+        Ok(data) => Ok(Response::new(StatusCode::OK)
+            .with_header("Content-Type", content_type)
+            .with_header("X-Content-Type-Options", "nosniff")
+            .with_header(
+                "Content-Disposition",
+                &format!(
+                    "{}; filename=\"{}\"",
+                    disposition,
+                    attachment.filename.replace(['"', '\\', '\r', '\n'], "")
+                ),
+            )
+            .with_body(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            error_response(StatusCode::NOT_FOUND, "Attachment file is missing")
+        }
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("Failed to read attachment file: {}", e.kind()),
+        ),
+    }
+}
+
+/// Delete an attachment, removing the file if no other attachment uses it
+///
+/// DELETE /transactions/{id}/attachments/{attachment_id}/
+#[delete("/{id}/attachments/{attachment_id}/", name = "attachments_delete")]
+pub async fn delete_attachment(
+    request: Request,
+    Path((id, attachment_id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    idempotent(&request, remove_attachment(id, attachment_id)).await
+}
+
+async fn remove_attachment(id: i64, attachment_id: i64) -> ViewResult<Response> {
+    // Attachments of a trashed transaction come back with it on restore
+    if !transaction_exists(id).await? {
+        return transaction_not_found(id);
+    }
+    let Some(attachment) = find_attachment(id, attachment_id).await? else {
+        return attachment_not_found(attachment_id);
+    };
+
+    Attachment::objects().delete(attachment_id).await?;
+    storage::remove_orphans(&[attachment.sha256]).await?;
+
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_mime_types() {
+        assert!(is_allowed_mime_type(&essence("image/JPEG")));
+        assert!(is_allowed_mime_type(&essence("application/pdf; charset=x")));
+        assert!(!is_allowed_mime_type(&essence("image/svg+xml")));
+        assert!(!is_allowed_mime_type(&essence("text/html")));
+        assert!(!is_allowed_mime_type(&essence("")));
+    }
+}
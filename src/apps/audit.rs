@@ -0,0 +1,18 @@
+//! Audit app: who changed which record and how
+//!
+//! Every create, update, delete, restore and purge of a category or
+//! transaction appends an entry with the actor, the action and a JSON diff
+//! of the changed fields. The actor comes from the `X-Actor` request header;
+//! there is no authentication, so it is whatever the client reports.
+//! Entries are written after the change in a separate insert, so a failed
+//! insert leaves a saved change without an entry (see [`log::record`]).
+
+pub mod log;
+pub mod models;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use log::{actor, record, snapshot};
+pub use models::*;
+pub use urls::url_patterns;
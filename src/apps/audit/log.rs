@@ -0,0 +1,143 @@
+//! Recording audit entries
+
+use chrono::Utc;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::Request;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::models::{AuditAction, AuditEntity, AuditEntry};
+
+/// Request header naming the person making the change
+pub const ACTOR_HEADER: &str = "x-actor";
+
+/// Actor recorded when the header is missing
+pub const ANONYMOUS_ACTOR: &str = "anonymous";
+
+/// Longest stored actor name
+const MAX_ACTOR_LENGTH: usize = 100;
+
+/// Fields left out of diffs because they change on every write
+const IGNORED_FIELDS: &[&str] = &["updated_at"];
+
+/// Returns the actor of a request from its `X-Actor` header
+///
+/// The header is set by the client and nothing authenticates it, so the
+/// value is a self-reported label, not an identity. Do not base access
+/// decisions on it.
+pub fn actor(request: &Request) -> String {
+    request
+        .headers
+        .get(ACTOR_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(|a| a.chars().take(MAX_ACTOR_LENGTH).collect())
+        .unwrap_or_else(|| ANONYMOUS_ACTOR.to_string())
+}
+
+/// Captures a record's fields for diffing
+pub fn snapshot<T: Serialize>(record: &T) -> Value {
+    serde_json::to_value(record).unwrap_or(Value::Null)
+}
+
+/// Diffs two snapshots into `{"field": {"old": ..., "new": ...}}`
+///
+/// Only changed fields are included. Without `old` (create) every field has
+/// just `new`; without `new` (purge) every field has just `old`.
+pub fn diff(old: Option<&Value>, new: Option<&Value>) -> Value {
+    let empty = Map::new();
+    let old_fields = old.and_then(Value::as_object);
+    let new_fields = new.and_then(Value::as_object);
+
+    let mut names: Vec<&String> = old_fields
+        .unwrap_or(&empty)
+        .keys()
+        .chain(new_fields.unwrap_or(&empty).keys())
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let mut changes = Map::new();
+    for name in names {
+        if IGNORED_FIELDS.contains(&name.as_str()) {
+            continue;
+        }
+        let old_value = old_fields.map(|f| f.get(name).cloned().unwrap_or(Value::Null));
+        let new_value = new_fields.map(|f| f.get(name).cloned().unwrap_or(Value::Null));
+        if old_value == new_value {
+            continue;
+        }
+        let mut change = Map::new();
+        if let Some(v) = old_value {
+            change.insert("old".to_string(), v);
+        }
+        if let Some(v) = new_value {
+            change.insert("new".to_string(), v);
+        }
+        changes.insert(name.clone(), Value::Object(change));
+    }
+    Value::Object(changes)
+}
+
+/// Appends an audit entry for a change
+///
+/// Called by the write views after the change has been saved, with
+/// snapshots of the record before and after it.
+///
+/// The entry is not written in the same database transaction as the
+/// change. If this insert fails, the change stays saved without an entry
+/// and the view answers with a server error, which the idempotency guard
+/// does not store, so a retry runs the view again against the saved
+/// state. The log can therefore miss a change, but never holds an entry
+/// for a change that was not saved.
+pub async fn record(
+    entity: AuditEntity,
+    object_id: i64,
+    action: AuditAction,
+    actor: &str,
+    old: Option<&Value>,
+    new: Option<&Value>,
+) -> ViewResult<()> {
+    let entry = AuditEntry {
+        id: None,
+        entity: entity.to_string(),
+        object_id,
+        action: action.to_string(),
+        actor: actor.to_string(),
+        changes: diff(old, new).to_string(),
+        created_at: Utc::now(),
+    };
+    AuditEntry::objects().create(&entry).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_update_keeps_only_changed_fields() {
+        let old = json!({"amount": 1500, "description": "ランチ", "updated_at": "a"});
+        let new = json!({"amount": 1800, "description": "ランチ", "updated_at": "b"});
+        assert_eq!(
+            diff(Some(&old), Some(&new)),
+            json!({"amount": {"old": 1500, "new": 1800}})
+        );
+    }
+
+    #[test]
+    fn test_diff_create_and_purge() {
+        let record = json!({"id": 1, "icon": null});
+        assert_eq!(
+            diff(None, Some(&record)),
+            json!({"icon": {"new": null}, "id": {"new": 1}})
+        );
+        assert_eq!(
+            diff(Some(&record), None),
+            json!({"icon": {"old": null}, "id": {"old": 1}})
+        );
+    }
+}
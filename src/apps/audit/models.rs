@@ -0,0 +1,167 @@
+//! Audit log model definitions
+
+use chrono::{DateTime, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Kind of record an audit entry refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditEntity {
+    Category,
+    Transaction,
+    /// The closed-books lock
+    Period,
+    Account,
+}
+
+impl std::fmt::Display for AuditEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditEntity::Category => write!(f, "category"),
+            AuditEntity::Transaction => write!(f, "transaction"),
+            AuditEntity::Period => write!(f, "period"),
+            AuditEntity::Account => write!(f, "account"),
+        }
+    }
+}
+
+impl std::str::FromStr for AuditEntity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "category" => Ok(AuditEntity::Category),
+            "transaction" => Ok(AuditEntity::Transaction),
+            "period" => Ok(AuditEntity::Period),
+            "account" => Ok(AuditEntity::Account),
+            _ => Err(format!("Invalid audit entity: {}", s)),
+        }
+    }
+}
+
+/// What was done to an audited record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Create,
+    Update,
+    /// Moved to the trash
+    Delete,
+    /// Restored from the trash
+    Restore,
+    /// Permanently deleted from the trash
+    Purge,
+    /// Books closed through a later date
+    Close,
+    /// Books reopened back to an earlier date
+    Reopen,
+    /// Cleared transactions marked reconciled against a statement
+    Reconcile,
+}
+
+impl std::fmt::Display for AuditAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditAction::Create => write!(f, "create"),
+            AuditAction::Update => write!(f, "update"),
+            AuditAction::Delete => write!(f, "delete"),
+            AuditAction::Restore => write!(f, "restore"),
+            AuditAction::Purge => write!(f, "purge"),
+            AuditAction::Close => write!(f, "close"),
+            AuditAction::Reopen => write!(f, "reopen"),
+            AuditAction::Reconcile => write!(f, "reconcile"),
+        }
+    }
+}
+
+impl std::str::FromStr for AuditAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "create" => Ok(AuditAction::Create),
+            "update" => Ok(AuditAction::Update),
+            "delete" => Ok(AuditAction::Delete),
+            "restore" => Ok(AuditAction::Restore),
+            "purge" => Ok(AuditAction::Purge),
+            "close" => Ok(AuditAction::Close),
+            "reopen" => Ok(AuditAction::Reopen),
+            "reconcile" => Ok(AuditAction::Reconcile),
+            _ => Err(format!("Invalid audit action: {}", s)),
+        }
+    }
+}
+
+/// One change to a category or transaction
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "audit", table_name = "audit_log")]
+pub struct AuditEntry {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Kind of record (category or transaction) - stored as string
+    #[field(max_length = 20)]
+    pub entity: String,
+
+    /// ID of the changed record
+    pub object_id: i64,
+
+    /// Create, update, delete, restore or purge - stored as string
+    #[field(max_length = 20)]
+    pub action: String,
+
+    /// Who made the change
+    #[field(max_length = 100)]
+    pub actor: String,
+
+    /// JSON diff of the changed fields
+    pub changes: String,
+
+    /// Time of the change
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    /// Gets the entity as enum
+    pub fn get_entity(&self) -> Option<AuditEntity> {
+        self.entity.parse().ok()
+    }
+
+    /// Gets the action as enum
+    pub fn get_action(&self) -> AuditAction {
+        self.action.parse().unwrap_or(AuditAction::Update)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_audit_entity_wire_values() {
+        assert_wire_values(&[
+            AuditEntity::Category,
+            AuditEntity::Transaction,
+            AuditEntity::Period,
+            AuditEntity::Account,
+        ]);
+    }
+
+    #[test]
+    fn test_audit_action_wire_values() {
+        assert_wire_values(&[
+            AuditAction::Create,
+            AuditAction::Update,
+            AuditAction::Delete,
+            AuditAction::Restore,
+            AuditAction::Purge,
+            AuditAction::Close,
+            AuditAction::Reopen,
+            AuditAction::Reconcile,
+        ]);
+    }
+}
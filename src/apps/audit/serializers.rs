@@ -0,0 +1,58 @@
+//! Audit serializers for request/response handling
+
+use serde::{Deserialize, Serialize};
+
+use super::models::{AuditAction, AuditEntity, AuditEntry};
+
+/// One recorded change to a category, transaction, account or the period lock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntryResponse {
+    pub id: i64,
+    pub entity: AuditEntity,
+    pub object_id: i64,
+    pub action: AuditAction,
+    /// Who made the change, from the `X-Actor` request header
+    pub actor: String,
+    /// Changed fields as `{"field": {"old": ..., "new": ...}}`; `old` is
+    /// missing on create and `new` when the record was removed (purge or
+    /// account delete)
+    pub changes: serde_json::Value,
+    pub created_at: String,
+}
+
+/// Response wrapper for a list of audit entries, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditListResponse {
+    pub count: usize,
+    pub results: Vec<AuditEntryResponse>,
+}
+
+impl From<AuditEntry> for AuditEntryResponse {
+    fn from(entry: AuditEntry) -> Self {
+        let entity = entry.get_entity().unwrap_or(AuditEntity::Transaction);
+        let action = entry.get_action();
+        let changes = serde_json::from_str(&entry.changes).unwrap_or_default();
+
+        Self {
+            id: entry.id.unwrap_or(0),
+            entity,
+            object_id: entry.object_id,
+            action,
+            actor: entry.actor,
+            changes,
+            created_at: entry.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl AuditListResponse {
+    /// Builds the response, newest entry first
+    pub fn new(mut entries: Vec<AuditEntry>) -> Self {
+        entries.sort_by(|a, b| b.id.cmp(&a.id));
+        let results: Vec<AuditEntryResponse> = entries.into_iter().map(Into::into).collect();
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
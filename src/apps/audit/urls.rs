@@ -0,0 +1,13 @@
+//! URL routing for audit app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the audit app
+///
+/// The per-record history endpoints are nested under the categories and
+/// transactions apps.
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new().endpoint(views::audit_feed)
+}
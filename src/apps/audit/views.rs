@@ -0,0 +1,103 @@
+//! Audit views (API endpoints)
+
+use chrono::NaiveDate;
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{get, Path, Query, Response, StatusCode};
+use serde::Deserialize;
+
+use crate::apps::categories::models::Category;
+use crate::apps::transactions::models::Transaction;
+use super::models::{AuditAction, AuditEntity, AuditEntry};
+use super::serializers::AuditListResponse;
+
+/// Default number of entries returned by the audit feed
+const DEFAULT_FEED_LIMIT: usize = 100;
+
+/// Query parameters for the audit feed
+#[derive(Debug, Deserialize)]
+pub struct AuditFeedQuery {
+    /// Only changes made by this actor
+    pub user: Option<String>,
+    /// Only changes to this kind of record
+    pub entity: Option<AuditEntity>,
+    /// Only this action
+    pub action: Option<AuditAction>,
+    /// Only changes on or after this day (UTC)
+    pub from: Option<NaiveDate>,
+    /// Only changes on or before this day (UTC)
+    pub to: Option<NaiveDate>,
+    /// Maximum number of entries (default 100)
+    pub limit: Option<usize>,
+}
+
+/// Returns the audit entries of one record
+async fn entries_for(entity: AuditEntity, object_id: i64) -> ViewResult<Vec<AuditEntry>> {
+    let entries = AuditEntry::objects().all().all().await?;
+    Ok(entries
+        .into_iter()
+        .filter(|e| e.get_entity() == Some(entity) && e.object_id == object_id)
+        .collect())
+}
+
+/// Audit entries for the whole ledger, newest first
+///
+/// GET /audit/?user=alice&entity=transaction&from=2026-01-01&to=2026-01-31
+#[get("/", name = "audit_feed")]
+pub async fn audit_feed(Query(params): Query<AuditFeedQuery>) -> ViewResult<Response> {
+    let entries: Vec<AuditEntry> = AuditEntry::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|e| params.user.as_deref().is_none_or(|u| e.actor == u))
+        .filter(|e| params.entity.is_none_or(|k| e.get_entity() == Some(k)))
+        .filter(|e| params.action.is_none_or(|action| e.get_action() == action))
+        .filter(|e| {
+            let day = e.created_at.date_naive();
+            params.from.is_none_or(|from| day >= from) && params.to.is_none_or(|to| day <= to)
+        })
+        .collect();
+
+    let mut response = AuditListResponse::new(entries);
+    response
+        .results
+        .truncate(params.limit.unwrap_or(DEFAULT_FEED_LIMIT));
+    response.count = response.results.len();
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Change history of a transaction, newest first
+///
+/// GET /transactions/{id}/history/
+///
+/// Also available for transactions in the trash or already purged.
+#[get("/{id}/history/", name = "transactions_history")]
+pub async fn transaction_history(Path(id): Path<i64>) -> ViewResult<Response> {
+    let entries = entries_for(AuditEntity::Transaction, id).await?;
+    if entries.is_empty() && Transaction::objects().get(id).first().await?.is_none() {
+        return Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Transaction with id {} not found"}}"#, id).into_bytes(),
+        ));
+    }
+
+    let response = AuditListResponse::new(entries);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Change history of a category, newest first
+///
+/// GET /categories/{id}/history/
+#[get("/{id}/history/", name = "categories_history")]
+pub async fn category_history(Path(id): Path<i64>) -> ViewResult<Response> {
+    let entries = entries_for(AuditEntity::Category, id).await?;
+    if entries.is_empty() && Category::objects().get(id).first().await?.is_none() {
+        return Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Category with id {} not found"}}"#, id).into_bytes(),
+        ));
+    }
+
+    let response = AuditListResponse::new(entries);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
@@ -0,0 +1,19 @@
+//! Cards app for credit card statements and payments
+//!
+//! A credit card account closes its statement on the closing day (締め日)
+//! and the amount due is withdrawn from its bank account on the payment
+//! day (支払日) of the following month. Statements are computed from the
+//! card's transactions; once a payment date has come, settling (the
+//! `settle-card-payments` endpoint or `manage settle_card_payments`) records
+//! the transfer from the bank account as a card payment, which moves the
+//! balances of both accounts without counting as income or expense.
+
+pub mod billing;
+pub mod models;
+pub mod payments;
+pub mod serializers;
+pub mod views;
+
+pub use models::*;
+pub use payments::{card_payments, settle_card_payments};
+pub use serializers::*;
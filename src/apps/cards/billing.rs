@@ -0,0 +1,114 @@
+//! Statement period arithmetic for credit cards
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+/// One billing period of a card
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatementPeriod {
+    /// First day of the period
+    pub start: NaiveDate,
+    /// Closing date (締め日), the last day of the period
+    pub closing: NaiveDate,
+    /// Withdrawal date (支払日) in the month after the closing date
+    pub payment: NaiveDate,
+}
+
+impl StatementPeriod {
+    /// Whether `date` falls in the period
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.closing
+    }
+}
+
+/// Returns the first day of the month of `date`
+pub fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// Returns `day` of the month starting at `first`, or its last day if the
+/// month is shorter (so 31 means end of month)
+pub fn day_in_month(first: NaiveDate, day: u32) -> NaiveDate {
+    let last = first + Months::new(1) - Days::new(1);
+    first.with_day(day.min(last.day())).unwrap_or(last)
+}
+
+/// Returns the period with the given closing date
+fn period_closing_on(closing: NaiveDate, closing_day: u32, payment_day: u32) -> StatementPeriod {
+    let month = first_of_month(closing);
+    let previous_closing = day_in_month(month - Months::new(1), closing_day);
+    StatementPeriod {
+        start: previous_closing + Days::new(1),
+        closing,
+        payment: day_in_month(month + Months::new(1), payment_day),
+    }
+}
+
+/// Returns the period a purchase on `date` is billed in
+pub fn period_containing(date: NaiveDate, closing_day: u32, payment_day: u32) -> StatementPeriod {
+    let month = first_of_month(date);
+    let mut closing = day_in_month(month, closing_day);
+    if date > closing {
+        closing = day_in_month(month + Months::new(1), closing_day);
+    }
+    period_closing_on(closing, closing_day, payment_day)
+}
+
+/// Returns the consecutive periods from the one containing `from` through
+/// the one containing `to`
+pub fn periods_between(
+    from: NaiveDate,
+    to: NaiveDate,
+    closing_day: u32,
+    payment_day: u32,
+) -> Vec<StatementPeriod> {
+    let mut periods = Vec::new();
+    let mut period = period_containing(from, closing_day, payment_day);
+    loop {
+        periods.push(period);
+        if period.closing >= to {
+            break;
+        }
+        period = period_containing(period.closing + Days::new(1), closing_day, payment_day);
+    }
+    periods
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_period_containing() {
+        // 15日締め翌月10日払い
+        let period = period_containing(date(2026, 1, 20), 15, 10);
+        assert_eq!(period.start, date(2026, 1, 16));
+        assert_eq!(period.closing, date(2026, 2, 15));
+        assert_eq!(period.payment, date(2026, 3, 10));
+        assert_eq!(
+            period_containing(date(2026, 1, 15), 15, 10).closing,
+            date(2026, 1, 15)
+        );
+
+        // 月末締め翌月27日払い, across February
+        let period = period_containing(date(2026, 2, 10), 31, 27);
+        assert_eq!(period.start, date(2026, 2, 1));
+        assert_eq!(period.closing, date(2026, 2, 28));
+        assert_eq!(period.payment, date(2026, 3, 27));
+        let period = period_containing(date(2026, 3, 1), 30, 31);
+        assert_eq!(period.start, date(2026, 3, 1));
+        assert_eq!(period.closing, date(2026, 3, 30));
+        assert_eq!(period.payment, date(2026, 4, 30));
+    }
+
+    #[test]
+    fn test_periods_between() {
+        let periods = periods_between(date(2025, 12, 20), date(2026, 2, 1), 15, 10);
+        let closings: Vec<NaiveDate> = periods.iter().map(|p| p.closing).collect();
+        assert_eq!(closings, [date(2026, 1, 15), date(2026, 2, 15)]);
+        assert_eq!(periods[1].start, periods[0].closing + Days::new(1));
+    }
+}
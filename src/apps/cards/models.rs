@@ -0,0 +1,55 @@
+//! Card payment model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Transfer from a bank account paying one credit card statement
+///
+/// Generated on the payment date; it is neither income nor expense (the
+/// purchases were already counted when they were made).
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "cards", table_name = "card_payments")]
+pub struct CardPayment {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Credit card account that was paid (foreign key)
+    pub card_account_id: i64,
+
+    /// Bank account the amount was withdrawn from (foreign key)
+    pub payment_account_id: i64,
+
+    /// Closing date of the statement it pays
+    pub closing_date: NaiveDate,
+
+    /// Withdrawal date
+    pub payment_date: NaiveDate,
+
+    /// Amount withdrawn in yen
+    pub amount: i64,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl CardPayment {
+    /// Effect on the balance of an account: the card goes up, the bank
+    /// account goes down, any other account is unaffected
+    pub fn balance_change(&self, account_id: i64) -> i64 {
+        if account_id == self.card_account_id {
+            self.amount
+        } else if account_id == self.payment_account_id {
+            -self.amount
+        } else {
+            0
+        }
+    }
+
+    /// Whether the payment moves money in or out of the account
+    pub fn involves(&self, account_id: i64) -> bool {
+        account_id == self.card_account_id || account_id == self.payment_account_id
+    }
+}
@@ -0,0 +1,263 @@
+//! Computing card statements and generating their payments
+
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+
+use crate::apps::accounts::models::Account;
+use crate::apps::installments::{schedules, InstallmentPayment, Schedules};
+use crate::apps::transactions::models::Transaction;
+use crate::apps::transactions::views::active_transactions;
+use super::billing::{first_of_month, periods_between, StatementPeriod};
+use super::models::CardPayment;
+
+/// A statement period with the amount charged in it
+pub struct Statement {
+    pub period: StatementPeriod,
+    /// Expenses minus refunds in the period (negative when refunds exceed
+    /// the charges)
+    pub net: i64,
+    /// `net`, but never negative
+    pub amount_due: i64,
+}
+
+fn same_month(a: NaiveDate, b: NaiveDate) -> bool {
+    a.year() == b.year() && a.month() == b.month()
+}
+
+/// Returns the statements of a card from its first transaction through the
+/// period open on `today` (or its last transaction or installment, if
+/// later), oldest first
+///
+/// `transactions` should be the active transactions (of any account).
+/// Purchases with an installment plan are billed as their payments, each
+/// in the statement withdrawn in the month it is due.
+pub fn card_statements(
+    card: &Account,
+    transactions: &[Transaction],
+    schedules: &Schedules,
+    today: NaiveDate,
+) -> Vec<Statement> {
+    let (Some(id), Some((closing_day, payment_day))) = (card.id, card.billing_days()) else {
+        return Vec::new();
+    };
+    let (installments, charges): (Vec<&Transaction>, Vec<&Transaction>) = transactions
+        .iter()
+        .filter(|t| t.account_id == Some(id))
+        .partition(|t| t.id.is_some_and(|id| schedules.contains_key(&id)));
+    let installments: Vec<&InstallmentPayment> = installments
+        .iter()
+        .filter_map(|t| t.id.and_then(|id| schedules.get(&id)))
+        .flatten()
+        .collect();
+
+    // The statement withdrawn in a month closes in the month before
+    let mut dates: Vec<NaiveDate> = charges
+        .iter()
+        .map(|t| t.transaction_date.date_naive())
+        .collect();
+    dates.extend(
+        installments
+            .iter()
+            .map(|p| first_of_month(p.due_date) - Months::new(1)),
+    );
+    let first = dates.iter().copied().min().unwrap_or(today).min(today);
+    let last = dates.iter().copied().max().unwrap_or(today).max(today);
+
+    periods_between(first, last, closing_day, payment_day)
+        .into_iter()
+        .map(|period| {
+            let charged: i64 = charges
+                .iter()
+                .filter(|t| period.contains(t.transaction_date.date_naive()))
+                .map(|t| t.signed_amount())
+                .sum();
+            let installment_due: i64 = installments
+                .iter()
+                .filter(|p| same_month(p.due_date, period.payment))
+                .map(|p| p.amount())
+                .sum();
+            let net = installment_due - charged;
+            Statement {
+                period,
+                net,
+                amount_due: net.max(0),
+            }
+        })
+        .collect()
+}
+
+/// Returns all recorded card payments
+///
+/// Read-only: payments are recorded by [`settle_card_payments`].
+pub async fn card_payments() -> ViewResult<Vec<CardPayment>> {
+    Ok(CardPayment::objects().all().all().await?)
+}
+
+/// Returns the payments still to record for a card's `statements` (oldest
+/// first) given its recorded `payments`, as (period, amount)
+///
+/// Everything billed through a statement whose payment date has come,
+/// minus everything already paid, is withdrawn with the first such
+/// statement after the latest paid one. So a statement that had nothing
+/// due when a later one was paid, or purchases entered late into an
+/// already paid period, are billed with the next unpaid statement rather
+/// than dropped, and changing the closing day never pays a purchase twice.
+pub fn unpaid_statements(
+    statements: &[Statement],
+    payments: &[&CardPayment],
+    today: NaiveDate,
+) -> Vec<(StatementPeriod, i64)> {
+    let last_paid = payments.iter().map(|p| p.closing_date).max();
+    let mut outstanding: i64 = -payments.iter().map(|p| p.amount).sum::<i64>();
+    let mut unpaid = Vec::new();
+    for statement in statements.iter().take_while(|s| s.period.payment <= today) {
+        outstanding += statement.net;
+        if last_paid.is_some_and(|paid| statement.period.closing <= paid) || outstanding <= 0 {
+            continue;
+        }
+        unpaid.push((statement.period, outstanding));
+        outstanding = 0;
+    }
+    unpaid
+}
+
+/// Records the payments of card statements whose payment date has come
+/// by `today` (see [`unpaid_statements`]) and returns the new payments
+///
+/// Run by `POST /api/accounts/settle-card-payments/` and `manage
+/// settle_card_payments`, e.g. daily. Cards without a payment account get
+/// no payment.
+pub async fn settle_card_payments(today: NaiveDate) -> ViewResult<Vec<CardPayment>> {
+    let manager = CardPayment::objects();
+    let payments = manager.all().all().await?;
+
+    let accounts = Account::objects().all().all().await?;
+    let cards: Vec<&Account> = accounts.iter().filter(|a| a.is_credit_card()).collect();
+    if cards.is_empty() {
+        return Ok(Vec::new());
+    }
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+
+    let mut created = Vec::new();
+    for card in cards {
+        let (Some(card_id), Some(payment_account_id)) = (card.id, card.payment_account_id) else {
+            continue;
+        };
+        let paid: Vec<&CardPayment> = payments
+            .iter()
+            .filter(|p| p.card_account_id == card_id)
+            .collect();
+        let statements = card_statements(card, &transactions, &schedules, today);
+
+        for (period, amount) in unpaid_statements(&statements, &paid, today) {
+            let payment = CardPayment {
+                id: None,
+                card_account_id: card_id,
+                payment_account_id,
+                closing_date: period.closing,
+                payment_date: period.payment,
+                amount,
+                created_at: Utc::now(),
+            };
+            match manager.create(&payment).await {
+                Ok(payment) => created.push(payment),
+                Err(e) => {
+                    // The unique (card, closing date) index rejects the
+                    // insert when a concurrent run recorded the payment;
+                    // anything else is a real failure
+                    let recorded = manager.all().all().await?;
+                    if !recorded
+                        .iter()
+                        .any(|p| p.card_account_id == card_id && p.closing_date == period.closing)
+                    {
+                        return Err(e.into());
+                    }
+                    // Later statements would be computed from stale totals
+                    break;
+                }
+            }
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    /// Statement closing on the 15th of `month` (2026), paid on the 10th
+    /// of the next month
+    fn statement(month: u32, net: i64) -> Statement {
+        Statement {
+            period: StatementPeriod {
+                start: date(2026, month - 1, 16),
+                closing: date(2026, month, 15),
+                payment: date(2026, month + 1, 10),
+            },
+            net,
+            amount_due: net.max(0),
+        }
+    }
+
+    fn paid(month: u32, amount: i64) -> CardPayment {
+        CardPayment {
+            id: Some(i64::from(month)),
+            card_account_id: 1,
+            payment_account_id: 2,
+            closing_date: date(2026, month, 15),
+            payment_date: date(2026, month + 1, 10),
+            amount,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_unpaid_statements() {
+        let statements = [statement(2, 10_000), statement(3, 0), statement(4, 5_000)];
+        let due = unpaid_statements(&statements, &[], date(2026, 5, 10));
+        let amounts: Vec<(NaiveDate, i64)> = due.iter().map(|(p, a)| (p.closing, *a)).collect();
+        assert_eq!(
+            amounts,
+            [(date(2026, 2, 15), 10_000), (date(2026, 4, 15), 5_000)]
+        );
+        // Payment date of April's statement not reached yet
+        assert_eq!(
+            unpaid_statements(&statements, &[], date(2026, 5, 9)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_late_purchases_billed_with_next_statement() {
+        // March had nothing due when April was paid; a purchase was then
+        // entered into March, and another into the paid April period
+        let payments = [paid(2, 10_000), paid(4, 5_000)];
+        let payments: Vec<&CardPayment> = payments.iter().collect();
+        let statements = [
+            statement(2, 10_000),
+            statement(3, 3_000),
+            statement(4, 6_000),
+            statement(5, 2_000),
+        ];
+
+        let due = unpaid_statements(&statements, &payments, date(2026, 6, 10));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0.closing, date(2026, 5, 15));
+        assert_eq!(due[0].1, 3_000 + 1_000 + 2_000);
+        assert!(unpaid_statements(&statements, &payments, date(2026, 6, 9)).is_empty());
+    }
+
+    #[test]
+    fn test_refund_credit_carries_over() {
+        let statements = [statement(2, -4_000), statement(3, 10_000)];
+        let due = unpaid_statements(&statements, &[], date(2026, 4, 10));
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].1, 6_000);
+    }
+}
@@ -0,0 +1,113 @@
+//! Card serializers for response handling
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::models::CardPayment;
+use super::payments::Statement;
+
+/// One billing period of a credit card
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardStatementResponse {
+    pub card_account_id: i64,
+    /// First day of the period as `YYYY-MM-DD`
+    pub period_start: String,
+    /// Closing date (締め日) as `YYYY-MM-DD`; the period includes it
+    pub closing_date: String,
+    /// Withdrawal date (支払日) as `YYYY-MM-DD`
+    pub payment_date: String,
+    /// Expenses minus refunds charged to the card in the period
+    pub amount_due: i64,
+    /// Whether the closing date has passed
+    pub closed: bool,
+    /// Transfer from the bank account, once the statement was settled
+    pub payment: Option<CardPaymentResponse>,
+}
+
+/// Transfer from the bank account paying a card statement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardPaymentResponse {
+    pub id: i64,
+    pub card_account_id: i64,
+    pub payment_account_id: i64,
+    /// Closing date of the statement it pays, as `YYYY-MM-DD`
+    pub closing_date: String,
+    /// Withdrawal date as `YYYY-MM-DD`
+    pub payment_date: String,
+    pub amount: i64,
+    pub created_at: String,
+}
+
+/// Response wrapper for the statements of a card, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardStatementListResponse {
+    pub count: usize,
+    pub results: Vec<CardStatementResponse>,
+}
+
+/// A card statement that has not been withdrawn yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingCardPaymentResponse {
+    pub card_name: String,
+    /// Bank account it will be withdrawn from (`None` if not set up)
+    pub payment_account_id: Option<i64>,
+    #[serde(flatten)]
+    pub statement: CardStatementResponse,
+}
+
+/// Upcoming card payments, soonest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingCardPaymentListResponse {
+    pub count: usize,
+    /// Sum of `amount_due`
+    pub total: i64,
+    pub results: Vec<UpcomingCardPaymentResponse>,
+}
+
+/// Card payments recorded by a settlement run
+#[derive(Debug, Serialize)]
+pub struct CardPaymentListResponse {
+    pub count: usize,
+    pub results: Vec<CardPaymentResponse>,
+}
+
+impl From<&CardPayment> for CardPaymentResponse {
+    fn from(payment: &CardPayment) -> Self {
+        Self {
+            id: payment.id.unwrap_or(0),
+            card_account_id: payment.card_account_id,
+            payment_account_id: payment.payment_account_id,
+            closing_date: payment.closing_date.format("%Y-%m-%d").to_string(),
+            payment_date: payment.payment_date.format("%Y-%m-%d").to_string(),
+            amount: payment.amount,
+            created_at: payment.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl CardStatementResponse {
+    /// Builds the response, attaching the payment of the statement from
+    /// `payments` if there is one
+    pub fn new(
+        card_account_id: i64,
+        statement: &Statement,
+        payments: &[CardPayment],
+        today: NaiveDate,
+    ) -> Self {
+        let period = statement.period;
+        let payment = payments
+            .iter()
+            .find(|p| p.card_account_id == card_account_id && p.closing_date == period.closing)
+            .map(CardPaymentResponse::from);
+
+        Self {
+            card_account_id,
+            period_start: period.start.format("%Y-%m-%d").to_string(),
+            closing_date: period.closing.format("%Y-%m-%d").to_string(),
+            payment_date: period.payment.format("%Y-%m-%d").to_string(),
+            amount_due: statement.amount_due,
+            closed: period.closing < today,
+            payment,
+        }
+    }
+}
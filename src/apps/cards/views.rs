@@ -0,0 +1,122 @@
+//! Card views (API endpoints)
+//!
+//! Mounted under `/api/accounts/`.
+
+use chrono::Utc;
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{get, post, Path, Request, Response, StatusCode};
+
+use crate::apps::accounts::models::Account;
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::installments::schedules;
+use crate::apps::transactions::views::active_transactions;
+use super::payments::{card_payments, card_statements, settle_card_payments};
+use super::serializers::{
+    CardPaymentListResponse, CardPaymentResponse, CardStatementListResponse, CardStatementResponse,
+    UpcomingCardPaymentListResponse, UpcomingCardPaymentResponse,
+};
+
+/// List the statements of a credit card, newest first
+///
+/// GET /accounts/{id}/statements/
+///
+/// Runs from the card's first transaction through the period still open
+/// today.
+#[get("/{id}/statements/", name = "accounts_statements")]
+pub async fn list_statements(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(card) = Account::objects().get(id).first().await? else {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            &format!("Account with id {} not found", id),
+        );
+    };
+    if card.billing_days().is_none() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!(
+                "Account {} is not a credit card with closing and payment days",
+                id
+            ),
+        );
+    }
+
+    let payments = card_payments().await?;
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+    let today = Utc::now().date_naive();
+
+    let results: Vec<CardStatementResponse> =
+        card_statements(&card, &transactions, &schedules, today)
+            .iter()
+            .rev()
+            .map(|s| CardStatementResponse::new(id, s, &payments, today))
+            .collect();
+    let response = CardStatementListResponse {
+        count: results.len(),
+        results,
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// List card statements still to be withdrawn, soonest first
+///
+/// GET /accounts/upcoming-payments/
+///
+/// Includes the period still open today with the amount charged so far.
+#[get("/upcoming-payments/", name = "accounts_upcoming_payments")]
+pub async fn upcoming_payments() -> ViewResult<Response> {
+    let payments = card_payments().await?;
+    let accounts = Account::objects().all().all().await?;
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+    let today = Utc::now().date_naive();
+
+    let mut results: Vec<UpcomingCardPaymentResponse> = Vec::new();
+    for card in accounts.iter().filter(|a| a.is_credit_card()) {
+        let Some(card_id) = card.id else {
+            continue;
+        };
+        for statement in card_statements(card, &transactions, &schedules, today) {
+            if statement.period.payment <= today || statement.amount_due == 0 {
+                continue;
+            }
+            results.push(UpcomingCardPaymentResponse {
+                card_name: card.name.clone(),
+                payment_account_id: card.payment_account_id,
+                statement: CardStatementResponse::new(card_id, &statement, &payments, today),
+            });
+        }
+    }
+    results.sort_by(|a, b| a.statement.payment_date.cmp(&b.statement.payment_date));
+
+    let response = UpcomingCardPaymentListResponse {
+        count: results.len(),
+        total: results.iter().map(|r| r.statement.amount_due).sum(),
+        results,
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Record the payments of card statements whose payment date has come
+///
+/// POST /accounts/settle-card-payments/
+///
+/// Returns the payments recorded by this call; run it (or `manage
+/// settle_card_payments`) daily so transfers appear on their payment date.
+#[post("/settle-card-payments/", name = "accounts_settle_card_payments")]
+pub async fn settle_card_payments_view(request: Request) -> ViewResult<Response> {
+    idempotent(&request, settle()).await
+}
+
+async fn settle() -> ViewResult<Response> {
+    let created = settle_card_payments(Utc::now().date_naive()).await?;
+    let results: Vec<CardPaymentResponse> = created.iter().map(CardPaymentResponse::from).collect();
+    let response = CardPaymentListResponse {
+        count: results.len(),
+        results,
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
@@ -4,36 +4,7 @@ use chrono::{DateTime, Utc};
 use reinhardt::prelude::*;
 use serde::{Deserialize, Serialize};
 
-/// Category type representing whether it's for income or expense
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum CategoryType {
-    /// Income category (salary, bonus, etc.)
-    Income,
-    /// Expense category (food, transport, etc.)
-    Expense,
-}
-
-impl std::fmt::Display for CategoryType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            CategoryType::Income => write!(f, "income"),
-            CategoryType::Expense => write!(f, "expense"),
-        }
-    }
-}
-
-impl std::str::FromStr for CategoryType {
-    type Err = String;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "income" => Ok(CategoryType::Income),
-            "expense" => Ok(CategoryType::Expense),
-            _ => Err(format!("Invalid category type: {}", s)),
-        }
-    }
-}
+pub use crate::shared::types::CategoryType;
 
 /// Category model for organizing transactions
 #[derive(Serialize, Deserialize)]
@@ -59,6 +30,13 @@ pub struct Category {
     #[field(max_length = 10, null = true)]
     pub color: Option<String>,
 
+    /// Display position in lists (ascending)
+    pub sort_order: i32,
+
+    /// When the category was moved to the trash (`None` if not deleted)
+    #[field(null = true)]
+    pub deleted_at: Option<DateTime<Utc>>,
+
     /// Creation timestamp
     #[field(auto_now_add = true)]
     pub created_at: DateTime<Utc>,
@@ -69,4 +47,9 @@ impl Category {
     pub fn get_category_type(&self) -> CategoryType {
         self.category_type.parse().unwrap_or(CategoryType::Expense)
     }
+
+    /// Whether the category is in the trash
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
 }
@@ -3,6 +3,7 @@
 use reinhardt::ServerRouter;
 
 use super::views;
+use crate::apps::audit::views as audit_views;
 
 /// Returns URL patterns for the categories app
 pub fn url_patterns() -> ServerRouter {
@@ -12,4 +13,6 @@ pub fn url_patterns() -> ServerRouter {
         .endpoint(views::create_category_view)
         .endpoint(views::update_category_view)
         .endpoint(views::delete_category_view)
+        // Change history from the audit log
+        .endpoint(audit_views::category_history)
 }
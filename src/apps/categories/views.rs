@@ -4,21 +4,30 @@ use chrono::Utc;
 use reinhardt::core::serde::json;
 use reinhardt::http::ViewResult;
 use reinhardt::Model;
-use reinhardt::{delete, get, post, put, Json, Path, Response, StatusCode};
+use reinhardt::{delete, get, post, put, Json, Path, Request, Response, StatusCode};
 use validator::Validate;
 
+use crate::apps::audit::{self, AuditAction, AuditEntity};
+use crate::apps::idempotency::idempotent;
+use crate::apps::sync::changes;
+use crate::apps::sync::models::{ChangeAction, SyncEntity};
 use super::models::Category;
 use super::serializers::{
     CategoryListResponse, CategoryResponse, CreateCategoryRequest, UpdateCategoryRequest,
 };
 
+/// Fetches all categories that are not in the trash
+pub async fn active_categories() -> ViewResult<Vec<Category>> {
+    let categories = Category::objects().all().all().await?;
+    Ok(categories.into_iter().filter(|c| !c.is_deleted()).collect())
+}
+
 /// List all categories
 ///
 /// GET /categories/
 #[get("/", name = "categories_list")]
 pub async fn list_categories() -> ViewResult<Response> {
-    let manager = Category::objects();
-    let categories = manager.all().all().await?;
+    let categories = active_categories().await?;
 
     let response = CategoryListResponse::new(categories);
     Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
@@ -30,7 +39,7 @@ pub async fn list_categories() -> ViewResult<Response> {
 #[get("/{id}/", name = "categories_get")]
 pub async fn get_category(Path(id): Path<i64>) -> ViewResult<Response> {
     let manager = Category::objects();
-    match manager.get(id).first().await? {
+    match manager.get(id).first().await?.filter(|c| !c.is_deleted()) {
         Some(category) => {
             let response: CategoryResponse = category.into();
             Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
@@ -46,11 +55,30 @@ pub async fn get_category(Path(id): Path<i64>) -> ViewResult<Response> {
 /// POST /categories/
 #[post("/", name = "categories_create")]
 pub async fn create_category_view(
+    request: Request,
     Json(create_req): Json<CreateCategoryRequest>,
 ) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, create_category(create_req, actor)).await
+}
+
+async fn create_category(create_req: CreateCategoryRequest, actor: String) -> ViewResult<Response> {
     // Validate request
     create_req.validate()?;
 
+    let manager = Category::objects();
+
+    // New categories go to the end unless a position is given
+    let sort_order = match create_req.sort_order {
+        Some(order) => order,
+        None => active_categories()
+            .await?
+            .iter()
+            .map(|c| c.sort_order + 1)
+            .max()
+            .unwrap_or(0),
+    };
+
     // Create category
     let now = Utc::now();
     let category = Category {
@@ -59,11 +87,25 @@ pub async fn create_category_view(
         category_type: create_req.category_type.to_string(),
         icon: create_req.icon,
         color: create_req.color,
+        sort_order,
+        deleted_at: None,
         created_at: now,
     };
 
-    let manager = Category::objects();
     let created = manager.create(&category).await?;
+    if let Some(id) = created.id {
+        changes::record(SyncEntity::Category, id, ChangeAction::Upsert).await?;
+        let new = audit::snapshot(&created);
+        audit::record(
+            AuditEntity::Category,
+            id,
+            AuditAction::Create,
+            &actor,
+            None,
+            Some(&new),
+        )
+        .await?;
+    }
 
     let response: CategoryResponse = created.into();
     Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
@@ -74,15 +116,27 @@ pub async fn create_category_view(
 /// PUT /categories/{id}/
 #[put("/{id}/", name = "categories_update")]
 pub async fn update_category_view(
+    request: Request,
     Path(id): Path<i64>,
     Json(update_req): Json<UpdateCategoryRequest>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, update_category(id, update_req, actor)).await
+}
+
+async fn update_category(
+    id: i64,
+    update_req: UpdateCategoryRequest,
+    actor: String,
 ) -> ViewResult<Response> {
     // Validate request
     update_req.validate()?;
 
     let manager = Category::objects();
-    match manager.get(id).first().await? {
+    match manager.get(id).first().await?.filter(|c| !c.is_deleted()) {
         Some(mut category) => {
+            let old = audit::snapshot(&category);
+
             // Apply updates
             if let Some(n) = update_req.name {
                 category.name = n;
@@ -93,8 +147,22 @@ pub async fn update_category_view(
             if let Some(c) = update_req.color {
                 category.color = Some(c);
             }
+            if let Some(o) = update_req.sort_order {
+                category.sort_order = o;
+            }
 
             let updated = manager.update(&category).await?;
+            changes::record(SyncEntity::Category, id, ChangeAction::Upsert).await?;
+            let new = audit::snapshot(&updated);
+            audit::record(
+                AuditEntity::Category,
+                id,
+                AuditAction::Update,
+                &actor,
+                Some(&old),
+                Some(&new),
+            )
+            .await?;
             let response: CategoryResponse = updated.into();
             Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
         }
@@ -104,15 +172,36 @@ pub async fn update_category_view(
     }
 }
 
-/// Delete a category
+/// Move a category to the trash
 ///
 /// DELETE /categories/{id}/
 #[delete("/{id}/", name = "categories_delete")]
-pub async fn delete_category_view(Path(id): Path<i64>) -> ViewResult<Response> {
+pub async fn delete_category_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, delete_category(id, actor)).await
+}
+
+async fn delete_category(id: i64, actor: String) -> ViewResult<Response> {
     let manager = Category::objects();
-    match manager.delete(id).await {
-        Ok(_) => Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new())),
-        Err(_) => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+    match manager.get(id).first().await?.filter(|c| !c.is_deleted()) {
+        Some(mut category) => {
+            let old = audit::snapshot(&category);
+            category.deleted_at = Some(Utc::now());
+            let deleted = manager.update(&category).await?;
+            changes::record(SyncEntity::Category, id, ChangeAction::Delete).await?;
+            let new = audit::snapshot(&deleted);
+            audit::record(
+                AuditEntity::Category,
+                id,
+                AuditAction::Delete,
+                &actor,
+                Some(&old),
+                Some(&new),
+            )
+            .await?;
+            Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+        }
+        None => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
             format!(r#"{{"error": "Category with id {} not found"}}"#, id).into_bytes(),
         )),
     }
@@ -0,0 +1,14 @@
+//! Donations app for ふるさと納税 (furusato nozei) tracking
+//!
+//! Tracks donations to municipalities, ワンストップ特例 application status and
+//! receipts, and compares the yearly total against an estimated limit.
+
+pub mod models;
+pub mod serializers;
+pub mod summary;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
@@ -0,0 +1,108 @@
+//! Donation model definitions
+
+use chrono::{DateTime, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of municipalities for the ワンストップ特例 (one-stop special exception)
+pub const ONE_STOP_MUNICIPALITY_LIMIT: usize = 5;
+
+/// Status of the ワンストップ特例 application for a donation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OneStopStatus {
+    /// Not using the one-stop exception (deducted via 確定申告)
+    NotApplicable,
+    /// Application form not sent yet
+    NotSent,
+    /// Application form sent to the municipality
+    Sent,
+}
+
+impl std::fmt::Display for OneStopStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OneStopStatus::NotApplicable => write!(f, "not_applicable"),
+            OneStopStatus::NotSent => write!(f, "not_sent"),
+            OneStopStatus::Sent => write!(f, "sent"),
+        }
+    }
+}
+
+impl std::str::FromStr for OneStopStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "not_applicable" => Ok(OneStopStatus::NotApplicable),
+            "not_sent" => Ok(OneStopStatus::NotSent),
+            "sent" => Ok(OneStopStatus::Sent),
+            _ => Err(format!("Invalid one-stop status: {}", s)),
+        }
+    }
+}
+
+/// Furusato nozei donation linked to an expense transaction
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "donations", table_name = "donations")]
+pub struct Donation {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Transaction ID (foreign key)
+    pub transaction_id: i64,
+
+    /// Municipality donated to (e.g., "北海道上士幌町")
+    #[field(max_length = 100)]
+    pub municipality: String,
+
+    /// Date of the donation
+    pub donation_date: DateTime<Utc>,
+
+    /// Amount in yen
+    pub amount: i64,
+
+    /// ワンストップ特例 application status - stored as string
+    #[field(max_length = 20)]
+    pub one_stop_status: String,
+
+    /// Whether the 受領証明書 (donation receipt) has arrived
+    pub receipt_received: bool,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    #[field(auto_now = true)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Donation {
+    /// Gets the one-stop status as enum
+    pub fn get_one_stop_status(&self) -> OneStopStatus {
+        self.one_stop_status
+            .parse()
+            .unwrap_or(OneStopStatus::NotSent)
+    }
+}
+
+/// Estimated yearly donation limit (控除上限額の目安)
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "donations", table_name = "donation_limits")]
+pub struct DonationLimit {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Calendar year the limit applies to
+    pub year: i32,
+
+    /// Estimated limit in yen
+    pub estimated_limit: i64,
+
+    /// Last update timestamp
+    #[field(auto_now = true)]
+    pub updated_at: DateTime<Utc>,
+}
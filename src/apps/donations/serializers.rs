@@ -0,0 +1,146 @@
+//! Donation serializers for request/response handling
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::models::{Donation, DonationLimit, OneStopStatus};
+
+/// Request payload for recording a donation
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateDonationRequest {
+    /// Expense transaction the donation was paid with
+    pub transaction_id: i64,
+    /// Municipality name (required, 1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Municipality must be between 1 and 100 characters"))]
+    pub municipality: String,
+    /// Donation date (defaults to the transaction date)
+    pub donation_date: Option<DateTime<Utc>>,
+    /// Amount in yen (defaults to the transaction amount)
+    #[validate(range(min = 1, message = "Amount must be positive"))]
+    pub amount: Option<i64>,
+    /// ワンストップ特例 application status
+    pub one_stop_status: OneStopStatus,
+    /// Whether the receipt has arrived
+    #[serde(default)]
+    pub receipt_received: bool,
+}
+
+/// Request payload for updating a donation
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UpdateDonationRequest {
+    /// Municipality name (optional, 1-100 characters if provided)
+    #[validate(length(min = 1, max = 100, message = "Municipality must be between 1 and 100 characters"))]
+    pub municipality: Option<String>,
+    /// Donation date (optional)
+    pub donation_date: Option<DateTime<Utc>>,
+    /// Amount in yen (optional, must be positive if provided)
+    #[validate(range(min = 1, message = "Amount must be positive"))]
+    pub amount: Option<i64>,
+    /// ワンストップ特例 application status (optional)
+    pub one_stop_status: Option<OneStopStatus>,
+    /// Whether the receipt has arrived (optional)
+    pub receipt_received: Option<bool>,
+}
+
+/// Request payload for setting the estimated limit of a year
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct SetDonationLimitRequest {
+    /// Estimated limit in yen
+    #[validate(range(min = 0, message = "Limit must not be negative"))]
+    pub estimated_limit: i64,
+}
+
+/// Response payload for a donation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonationResponse {
+    pub id: i64,
+    pub transaction_id: i64,
+    pub municipality: String,
+    pub donation_date: String,
+    pub amount: i64,
+    pub one_stop_status: OneStopStatus,
+    pub receipt_received: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<Donation> for DonationResponse {
+    fn from(donation: Donation) -> Self {
+        let one_stop_status = donation.get_one_stop_status();
+        let donation_date = donation.donation_date.format("%Y-%m-%d").to_string();
+        let created_at = donation.created_at.to_rfc3339();
+        let updated_at = donation.updated_at.to_rfc3339();
+
+        Self {
+            id: donation.id.unwrap_or(0),
+            transaction_id: donation.transaction_id,
+            municipality: donation.municipality,
+            donation_date,
+            amount: donation.amount,
+            one_stop_status,
+            receipt_received: donation.receipt_received,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+/// Response wrapper for list of donations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonationListResponse {
+    pub count: usize,
+    pub results: Vec<DonationResponse>,
+}
+
+impl DonationListResponse {
+    pub fn new(donations: Vec<Donation>) -> Self {
+        let results: Vec<DonationResponse> = donations.into_iter().map(Into::into).collect();
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
+
+/// Response payload for a yearly estimated limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonationLimitResponse {
+    pub year: i32,
+    pub estimated_limit: i64,
+}
+
+impl From<DonationLimit> for DonationLimitResponse {
+    fn from(limit: DonationLimit) -> Self {
+        Self {
+            year: limit.year,
+            estimated_limit: limit.estimated_limit,
+        }
+    }
+}
+
+/// Yearly donation summary response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonationSummaryResponse {
+    pub year: i32,
+    pub total_amount: i64,
+    pub donation_count: usize,
+    /// Configured estimated limit, if set for the year
+    pub estimated_limit: Option<i64>,
+    /// Limit minus total (negative when over the limit)
+    pub remaining: Option<i64>,
+    pub over_limit: bool,
+    /// Number of distinct municipalities donated to
+    pub municipality_count: usize,
+    /// Number of distinct municipalities using the one-stop exception
+    pub one_stop_municipality_count: usize,
+    /// Maximum municipalities for the one-stop exception
+    pub one_stop_municipality_limit: usize,
+    /// False once more municipalities than the limit were donated to
+    pub one_stop_eligible: bool,
+    /// Donations whose one-stop form is not sent yet
+    pub one_stop_forms_pending: usize,
+    /// Donations whose receipt has not arrived yet
+    pub receipts_pending: usize,
+    pub donations: Vec<DonationResponse>,
+}
@@ -0,0 +1,129 @@
+//! Yearly donation totals and the ワンストップ特例 checks
+//!
+//! The one-stop exception only applies when the year's donations went to
+//! at most [`ONE_STOP_MUNICIPALITY_LIMIT`] municipalities, counting every
+//! municipality whether or not a form was sent to it.
+
+use chrono::Datelike;
+use std::collections::HashSet;
+
+use super::models::{Donation, OneStopStatus, ONE_STOP_MUNICIPALITY_LIMIT};
+use super::serializers::DonationSummaryResponse;
+
+/// Keeps the donations made in `year`
+pub fn in_year(donations: Vec<Donation>, year: i32) -> Vec<Donation> {
+    donations
+        .into_iter()
+        .filter(|d| d.donation_date.year() == year)
+        .collect()
+}
+
+/// Summarizes the donations of `year` against the estimated limit
+pub fn summarize(
+    year: i32,
+    donations: Vec<Donation>,
+    estimated_limit: Option<i64>,
+) -> DonationSummaryResponse {
+    let total_amount: i64 = donations.iter().map(|d| d.amount).sum();
+    let remaining = estimated_limit.map(|limit| limit - total_amount);
+
+    let municipality_count = donations
+        .iter()
+        .map(|d| d.municipality.as_str())
+        .collect::<HashSet<_>>()
+        .len();
+    let one_stop_municipality_count = donations
+        .iter()
+        .filter(|d| d.get_one_stop_status() != OneStopStatus::NotApplicable)
+        .map(|d| d.municipality.as_str())
+        .collect::<HashSet<_>>()
+        .len();
+
+    let one_stop_forms_pending = donations
+        .iter()
+        .filter(|d| d.get_one_stop_status() == OneStopStatus::NotSent)
+        .count();
+    let receipts_pending = donations.iter().filter(|d| !d.receipt_received).count();
+
+    DonationSummaryResponse {
+        year,
+        total_amount,
+        donation_count: donations.len(),
+        estimated_limit,
+        remaining,
+        over_limit: remaining.is_some_and(|r| r < 0),
+        municipality_count,
+        one_stop_municipality_count,
+        one_stop_municipality_limit: ONE_STOP_MUNICIPALITY_LIMIT,
+        one_stop_eligible: municipality_count <= ONE_STOP_MUNICIPALITY_LIMIT,
+        one_stop_forms_pending,
+        receipts_pending,
+        donations: donations.into_iter().map(Into::into).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn donation(municipality: &str, year: i32, amount: i64, status: OneStopStatus) -> Donation {
+        let date = Utc.with_ymd_and_hms(year, 6, 1, 0, 0, 0).unwrap();
+        Donation {
+            id: None,
+            transaction_id: 1,
+            municipality: municipality.to_string(),
+            donation_date: date,
+            amount,
+            one_stop_status: status.to_string(),
+            receipt_received: status == OneStopStatus::Sent,
+            created_at: date,
+            updated_at: date,
+        }
+    }
+
+    #[test]
+    fn test_year_totals() {
+        let donations = vec![
+            donation("北海道上士幌町", 2026, 30_000, OneStopStatus::Sent),
+            donation("北海道上士幌町", 2026, 10_000, OneStopStatus::NotSent),
+            donation("宮崎県都城市", 2026, 20_000, OneStopStatus::NotApplicable),
+            donation("佐賀県唐津市", 2025, 50_000, OneStopStatus::Sent),
+        ];
+
+        let summary = summarize(2026, in_year(donations, 2026), Some(55_000));
+        assert_eq!(summary.total_amount, 60_000);
+        assert_eq!(summary.donation_count, 3);
+        assert_eq!(summary.remaining, Some(-5_000));
+        assert!(summary.over_limit);
+        assert_eq!(summary.municipality_count, 2);
+        assert_eq!(summary.one_stop_municipality_count, 1);
+        assert_eq!(summary.one_stop_forms_pending, 1);
+        assert_eq!(summary.receipts_pending, 2);
+
+        let summary = summarize(2024, Vec::new(), None);
+        assert_eq!(summary.total_amount, 0);
+        assert_eq!(summary.remaining, None);
+        assert!(!summary.over_limit);
+    }
+
+    #[test]
+    fn test_one_stop_municipality_limit() {
+        let five = || -> Vec<Donation> {
+            let mut donations: Vec<Donation> = (1..=5)
+                .map(|i| donation(&format!("町{}", i), 2026, 10_000, OneStopStatus::Sent))
+                .collect();
+            // A second donation to the same municipality does not count again
+            donations.push(donation("町1", 2026, 10_000, OneStopStatus::Sent));
+            donations
+        };
+        assert!(summarize(2026, five(), None).one_stop_eligible);
+
+        // A sixth municipality rules the exception out, even without a form
+        let mut donations = five();
+        donations.push(donation("町6", 2026, 10_000, OneStopStatus::NotApplicable));
+        let summary = summarize(2026, donations, None);
+        assert_eq!(summary.municipality_count, 6);
+        assert!(!summary.one_stop_eligible);
+    }
+}
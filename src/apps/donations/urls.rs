@@ -0,0 +1,18 @@
+//! URL routing for donations app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the donations app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_donations)
+        .endpoint(views::donation_summary)
+        .endpoint(views::get_donation_limit)
+        .endpoint(views::set_donation_limit)
+        .endpoint(views::get_donation)
+        .endpoint(views::create_donation_view)
+        .endpoint(views::update_donation_view)
+        .endpoint(views::delete_donation_view)
+}
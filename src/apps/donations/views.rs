@@ -0,0 +1,302 @@
+//! Donation views (API endpoints)
+
+use chrono::{Datelike, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Query, Request, Response, StatusCode};
+use serde::Deserialize;
+use std::collections::HashSet;
+use validator::Validate;
+
+use crate::apps::idempotency::idempotent;
+use crate::apps::transactions::models::{Transaction, TransactionType};
+use crate::apps::transactions::views::active_transactions;
+use super::models::{Donation, DonationLimit};
+use super::serializers::{
+    CreateDonationRequest, DonationLimitResponse, DonationListResponse, DonationResponse,
+    SetDonationLimitRequest, UpdateDonationRequest,
+};
+use super::summary;
+
+/// Query parameters for donation list and summary
+#[derive(Debug, Deserialize)]
+pub struct DonationYearQuery {
+    pub year: Option<i32>,
+}
+
+/// Returns the donations whose transaction is not in the trash
+async fn active_donations() -> ViewResult<Vec<Donation>> {
+    let transaction_ids: HashSet<i64> = active_transactions()
+        .await?
+        .into_iter()
+        .filter_map(|t| t.id)
+        .collect();
+    let donations = Donation::objects().all().all().await?;
+    Ok(donations
+        .into_iter()
+        .filter(|d| transaction_ids.contains(&d.transaction_id))
+        .collect())
+}
+
+/// Returns the donation with `id` unless its transaction is in the trash
+async fn active_donation(id: i64) -> ViewResult<Option<Donation>> {
+    let Some(donation) = Donation::objects().get(id).first().await? else {
+        return Ok(None);
+    };
+    let active = Transaction::objects()
+        .get(donation.transaction_id)
+        .first()
+        .await?
+        .is_some_and(|t| !t.is_deleted());
+    Ok(active.then_some(donation))
+}
+
+/// Returns the donations made in a year
+async fn donations_for_year(year: i32) -> ViewResult<Vec<Donation>> {
+    Ok(summary::in_year(active_donations().await?, year))
+}
+
+/// Returns the configured limit for a year, if any
+async fn limit_for_year(year: i32) -> ViewResult<Option<DonationLimit>> {
+    let limits = DonationLimit::objects().all().all().await?;
+    Ok(limits.into_iter().find(|l| l.year == year))
+}
+
+/// List donations, optionally for a single year
+///
+/// GET /donations/?year=2026
+#[get("/", name = "donations_list")]
+pub async fn list_donations(Query(params): Query<DonationYearQuery>) -> ViewResult<Response> {
+    let donations = match params.year {
+        Some(year) => donations_for_year(year).await?,
+        None => active_donations().await?,
+    };
+
+    let response = DonationListResponse::new(donations);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get the yearly summary against the estimated limit
+///
+/// GET /donations/summary/?year=2026
+#[get("/summary/", name = "donations_summary")]
+pub async fn donation_summary(Query(params): Query<DonationYearQuery>) -> ViewResult<Response> {
+    let year = params.year.unwrap_or_else(|| Utc::now().year());
+
+    let donations = donations_for_year(year).await?;
+    let estimated_limit = limit_for_year(year).await?.map(|l| l.estimated_limit);
+    let response = summary::summarize(year, donations, estimated_limit);
+
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get the estimated limit of a year
+///
+/// GET /donations/limits/{year}/
+#[get("/limits/{year}/", name = "donations_limit_get")]
+pub async fn get_donation_limit(Path(year): Path<i32>) -> ViewResult<Response> {
+    match limit_for_year(year).await? {
+        Some(limit) => {
+            let response: DonationLimitResponse = limit.into();
+            Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+        }
+        None => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "No donation limit configured for {}"}}"#, year).into_bytes(),
+        )),
+    }
+}
+
+/// Set the estimated limit of a year
+///
+/// PUT /donations/limits/{year}/
+#[put("/limits/{year}/", name = "donations_limit_set")]
+pub async fn set_donation_limit(
+    request: Request,
+    Path(year): Path<i32>,
+    Json(set_req): Json<SetDonationLimitRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, save_donation_limit(year, set_req)).await
+}
+
+async fn save_donation_limit(year: i32, set_req: SetDonationLimitRequest) -> ViewResult<Response> {
+    // Validate request
+    set_req.validate()?;
+
+    let manager = DonationLimit::objects();
+    let saved = match limit_for_year(year).await? {
+        Some(mut limit) => {
+            limit.estimated_limit = set_req.estimated_limit;
+            limit.updated_at = Utc::now();
+            manager.update(&limit).await?
+        }
+        None => {
+            let limit = DonationLimit {
+                id: None,
+                year,
+                estimated_limit: set_req.estimated_limit,
+                updated_at: Utc::now(),
+            };
+            manager.create(&limit).await?
+        }
+    };
+
+    let response: DonationLimitResponse = saved.into();
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get a single donation by ID
+///
+/// GET /donations/{id}/
+#[get("/{id}/", name = "donations_get")]
+pub async fn get_donation(Path(id): Path<i64>) -> ViewResult<Response> {
+    match active_donation(id).await? {
+        Some(donation) => {
+            let response: DonationResponse = donation.into();
+            Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+        }
+        None => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Donation with id {} not found"}}"#, id).into_bytes(),
+        )),
+    }
+}
+
+/// Record a donation for an expense transaction
+///
+/// POST /donations/
+#[post("/", name = "donations_create")]
+pub async fn create_donation_view(
+    request: Request,
+    Json(create_req): Json<CreateDonationRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_donation(create_req)).await
+}
+
+async fn create_donation(create_req: CreateDonationRequest) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+
+    let transaction_id = create_req.transaction_id;
+    let transaction = Transaction::objects()
+        .get(transaction_id)
+        .first()
+        .await?
+        .filter(|t| !t.is_deleted());
+    let transaction = match transaction {
+        Some(t) if t.get_transaction_type() == TransactionType::Expense => t,
+        Some(_) => {
+            return Ok(Response::new(StatusCode::BAD_REQUEST).with_body(
+                format!(
+                    r#"{{"error": "Transaction {} is not an expense"}}"#,
+                    transaction_id
+                )
+                .into_bytes(),
+            ));
+        }
+        None => {
+            return Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+                format!(r#"{{"error": "Transaction with id {} not found"}}"#, transaction_id)
+                    .into_bytes(),
+            ));
+        }
+    };
+
+    let now = Utc::now();
+    let donation = Donation {
+        id: None,
+        transaction_id,
+        municipality: create_req.municipality,
+        donation_date: create_req
+            .donation_date
+            .unwrap_or(transaction.transaction_date),
+        amount: create_req.amount.unwrap_or(transaction.amount),
+        one_stop_status: create_req.one_stop_status.to_string(),
+        receipt_received: create_req.receipt_received,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let manager = Donation::objects();
+    let created = manager.create(&donation).await?;
+
+    let response: DonationResponse = created.into();
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Update a donation
+///
+/// PUT /donations/{id}/
+#[put("/{id}/", name = "donations_update")]
+pub async fn update_donation_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateDonationRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, update_donation(id, update_req)).await
+}
+
+async fn update_donation(id: i64, update_req: UpdateDonationRequest) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = Donation::objects();
+    match active_donation(id).await? {
+        Some(mut donation) => {
+            // Apply updates
+            if let Some(m) = update_req.municipality {
+                donation.municipality = m;
+            }
+            if let Some(date) = update_req.donation_date {
+                donation.donation_date = date;
+            }
+            if let Some(a) = update_req.amount {
+                donation.amount = a;
+            }
+            if let Some(s) = update_req.one_stop_status {
+                donation.one_stop_status = s.to_string();
+            }
+            if let Some(r) = update_req.receipt_received {
+                donation.receipt_received = r;
+            }
+            donation.updated_at = Utc::now();
+
+            let updated = manager.update(&donation).await?;
+            let response: DonationResponse = updated.into();
+            Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+        }
+        None => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Donation with id {} not found"}}"#, id).into_bytes(),
+        )),
+    }
+}
+
+/// Delete a donation
+///
+/// DELETE /donations/{id}/
+#[delete("/{id}/", name = "donations_delete")]
+pub async fn delete_donation_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    idempotent(&request, delete_donation(id)).await
+}
+
+async fn delete_donation(id: i64) -> ViewResult<Response> {
+    let manager = Donation::objects();
+    match manager.delete(id).await {
+        Ok(_) => Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new())),
+        Err(_) => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Donation with id {} not found"}}"#, id).into_bytes(),
+        )),
+    }
+}
+
+/// Deletes the donations recorded for a transaction, if any
+pub async fn delete_for_transaction(transaction_id: i64) -> ViewResult<()> {
+    let manager = Donation::objects();
+    for donation in manager.all().all().await? {
+        if donation.transaction_id == transaction_id
+            && let Some(id) = donation.id
+        {
+            manager.delete(id).await?;
+        }
+    }
+    Ok(())
+}
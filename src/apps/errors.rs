@@ -0,0 +1,11 @@
+//! JSON error responses shared by the app views
+
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::{Response, StatusCode};
+
+/// Responds with `status` and a `{"error": message}` body
+pub fn error_response(status: StatusCode, message: &str) -> ViewResult<Response> {
+    let body = serde_json::json!({ "error": message });
+    Ok(Response::new(status).with_body(json::to_vec(&body)?))
+}
@@ -0,0 +1,18 @@
+//! Forecast app for projecting account balances
+//!
+//! Recurring items (給与, 家賃, subscriptions) are registered with a
+//! frequency. The forecast starts from today's balances and adds their
+//! occurrences, the card statements still to be withdrawn and scheduled
+//! loan repayments, optionally spreading the recent average spending of
+//! the other categories over every day.
+
+pub mod models;
+pub mod projection;
+pub mod recurrence;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
@@ -0,0 +1,118 @@
+//! Recurring item model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub use crate::shared::types::TransactionType;
+
+/// How often a recurring item repeats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Frequency {
+    /// Every 7 days from the start date
+    Weekly,
+    /// On the start date's day every month (clamped to the month's end)
+    #[default]
+    Monthly,
+    /// On the start date's month and day every year
+    Yearly,
+}
+
+impl std::fmt::Display for Frequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Frequency::Weekly => write!(f, "weekly"),
+            Frequency::Monthly => write!(f, "monthly"),
+            Frequency::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+impl std::str::FromStr for Frequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "weekly" => Ok(Frequency::Weekly),
+            "monthly" => Ok(Frequency::Monthly),
+            "yearly" => Ok(Frequency::Yearly),
+            _ => Err(format!("Invalid frequency: {}", s)),
+        }
+    }
+}
+
+/// Income or expense that repeats on a schedule
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "forecast", table_name = "recurring_items")]
+pub struct RecurringItem {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Item name (e.g., "給与", "家賃")
+    #[field(max_length = 100)]
+    pub name: String,
+
+    /// Account the money moves in or out of (foreign key)
+    pub account_id: i64,
+
+    /// Category the item is recorded in (foreign key, optional)
+    #[field(null = true)]
+    pub category_id: Option<i64>,
+
+    /// Income or expense - stored as string
+    #[field(max_length = 20)]
+    pub transaction_type: String,
+
+    /// Amount in yen (positive value)
+    pub amount: i64,
+
+    /// Weekly, monthly or yearly - stored as string
+    #[field(max_length = 20)]
+    pub frequency: String,
+
+    /// First occurrence
+    pub start_date: NaiveDate,
+
+    /// Last day an occurrence may fall on
+    #[field(null = true)]
+    pub end_date: Option<NaiveDate>,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl RecurringItem {
+    /// Gets the transaction type as enum
+    pub fn get_transaction_type(&self) -> TransactionType {
+        self.transaction_type
+            .parse()
+            .unwrap_or(TransactionType::Expense)
+    }
+
+    /// Gets the frequency as enum
+    pub fn get_frequency(&self) -> Frequency {
+        self.frequency.parse().unwrap_or_default()
+    }
+
+    /// Amount with income positive and expenses negative
+    pub fn signed_amount(&self) -> i64 {
+        match self.get_transaction_type() {
+            TransactionType::Income => self.amount,
+            TransactionType::Expense => -self.amount,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_frequency_wire_values() {
+        assert_wire_values(&[Frequency::Weekly, Frequency::Monthly, Frequency::Yearly]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Projecting daily balances
+
+use chrono::{Days, NaiveDate};
+
+/// Returns the balance at the end of each of `days` days from `first`
+///
+/// `flows` are dated amounts (positive in, negative out); flows before
+/// `first` are ignored. `daily_spending` is taken out every day, rounded
+/// so that the running total stays exact.
+pub fn project(
+    opening: i64,
+    first: NaiveDate,
+    days: u32,
+    flows: &[(NaiveDate, i64)],
+    daily_spending: f64,
+) -> Vec<i64> {
+    let mut balance = opening;
+    (0..days)
+        .map(|index| {
+            let date = first + Days::new(u64::from(index));
+            balance += flows
+                .iter()
+                .filter(|(d, _)| *d == date)
+                .map(|(_, amount)| amount)
+                .sum::<i64>();
+            let spent_before = (daily_spending * f64::from(index)).round() as i64;
+            let spent_through = (daily_spending * f64::from(index + 1)).round() as i64;
+            balance -= spent_through - spent_before;
+            balance
+        })
+        .collect()
+}
+
+/// Returns the indices of days after which the balance rises again
+pub fn low_points(balances: &[i64]) -> Vec<usize> {
+    (0..balances.len().saturating_sub(1))
+        .filter(|&i| balances[i + 1] > balances[i] && (i == 0 || balances[i] <= balances[i - 1]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_spreads_spending_and_adds_flows() {
+        let first = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let payday = NaiveDate::from_ymd_opt(2026, 1, 3).unwrap();
+        let balances = project(10_000, first, 4, &[(payday, 5_000)], 1_000.5);
+        assert_eq!(balances, [8_999, 7_999, 11_998, 10_998]);
+    }
+
+    #[test]
+    fn test_low_points_are_days_before_a_rise() {
+        assert_eq!(low_points(&[100, 50, 200, 150, 150, 300, 250]), [1, 4]);
+        assert!(low_points(&[300, 200, 100]).is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+//! Occurrences of recurring items
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::apps::cards::billing::{day_in_month, first_of_month};
+use super::models::Frequency;
+
+/// Returns the `index`-th occurrence (0-based) of a schedule starting on
+/// `start`
+///
+/// Monthly and yearly occurrences keep the start date's day, or fall on
+/// the last day of shorter months.
+fn nth(frequency: Frequency, start: NaiveDate, index: u32) -> Option<NaiveDate> {
+    match frequency {
+        Frequency::Weekly => start.checked_add_days(Days::new(u64::from(index) * 7)),
+        Frequency::Monthly => first_of_month(start)
+            .checked_add_months(Months::new(index))
+            .map(|first| day_in_month(first, start.day())),
+        Frequency::Yearly => first_of_month(start)
+            .checked_add_months(Months::new(index.checked_mul(12)?))
+            .map(|first| day_in_month(first, start.day())),
+    }
+}
+
+/// Returns the occurrences from `from` through `to`, stopping after `end`
+pub fn occurrences(
+    frequency: Frequency,
+    start: NaiveDate,
+    end: Option<NaiveDate>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<NaiveDate> {
+    let last = end.map_or(to, |end| end.min(to));
+    (0..)
+        .map_while(|index| nth(frequency, start, index))
+        .take_while(|&date| date <= last)
+        .filter(|&date| date >= from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_monthly_clamps_to_month_end() {
+        let dates = occurrences(
+            Frequency::Monthly,
+            date(2026, 1, 31),
+            None,
+            date(2026, 2, 1),
+            date(2026, 4, 30),
+        );
+        assert_eq!(
+            dates,
+            [date(2026, 2, 28), date(2026, 3, 31), date(2026, 4, 30)]
+        );
+    }
+
+    #[test]
+    fn test_weekly_stops_at_end_date() {
+        let dates = occurrences(
+            Frequency::Weekly,
+            date(2026, 1, 2),
+            Some(date(2026, 1, 20)),
+            date(2026, 1, 5),
+            date(2026, 3, 1),
+        );
+        assert_eq!(dates, [date(2026, 1, 9), date(2026, 1, 16)]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Forecast serializers for request/response handling
+
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::models::{Frequency, RecurringItem};
+use super::recurrence::occurrences;
+use crate::apps::transactions::models::TransactionType;
+
+/// Where a projected cash flow comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastSource {
+    /// A recurring item (給与, 家賃, subscriptions)
+    Recurring,
+    /// A card statement withdrawn from the payment account
+    CardPayment,
+    /// A scheduled loan repayment
+    Loan,
+}
+
+/// Request payload for creating a recurring item
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateRecurringItemRequest {
+    /// Item name (1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    /// Account the money moves in or out of
+    pub account_id: i64,
+    /// Category the item is recorded in; its spending is then left out of
+    /// the variable spending average
+    #[serde(default)]
+    pub category_id: Option<i64>,
+    pub transaction_type: TransactionType,
+    /// Amount in yen (must be positive)
+    #[validate(range(min = 1, message = "Amount must be positive"))]
+    pub amount: i64,
+    #[serde(default)]
+    pub frequency: Frequency,
+    /// First occurrence; later ones follow the frequency
+    pub start_date: NaiveDate,
+    /// Last day an occurrence may fall on
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Request payload for updating a recurring item
+///
+/// The schedule is fixed; end the item and create a new one to change it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateRecurringItemRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    #[validate(range(min = 1, message = "Amount must be positive"))]
+    pub amount: Option<i64>,
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Response payload for a recurring item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringItemResponse {
+    pub id: i64,
+    pub name: String,
+    pub account_id: i64,
+    pub category_id: Option<i64>,
+    pub transaction_type: TransactionType,
+    pub amount: i64,
+    pub frequency: Frequency,
+    /// Dates as `YYYY-MM-DD`
+    pub start_date: String,
+    pub end_date: Option<String>,
+    /// Next occurrence after today, if any
+    pub next_date: Option<String>,
+    pub created_at: String,
+}
+
+/// Response payload for recurring item list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringItemListResponse {
+    pub count: usize,
+    pub results: Vec<RecurringItemResponse>,
+}
+
+/// A projected cash flow on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastFlowResponse {
+    /// Date as `YYYY-MM-DD`
+    pub date: String,
+    pub account_id: i64,
+    pub source: ForecastSource,
+    pub description: String,
+    /// Positive for money in, negative for money out
+    pub amount: i64,
+}
+
+/// Average monthly spending of a category, spread over the forecast days
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableSpendingResponse {
+    pub account_id: i64,
+    pub category_id: i64,
+    pub category_name: String,
+    pub monthly_average: i64,
+}
+
+/// Projected balance of an account at the end of a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastDayResponse {
+    /// Date as `YYYY-MM-DD`
+    pub date: String,
+    pub balance: i64,
+}
+
+/// Projection of one account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastAccountResponse {
+    pub account_id: i64,
+    pub account_name: String,
+    pub current_balance: i64,
+    pub ending_balance: i64,
+    /// Lowest projected balance and its first date
+    pub lowest: ForecastDayResponse,
+    /// First day the balance is projected below zero
+    pub below_zero_on: Option<String>,
+    /// Days after which the balance rises again (e.g. the day before payday)
+    pub low_points: Vec<ForecastDayResponse>,
+    pub days: Vec<ForecastDayResponse>,
+}
+
+/// Cash flow forecast response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForecastResponse {
+    /// First and last projected day as `YYYY-MM-DD`
+    pub from: String,
+    pub to: String,
+    /// Months the variable spending is averaged over (0 when not used)
+    pub average_months: u32,
+    pub accounts: Vec<ForecastAccountResponse>,
+    /// Scheduled flows, in date order
+    pub flows: Vec<ForecastFlowResponse>,
+    pub variable_spending: Vec<VariableSpendingResponse>,
+}
+
+impl RecurringItemResponse {
+    pub fn new(item: &RecurringItem, today: NaiveDate) -> Self {
+        // Within a year and a day, every frequency occurs at least once
+        let from = (today + Days::new(1)).max(item.start_date);
+        let next_date = occurrences(
+            item.get_frequency(),
+            item.start_date,
+            item.end_date,
+            from,
+            from + Days::new(366),
+        )
+        .first()
+        .map(|d| d.format("%Y-%m-%d").to_string());
+
+        Self {
+            id: item.id.unwrap_or(0),
+            name: item.name.clone(),
+            account_id: item.account_id,
+            category_id: item.category_id,
+            transaction_type: item.get_transaction_type(),
+            amount: item.amount,
+            frequency: item.get_frequency(),
+            start_date: item.start_date.format("%Y-%m-%d").to_string(),
+            end_date: item.end_date.map(|d| d.format("%Y-%m-%d").to_string()),
+            next_date,
+            created_at: item.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl RecurringItemListResponse {
+    pub fn new(items: &[RecurringItem], today: NaiveDate) -> Self {
+        let results: Vec<RecurringItemResponse> = items
+            .iter()
+            .map(|item| RecurringItemResponse::new(item, today))
+            .collect();
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
+
+impl ForecastDayResponse {
+    pub fn new(date: NaiveDate, balance: i64) -> Self {
+        Self {
+            date: date.format("%Y-%m-%d").to_string(),
+            balance,
+        }
+    }
+}
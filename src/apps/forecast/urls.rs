@@ -0,0 +1,16 @@
+//! URL routing for forecast app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the forecast app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::get_forecast)
+        .endpoint(views::list_recurring_items)
+        .endpoint(views::get_recurring_item)
+        .endpoint(views::create_recurring_item_view)
+        .endpoint(views::update_recurring_item_view)
+        .endpoint(views::delete_recurring_item_view)
+}
@@ -0,0 +1,505 @@
+//! Forecast views (API endpoints)
+
+use chrono::{Days, Months, NaiveDate, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Query, Request, Response, StatusCode};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use validator::Validate;
+
+use crate::apps::accounts::models::Account;
+use crate::apps::accounts::serializers::AccountResponse;
+use crate::apps::accounts::views::account_exists;
+use crate::apps::cards::billing::first_of_month;
+use crate::apps::cards::payments::card_statements;
+use crate::apps::cards::card_payments;
+use crate::apps::categories::models::Category;
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::installments::{schedules, Schedules};
+use crate::apps::loans::amortization::schedule;
+use crate::apps::loans::models::{Loan, LoanPrepayment, LoanRepayment};
+use crate::apps::transactions::models::{Transaction, TransactionType};
+use crate::apps::transactions::views::active_transactions;
+use super::models::RecurringItem;
+use super::projection::{low_points, project};
+use super::recurrence::occurrences;
+use super::serializers::{
+    CreateRecurringItemRequest, ForecastAccountResponse, ForecastDayResponse, ForecastFlowResponse,
+    ForecastResponse, ForecastSource, RecurringItemListResponse, RecurringItemResponse,
+    UpdateRecurringItemRequest, VariableSpendingResponse,
+};
+
+/// Longest forecast in days
+const MAX_FORECAST_DAYS: u32 = 366;
+
+/// Forecast length when `days` is not given
+const DEFAULT_FORECAST_DAYS: u32 = 30;
+
+/// Most past months the variable spending can be averaged over
+const MAX_AVERAGE_MONTHS: u32 = 24;
+
+/// Query parameters for the forecast
+#[derive(Debug, Deserialize)]
+pub struct ForecastQuery {
+    /// Days to project after today (default 30, at most 366)
+    pub days: Option<u32>,
+    /// Only this account
+    pub account_id: Option<i64>,
+    /// Spread the average spending of the past full months over every day
+    pub average_months: Option<u32>,
+}
+
+fn recurring_item_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Recurring item with id {} not found", id),
+    )
+}
+
+/// List recurring items with their next occurrence
+///
+/// GET /forecast/recurring/
+#[get("/recurring/", name = "forecast_recurring_list")]
+pub async fn list_recurring_items() -> ViewResult<Response> {
+    let items = RecurringItem::objects().all().all().await?;
+
+    let response = RecurringItemListResponse::new(&items, Utc::now().date_naive());
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get a recurring item
+///
+/// GET /forecast/recurring/{id}/
+#[get("/recurring/{id}/", name = "forecast_recurring_get")]
+pub async fn get_recurring_item(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(item) = RecurringItem::objects().get(id).first().await? else {
+        return recurring_item_not_found(id);
+    };
+
+    let response = RecurringItemResponse::new(&item, Utc::now().date_naive());
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Create a recurring item
+///
+/// POST /forecast/recurring/
+#[post("/recurring/", name = "forecast_recurring_create")]
+pub async fn create_recurring_item_view(
+    request: Request,
+    Json(create_req): Json<CreateRecurringItemRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_recurring_item(create_req)).await
+}
+
+async fn create_recurring_item(create_req: CreateRecurringItemRequest) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+    if !account_exists(create_req.account_id).await? {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("Account with id {} not found", create_req.account_id),
+        );
+    }
+    if let Some(category_id) = create_req.category_id {
+        if Category::objects().get(category_id).first().await?.is_none() {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Category with id {} not found", category_id),
+            );
+        }
+    }
+    if create_req
+        .end_date
+        .is_some_and(|end| end < create_req.start_date)
+    {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "end_date must not be before start_date",
+        );
+    }
+
+    let item = RecurringItem {
+        id: None,
+        name: create_req.name,
+        account_id: create_req.account_id,
+        category_id: create_req.category_id,
+        transaction_type: create_req.transaction_type.to_string(),
+        amount: create_req.amount,
+        frequency: create_req.frequency.to_string(),
+        start_date: create_req.start_date,
+        end_date: create_req.end_date,
+        created_at: Utc::now(),
+    };
+    let created = RecurringItem::objects().create(&item).await?;
+
+    let response = RecurringItemResponse::new(&created, Utc::now().date_naive());
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Update the name, amount or end date of a recurring item
+///
+/// PUT /forecast/recurring/{id}/
+#[put("/recurring/{id}/", name = "forecast_recurring_update")]
+pub async fn update_recurring_item_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateRecurringItemRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, update_recurring_item(id, update_req)).await
+}
+
+async fn update_recurring_item(
+    id: i64,
+    update_req: UpdateRecurringItemRequest,
+) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = RecurringItem::objects();
+    let Some(mut item) = manager.get(id).first().await? else {
+        return recurring_item_not_found(id);
+    };
+
+    if let Some(n) = update_req.name {
+        item.name = n;
+    }
+    if let Some(a) = update_req.amount {
+        item.amount = a;
+    }
+    if let Some(end) = update_req.end_date {
+        if end < item.start_date {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                "end_date must not be before start_date",
+            );
+        }
+        item.end_date = Some(end);
+    }
+    let updated = manager.update(&item).await?;
+
+    let response = RecurringItemResponse::new(&updated, Utc::now().date_naive());
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Delete a recurring item
+///
+/// DELETE /forecast/recurring/{id}/
+#[delete("/recurring/{id}/", name = "forecast_recurring_delete")]
+pub async fn delete_recurring_item_view(
+    request: Request,
+    Path(id): Path<i64>,
+) -> ViewResult<Response> {
+    idempotent(&request, delete_recurring_item(id)).await
+}
+
+async fn delete_recurring_item(id: i64) -> ViewResult<Response> {
+    let manager = RecurringItem::objects();
+    if manager.get(id).first().await?.is_none() {
+        return recurring_item_not_found(id);
+    }
+
+    manager.delete(id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// A scheduled flow on the way to the response
+struct Flow {
+    date: NaiveDate,
+    account_id: i64,
+    source: ForecastSource,
+    description: String,
+    amount: i64,
+}
+
+/// Occurrences of the recurring items from `from` through `to`
+fn recurring_flows(items: &[RecurringItem], from: NaiveDate, to: NaiveDate) -> Vec<Flow> {
+    items
+        .iter()
+        .flat_map(|item| {
+            let dates = occurrences(
+                item.get_frequency(),
+                item.start_date,
+                item.end_date,
+                from,
+                to,
+            );
+            dates.into_iter().map(|date| Flow {
+                date,
+                account_id: item.account_id,
+                source: ForecastSource::Recurring,
+                description: item.name.clone(),
+                amount: item.signed_amount(),
+            })
+        })
+        .collect()
+}
+
+/// Card statements withdrawn from `from` through `to`: out of the payment
+/// account and into the card
+fn card_payment_flows(
+    accounts: &[Account],
+    transactions: &[Transaction],
+    schedules: &Schedules,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<Flow> {
+    let today = from - Days::new(1);
+    let mut flows = Vec::new();
+    for card in accounts.iter().filter(|a| a.is_credit_card()) {
+        let (Some(card_id), Some(payment_account_id)) = (card.id, card.payment_account_id) else {
+            continue;
+        };
+        for statement in card_statements(card, transactions, schedules, today) {
+            let period = statement.period;
+            if period.payment < from || period.payment > to || statement.amount_due == 0 {
+                continue;
+            }
+            let description = format!("{} (closing {})", card.name, period.closing);
+            for (account_id, amount) in [
+                (payment_account_id, -statement.amount_due),
+                (card_id, statement.amount_due),
+            ] {
+                flows.push(Flow {
+                    date: period.payment,
+                    account_id,
+                    source: ForecastSource::CardPayment,
+                    description: description.clone(),
+                    amount,
+                });
+            }
+        }
+    }
+    flows
+}
+
+/// Scheduled repayments of loans with an account, from `from` through
+/// `to`, that are not linked to a transaction yet
+async fn loan_flows(from: NaiveDate, to: NaiveDate) -> ViewResult<Vec<Flow>> {
+    let prepayments = LoanPrepayment::objects().all().all().await?;
+    let linked: HashSet<(i64, i32)> = LoanRepayment::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .map(|r| (r.loan_id, r.number))
+        .collect();
+
+    let mut flows = Vec::new();
+    for loan in Loan::objects().all().all().await? {
+        let (Some(loan_id), Some(account_id)) = (loan.id, loan.account_id) else {
+            continue;
+        };
+        let planned: Vec<_> = prepayments
+            .iter()
+            .filter(|p| p.loan_id == loan_id)
+            .map(LoanPrepayment::to_prepayment)
+            .collect();
+        for row in schedule(&loan.terms(), &planned) {
+            if row.due_date < from
+                || row.due_date > to
+                || linked.contains(&(loan_id, row.number as i32))
+            {
+                continue;
+            }
+            flows.push(Flow {
+                date: row.due_date,
+                account_id,
+                source: ForecastSource::Loan,
+                description: format!("{} #{}", loan.name, row.number),
+                amount: -row.payment(),
+            });
+        }
+    }
+    Ok(flows)
+}
+
+/// Average monthly spending per account and category over the `months`
+/// full months before this one
+///
+/// Categories of recurring items, loan repayments and purchases paid in
+/// installments are left out, since the forecast schedules them.
+async fn variable_spending(
+    transactions: &[Transaction],
+    items: &[RecurringItem],
+    months: u32,
+    today: NaiveDate,
+) -> ViewResult<Vec<VariableSpendingResponse>> {
+    let until = first_of_month(today);
+    let since = until - Months::new(months);
+    let scheduled_categories: HashSet<i64> = items.iter().filter_map(|i| i.category_id).collect();
+    let mut scheduled_transactions: HashSet<i64> = LoanRepayment::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .map(|r| r.transaction_id)
+        .collect();
+    scheduled_transactions.extend(schedules().await?.into_keys());
+
+    let mut totals: BTreeMap<(i64, i64), i64> = BTreeMap::new();
+    for t in transactions {
+        let date = t.transaction_date.date_naive();
+        let Some(account_id) = t.account_id else {
+            continue;
+        };
+        if t.get_transaction_type() != TransactionType::Expense
+            || date < since
+            || date >= until
+            || scheduled_categories.contains(&t.category_id)
+            || t.id.is_some_and(|id| scheduled_transactions.contains(&id))
+        {
+            continue;
+        }
+        *totals.entry((account_id, t.category_id)).or_insert(0) += t.amount;
+    }
+
+    let names: HashMap<i64, String> = Category::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter_map(|c| Some((c.id?, c.name)))
+        .collect();
+    Ok(totals
+        .into_iter()
+        .map(|((account_id, category_id), total)| {
+            let category_name = names.get(&category_id).cloned().unwrap_or_default();
+            VariableSpendingResponse {
+                account_id,
+                category_id,
+                category_name,
+                monthly_average: (total as f64 / f64::from(months)).round() as i64,
+            }
+        })
+        .collect())
+}
+
+/// Project account balances day by day
+///
+/// GET /forecast/?days=60&account_id=1&average_months=3
+///
+/// Starts from today's balances and adds recurring items, card statements
+/// still to be withdrawn and loan repayments not linked yet. With
+/// `average_months`, the average spending of the other categories over
+/// that many past months is spread over every day.
+#[get("/", name = "forecast_get")]
+pub async fn get_forecast(Query(params): Query<ForecastQuery>) -> ViewResult<Response> {
+    let days = params.days.unwrap_or(DEFAULT_FORECAST_DAYS);
+    if !(1..=MAX_FORECAST_DAYS).contains(&days) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("days must be between 1 and {}", MAX_FORECAST_DAYS),
+        );
+    }
+    let average_months = params.average_months.unwrap_or(0);
+    if average_months > MAX_AVERAGE_MONTHS {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("average_months must be at most {}", MAX_AVERAGE_MONTHS),
+        );
+    }
+
+    let payments = card_payments().await?;
+    let accounts = Account::objects().all().all().await?;
+    if let Some(account_id) = params.account_id {
+        if !accounts.iter().any(|a| a.id == Some(account_id)) {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                &format!("Account with id {} not found", account_id),
+            );
+        }
+    }
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+    let items = RecurringItem::objects().all().all().await?;
+
+    let today = Utc::now().date_naive();
+    let from = today + Days::new(1);
+    let to = today + Days::new(u64::from(days));
+
+    let mut flows = recurring_flows(&items, from, to);
+    flows.extend(card_payment_flows(
+        &accounts,
+        &transactions,
+        &schedules,
+        from,
+        to,
+    ));
+    flows.extend(loan_flows(from, to).await?);
+    flows.retain(|f| params.account_id.is_none_or(|id| f.account_id == id));
+    flows.sort_by(|a, b| (a.date, a.account_id).cmp(&(b.date, b.account_id)));
+
+    let variable = if average_months > 0 {
+        variable_spending(&transactions, &items, average_months, today).await?
+    } else {
+        Vec::new()
+    };
+
+    let mut results = Vec::new();
+    for account in accounts {
+        let Some(account_id) = account.id else {
+            continue;
+        };
+        if params.account_id.is_some_and(|id| id != account_id) {
+            continue;
+        }
+        let account_name = account.name.clone();
+        let current_balance =
+            AccountResponse::new(account, &transactions, &payments, &schedules).balance;
+
+        let own_flows: Vec<(NaiveDate, i64)> = flows
+            .iter()
+            .filter(|f| f.account_id == account_id)
+            .map(|f| (f.date, f.amount))
+            .collect();
+        let monthly: i64 = variable
+            .iter()
+            .filter(|v| v.account_id == account_id)
+            .map(|v| v.monthly_average)
+            .sum();
+        let daily_spending = monthly as f64 * 12.0 / 365.0;
+        let balances = project(current_balance, from, days, &own_flows, daily_spending);
+
+        let day = |index: usize| {
+            ForecastDayResponse::new(from + Days::new(index as u64), balances[index])
+        };
+        let lowest = (0..balances.len())
+            .min_by_key(|&i| balances[i])
+            .unwrap_or(0);
+        results.push(ForecastAccountResponse {
+            account_id,
+            account_name,
+            current_balance,
+            ending_balance: balances.last().copied().unwrap_or(current_balance),
+            lowest: day(lowest),
+            below_zero_on: balances.iter().position(|&b| b < 0).map(|i| day(i).date),
+            low_points: low_points(&balances).into_iter().map(day).collect(),
+            days: (0..balances.len()).map(day).collect(),
+        });
+    }
+
+    let response = ForecastResponse {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        average_months,
+        accounts: results,
+        flows: flows
+            .into_iter()
+            .map(|f| ForecastFlowResponse {
+                date: f.date.format("%Y-%m-%d").to_string(),
+                account_id: f.account_id,
+                source: f.source,
+                description: f.description,
+                amount: f.amount,
+            })
+            .collect(),
+        variable_spending: variable
+            .into_iter()
+            .filter(|v| params.account_id.is_none_or(|id| v.account_id == id))
+            .collect(),
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
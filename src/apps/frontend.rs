@@ -0,0 +1,11 @@
+//! Frontend app serving the WASM client
+//!
+//! Serves `index.html`, the `wasm-pack` bundle in `dist-wasm/` and the files
+//! in `static/`. Any other page navigation outside `/api/` falls back to
+//! `index.html` so that a reload on e.g. `/transactions` boots the client
+//! on that route.
+
+pub mod views;
+pub mod urls;
+
+pub use urls::url_patterns;
@@ -0,0 +1,18 @@
+//! URL routing for frontend app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the frontend app
+///
+/// Mount last, after the API routes.
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::wasm_bundle)
+        .endpoint(views::static_file)
+        .endpoint(views::service_worker)
+        // History fallback for client routes (see client/router.rs)
+        .endpoint(views::index)
+        .endpoint(views::spa_fallback)
+}
@@ -0,0 +1,128 @@
+//! Frontend views (static files and SPA fallback)
+
+use reinhardt::http::ViewResult;
+use reinhardt::{get, Path, Request, Response, StatusCode};
+use std::path::{Path as FsPath, PathBuf};
+
+/// HTML shell that boots the WASM client
+const INDEX_HTML: &str = "index.html";
+
+/// Output directory of `wasm-pack build --target web --out-dir dist-wasm`
+const WASM_BUNDLE_DIR: &str = "dist-wasm";
+
+/// Directory of plain static assets
+const STATIC_DIR: &str = "static";
+
+/// Resolves a path relative to the project directory
+fn project_path(relative: impl AsRef<FsPath>) -> PathBuf {
+    std::env::current_dir()
+        .expect("Failed to get current directory")
+        .join(relative)
+}
+
+/// Rejects names that could escape the served directory
+fn is_safe_file_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\'])
+}
+
+/// Guesses the content type from the file extension
+fn content_type(path: &FsPath) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or_default() {
+        "html" => "text/html; charset=utf-8",
+        "js" => "text/javascript; charset=utf-8",
+        "wasm" => "application/wasm",
+        "css" => "text/css; charset=utf-8",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "ico" => "image/x-icon",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Reads a file from disk into a response, or 404 if it does not exist
+async fn serve_file(path: PathBuf) -> ViewResult<Response> {
+    match tokio::fs::read(&path).await {
+        Ok(data) => Ok(Response::new(StatusCode::OK)
+            .with_header("Content-Type", content_type(&path))
+            .with_body(data)),
+        Err(_) => Ok(not_found()),
+    }
+}
+
+/// 404 that does not reveal where files are looked up on disk
+fn not_found() -> Response {
+    Response::new(StatusCode::NOT_FOUND).with_body(br#"{"error": "Not found"}"#.to_vec())
+}
+
+/// Whether the request is a browser navigation rather than an API or
+/// asset request
+fn accepts_html(request: &Request) -> bool {
+    request
+        .headers
+        .get("accept")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"))
+}
+
+/// Serves the client shell for any client-side route
+async fn spa_index() -> ViewResult<Response> {
+    serve_file(project_path(INDEX_HTML)).await
+}
+
+/// Serve the WASM bundle (JS glue and `.wasm`)
+///
+/// GET /dist-wasm/{file}
+#[get("/dist-wasm/{file}", name = "frontend_wasm_bundle")]
+pub async fn wasm_bundle(Path(file): Path<String>) -> ViewResult<Response> {
+    if !is_safe_file_name(&file) {
+        return Ok(not_found());
+    }
+    serve_file(project_path(WASM_BUNDLE_DIR).join(file)).await
+}
+
+/// Serve a static asset
+///
+/// GET /static/{file}
+#[get("/static/{file}", name = "frontend_static")]
+pub async fn static_file(Path(file): Path<String>) -> ViewResult<Response> {
+    if !is_safe_file_name(&file) {
+        return Ok(not_found());
+    }
+    serve_file(project_path(STATIC_DIR).join(file)).await
+}
+
+/// Service worker script
+///
+/// GET /sw.js
+///
+/// Served from the root rather than `/static/` because a service worker only
+/// controls pages below its own path.
+#[get("/sw.js", name = "frontend_service_worker")]
+pub async fn service_worker() -> ViewResult<Response> {
+    serve_file(project_path(STATIC_DIR).join("sw.js")).await
+}
+
+/// Dashboard route
+///
+/// GET /
+#[get("/", name = "frontend_index")]
+pub async fn index() -> ViewResult<Response> {
+    spa_index().await
+}
+
+/// History fallback for every other client route
+///
+/// GET /{path}
+///
+/// Any page navigation outside `/api/` boots the client, which routes it
+/// (see client/router.rs) and shows its own 404 page for unknown paths.
+/// Requests that do not accept HTML get a plain 404.
+#[get("/{<path:path>}", name = "frontend_fallback")]
+pub async fn spa_fallback(request: Request, Path(path): Path<String>) -> ViewResult<Response> {
+    let is_api = path == "api" || path.starts_with("api/");
+    if is_api || !accepts_html(&request) {
+        return Ok(not_found());
+    }
+    spa_index().await
+}
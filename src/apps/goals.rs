@@ -0,0 +1,17 @@
+//! Goals app for savings goals (貯金目標)
+//!
+//! A goal either tracks an account whose balance is the money saved, or
+//! collects transactions linked to it (expenses set aside add to it,
+//! income drawn from it subtracts). Progress reports the monthly amount
+//! needed to meet the deadline and projects completion from the recent
+//! contribution rate.
+
+pub mod models;
+pub mod progress;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
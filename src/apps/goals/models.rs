@@ -0,0 +1,53 @@
+//! Savings goal model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A savings goal (貯金目標)
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "goals", table_name = "goals")]
+pub struct Goal {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Goal name (e.g., "家族旅行", "教育資金")
+    #[field(max_length = 100)]
+    pub name: String,
+
+    /// Amount to save in yen
+    pub target_amount: i64,
+
+    /// Date the target should be reached by
+    #[field(null = true)]
+    pub deadline: Option<NaiveDate>,
+
+    /// Account the money is kept in (foreign key, optional); without one,
+    /// the contributions are linked transactions
+    #[field(null = true)]
+    pub account_id: Option<i64>,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+/// A transaction earmarked for a goal without an account
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "goals", table_name = "goal_contributions")]
+pub struct GoalContribution {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Goal saved for (foreign key)
+    pub goal_id: i64,
+
+    /// Contribution transaction (foreign key, linked to one goal at most)
+    pub transaction_id: i64,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,111 @@
+//! Progress arithmetic for savings goals
+//!
+//! Contributions are counted by month: the required amount is spread over
+//! the months from next month through the deadline's month, and the
+//! projection saves the recent monthly average from next month on.
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::apps::cards::billing::first_of_month;
+
+/// Full months before the current one averaged for the recent rate
+pub const RECENT_MONTHS: u32 = 3;
+
+/// Months from next month through the month of `deadline` (zero when the
+/// deadline is this month or has passed)
+pub fn months_until(today: NaiveDate, deadline: NaiveDate) -> u32 {
+    let months =
+        (deadline.year() - today.year()) * 12 + deadline.month() as i32 - today.month() as i32;
+    u32::try_from(months).unwrap_or(0)
+}
+
+/// Monthly amount that saves `remaining` by `deadline`, rounded up to the
+/// yen; all of it when no month is left
+pub fn required_monthly(remaining: i64, today: NaiveDate, deadline: NaiveDate) -> i64 {
+    match i64::from(months_until(today, deadline)) {
+        0 => remaining,
+        months => (remaining + months - 1) / months,
+    }
+}
+
+/// Average monthly sum of the dated `contributions` over the
+/// [`RECENT_MONTHS`] full months before the month of `today`
+pub fn recent_monthly(contributions: &[(NaiveDate, i64)], today: NaiveDate) -> i64 {
+    let until = first_of_month(today);
+    let since = until - Months::new(RECENT_MONTHS);
+    let total: i64 = contributions
+        .iter()
+        .filter(|(date, _)| *date >= since && *date < until)
+        .map(|(_, amount)| amount)
+        .sum();
+    (total as f64 / f64::from(RECENT_MONTHS)).round() as i64
+}
+
+/// Last day of the month `remaining` is saved by putting aside `monthly`
+/// from next month on; `None` when nothing remains or nothing is saved
+pub fn projected_completion(remaining: i64, monthly: i64, today: NaiveDate) -> Option<NaiveDate> {
+    if remaining <= 0 || monthly <= 0 {
+        return None;
+    }
+    let months = u32::try_from((remaining + monthly - 1) / monthly).ok()?;
+    let after = first_of_month(today).checked_add_months(Months::new(months.checked_add(1)?))?;
+    after.checked_sub_days(Days::new(1))
+}
+
+/// `saved` relative to `target` in percent, rounded to one decimal
+pub fn progress_percent(saved: i64, target: i64) -> f64 {
+    if target <= 0 {
+        return 0.0;
+    }
+    (saved as f64 / target as f64 * 1000.0).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_required_monthly() {
+        let today = date(2026, 10, 18);
+        assert_eq!(months_until(today, date(2027, 3, 31)), 5);
+        assert_eq!(
+            required_monthly(1_000_000, today, date(2027, 3, 31)),
+            200_000
+        );
+        assert_eq!(required_monthly(100_000, today, date(2026, 12, 1)), 50_000);
+        assert_eq!(required_monthly(10, today, date(2026, 12, 1)), 5);
+        assert_eq!(required_monthly(11, today, date(2026, 12, 1)), 6);
+        // This month or past: everything now
+        assert_eq!(required_monthly(30_000, today, date(2026, 10, 31)), 30_000);
+        assert_eq!(required_monthly(30_000, today, date(2025, 1, 1)), 30_000);
+    }
+
+    #[test]
+    fn test_recent_rate_and_projection() {
+        let today = date(2026, 10, 18);
+        let contributions = [
+            (date(2026, 6, 30), 500_000),
+            (date(2026, 7, 25), 30_000),
+            (date(2026, 8, 25), 30_000),
+            (date(2026, 9, 25), 30_000),
+            (date(2026, 10, 1), 30_000),
+        ];
+        assert_eq!(recent_monthly(&contributions, today), 30_000);
+
+        assert_eq!(
+            projected_completion(90_000, 30_000, today),
+            Some(date(2027, 1, 31))
+        );
+        assert_eq!(
+            projected_completion(100_000, 30_000, today),
+            Some(date(2027, 2, 28))
+        );
+        assert_eq!(projected_completion(0, 30_000, today), None);
+        assert_eq!(projected_completion(90_000, 0, today), None);
+        assert_eq!(progress_percent(123_456, 1_000_000), 12.3);
+    }
+}
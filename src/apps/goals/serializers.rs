@@ -0,0 +1,168 @@
+//! Goal serializers for request/response handling
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::apps::transactions::models::Transaction;
+use super::models::Goal;
+use super::progress::{progress_percent, projected_completion, recent_monthly, required_monthly};
+
+/// Request payload for creating a savings goal
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateGoalRequest {
+    /// Goal name (1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    /// Amount to save in yen
+    #[validate(range(min = 1, message = "Target amount must be positive"))]
+    pub target_amount: i64,
+    #[serde(default)]
+    pub deadline: Option<NaiveDate>,
+    /// Account the money is kept in; its balance is the amount saved.
+    /// Without one, linked transactions are the contributions.
+    #[serde(default)]
+    pub account_id: Option<i64>,
+}
+
+/// Request payload for updating a savings goal
+///
+/// The account is fixed; create a new goal to track another one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateGoalRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    #[validate(range(min = 1, message = "Target amount must be positive"))]
+    pub target_amount: Option<i64>,
+    pub deadline: Option<NaiveDate>,
+}
+
+/// Request payload for linking a contribution transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkGoalContributionRequest {
+    pub transaction_id: i64,
+}
+
+/// Response payload for a savings goal with its progress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalResponse {
+    pub id: i64,
+    pub name: String,
+    pub target_amount: i64,
+    /// Deadline as `YYYY-MM-DD`
+    pub deadline: Option<String>,
+    pub account_id: Option<i64>,
+    /// Balance of the account, or the sum of the linked contributions
+    pub saved: i64,
+    /// Amount still to save (never negative)
+    pub remaining: i64,
+    /// `saved` relative to `target_amount` in percent (one decimal)
+    pub progress_percent: f64,
+    /// Monthly amount that reaches the target by the deadline, saving from
+    /// next month through the deadline's month (`None` without a deadline)
+    pub required_monthly: Option<i64>,
+    /// Average monthly contribution over the last three full months
+    pub recent_monthly: i64,
+    /// Last day of the month the target is reached at `recent_monthly`
+    /// (`None` once reached, or when nothing is being saved)
+    pub projected_completion: Option<String>,
+    /// Whether the target is reached, or projected to be, by the deadline
+    /// (`None` without a deadline)
+    pub on_track: Option<bool>,
+    pub created_at: String,
+}
+
+/// A transaction linked to a savings goal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalContributionResponse {
+    pub goal_id: i64,
+    pub transaction_id: i64,
+    /// Transaction date as `YYYY-MM-DD`
+    pub transaction_date: String,
+    pub description: String,
+    /// Positive for expenses set aside, negative for income drawn from
+    /// the savings
+    pub amount: i64,
+}
+
+/// A savings goal with its linked contributions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalDetailResponse {
+    #[serde(flatten)]
+    pub goal: GoalResponse,
+    pub contributions: Vec<GoalContributionResponse>,
+}
+
+/// Response wrapper for list of savings goals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalListResponse {
+    pub count: usize,
+    pub results: Vec<GoalResponse>,
+}
+
+/// Amount a linked transaction adds to its goal: expenses are money set
+/// aside, income is money drawn from the savings
+pub fn contribution_amount(transaction: &Transaction) -> i64 {
+    -transaction.signed_amount()
+}
+
+impl GoalResponse {
+    /// Builds the response from the amount `saved` and the dated
+    /// `contributions` the recent rate is computed from
+    pub fn new(
+        goal: &Goal,
+        saved: i64,
+        contributions: &[(NaiveDate, i64)],
+        today: NaiveDate,
+    ) -> Self {
+        let remaining = (goal.target_amount - saved).max(0);
+        let recent = recent_monthly(contributions, today);
+        let projected = projected_completion(remaining, recent, today);
+        let on_track = goal
+            .deadline
+            .map(|deadline| remaining == 0 || projected.is_some_and(|date| date <= deadline));
+
+        Self {
+            id: goal.id.unwrap_or(0),
+            name: goal.name.clone(),
+            target_amount: goal.target_amount,
+            deadline: goal.deadline.map(|d| d.format("%Y-%m-%d").to_string()),
+            account_id: goal.account_id,
+            saved,
+            remaining,
+            progress_percent: progress_percent(saved, goal.target_amount),
+            required_monthly: goal
+                .deadline
+                .map(|deadline| required_monthly(remaining, today, deadline)),
+            recent_monthly: recent,
+            projected_completion: projected.map(|d| d.format("%Y-%m-%d").to_string()),
+            on_track,
+            created_at: goal.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl GoalContributionResponse {
+    pub fn new(goal_id: i64, transaction: &Transaction) -> Self {
+        Self {
+            goal_id,
+            transaction_id: transaction.id.unwrap_or(0),
+            transaction_date: transaction
+                .transaction_date
+                .date_naive()
+                .format("%Y-%m-%d")
+                .to_string(),
+            description: transaction.description.clone(),
+            amount: contribution_amount(transaction),
+        }
+    }
+}
+
+impl GoalListResponse {
+    pub fn new(results: Vec<GoalResponse>) -> Self {
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
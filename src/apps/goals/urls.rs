@@ -0,0 +1,17 @@
+//! URL routing for goals app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the goals app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_goals)
+        .endpoint(views::get_goal)
+        .endpoint(views::create_goal_view)
+        .endpoint(views::update_goal_view)
+        .endpoint(views::delete_goal_view)
+        .endpoint(views::link_contribution_view)
+        .endpoint(views::unlink_contribution_view)
+}
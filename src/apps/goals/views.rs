@@ -0,0 +1,360 @@
+//! Goal views (API endpoints)
+
+use chrono::{NaiveDate, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Request, Response, StatusCode};
+use std::collections::HashMap;
+use validator::Validate;
+
+use crate::apps::accounts::models::Account;
+use crate::apps::accounts::serializers::AccountResponse;
+use crate::apps::accounts::views::account_exists;
+use crate::apps::cards::card_payments;
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::installments::schedules;
+use crate::apps::transactions::models::Transaction;
+use crate::apps::transactions::views::active_transactions;
+use super::models::{Goal, GoalContribution};
+use super::serializers::{
+    contribution_amount, CreateGoalRequest, GoalContributionResponse, GoalDetailResponse,
+    GoalListResponse, GoalResponse, LinkGoalContributionRequest, UpdateGoalRequest,
+};
+
+fn goal_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Goal with id {} not found", id),
+    )
+}
+
+/// Deletes the goal link of a transaction, if any
+pub async fn delete_for_transaction(transaction_id: i64) -> ViewResult<()> {
+    let manager = GoalContribution::objects();
+    for contribution in manager.all().all().await? {
+        if contribution.transaction_id == transaction_id
+            && let Some(id) = contribution.id
+        {
+            manager.delete(id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Builds the progress of each goal
+///
+/// A goal with an account has saved the account's balance, and its
+/// contributions are the account's transactions and card payments. Other
+/// goals sum their linked transactions; trashed ones are left out until
+/// they are restored.
+async fn goal_responses(goals: &[Goal]) -> ViewResult<Vec<GoalResponse>> {
+    let payments = card_payments().await?;
+    let transactions = active_transactions().await?;
+    let schedules = schedules().await?;
+    let contributions = GoalContribution::objects().all().all().await?;
+    let today = Utc::now().date_naive();
+
+    let balances: HashMap<i64, i64> = Account::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter_map(|account| {
+            let id = account.id?;
+            let response = AccountResponse::new(account, &transactions, &payments, &schedules);
+            Some((id, response.balance))
+        })
+        .collect();
+    let by_id: HashMap<i64, &Transaction> = transactions
+        .iter()
+        .filter_map(|t| t.id.map(|id| (id, t)))
+        .collect();
+
+    Ok(goals
+        .iter()
+        .map(|goal| {
+            let goal_id = goal.id.unwrap_or(0);
+            let (saved, dated): (i64, Vec<(NaiveDate, i64)>) = match goal.account_id {
+                Some(account_id) => {
+                    let mut dated: Vec<(NaiveDate, i64)> = transactions
+                        .iter()
+                        .filter(|t| t.account_id == Some(account_id))
+                        .map(|t| (t.transaction_date.date_naive(), t.signed_amount()))
+                        .collect();
+                    dated.extend(
+                        payments
+                            .iter()
+                            .map(|p| (p.payment_date, p.balance_change(account_id))),
+                    );
+                    (balances.get(&account_id).copied().unwrap_or(0), dated)
+                }
+                None => {
+                    let dated: Vec<(NaiveDate, i64)> = contributions
+                        .iter()
+                        .filter(|c| c.goal_id == goal_id)
+                        .filter_map(|c| by_id.get(&c.transaction_id))
+                        .map(|t| (t.transaction_date.date_naive(), contribution_amount(t)))
+                        .collect();
+                    (dated.iter().map(|(_, amount)| amount).sum(), dated)
+                }
+            };
+            GoalResponse::new(goal, saved, &dated, today)
+        })
+        .collect())
+}
+
+async fn detail_response(goal: &Goal) -> ViewResult<GoalDetailResponse> {
+    let goal_id = goal.id.unwrap_or(0);
+    let linked: Vec<i64> = GoalContribution::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|c| c.goal_id == goal_id)
+        .map(|c| c.transaction_id)
+        .collect();
+    let transactions = active_transactions().await?;
+    let mut contributions: Vec<&Transaction> = transactions
+        .iter()
+        .filter(|t| t.id.is_some_and(|id| linked.contains(&id)))
+        .collect();
+    contributions.sort_by_key(|t| (t.transaction_date, t.id));
+
+    let mut responses = goal_responses(std::slice::from_ref(goal)).await?;
+    Ok(GoalDetailResponse {
+        goal: responses.remove(0),
+        contributions: contributions
+            .into_iter()
+            .map(|t| GoalContributionResponse::new(goal_id, t))
+            .collect(),
+    })
+}
+
+/// List all goals with their progress
+///
+/// GET /goals/
+#[get("/", name = "goals_list")]
+pub async fn list_goals() -> ViewResult<Response> {
+    let goals = Goal::objects().all().all().await?;
+
+    let response = GoalListResponse::new(goal_responses(&goals).await?);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get a goal with its progress and linked contributions
+///
+/// GET /goals/{id}/
+#[get("/{id}/", name = "goals_get")]
+pub async fn get_goal(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(goal) = Goal::objects().get(id).first().await? else {
+        return goal_not_found(id);
+    };
+
+    let response = detail_response(&goal).await?;
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Create a new goal
+///
+/// POST /goals/
+#[post("/", name = "goals_create")]
+pub async fn create_goal_view(
+    request: Request,
+    Json(create_req): Json<CreateGoalRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_goal(create_req)).await
+}
+
+async fn create_goal(create_req: CreateGoalRequest) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+    if let Some(account_id) = create_req.account_id
+        && !account_exists(account_id).await?
+    {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("Account with id {} not found", account_id),
+        );
+    }
+
+    let goal = Goal {
+        id: None,
+        name: create_req.name,
+        target_amount: create_req.target_amount,
+        deadline: create_req.deadline,
+        account_id: create_req.account_id,
+        created_at: Utc::now(),
+    };
+    let created = Goal::objects().create(&goal).await?;
+
+    let response = detail_response(&created).await?;
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Update the name, target amount or deadline of a goal
+///
+/// PUT /goals/{id}/
+#[put("/{id}/", name = "goals_update")]
+pub async fn update_goal_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateGoalRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, update_goal(id, update_req)).await
+}
+
+async fn update_goal(id: i64, update_req: UpdateGoalRequest) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = Goal::objects();
+    let Some(mut goal) = manager.get(id).first().await? else {
+        return goal_not_found(id);
+    };
+
+    if let Some(n) = update_req.name {
+        goal.name = n;
+    }
+    if let Some(t) = update_req.target_amount {
+        goal.target_amount = t;
+    }
+    if let Some(d) = update_req.deadline {
+        goal.deadline = Some(d);
+    }
+    let updated = manager.update(&goal).await?;
+
+    let response = detail_response(&updated).await?;
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Delete a goal with its links; the transactions are kept
+///
+/// DELETE /goals/{id}/
+#[delete("/{id}/", name = "goals_delete")]
+pub async fn delete_goal_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    idempotent(&request, delete_goal(id)).await
+}
+
+async fn delete_goal(id: i64) -> ViewResult<Response> {
+    let manager = Goal::objects();
+    if manager.get(id).first().await?.is_none() {
+        return goal_not_found(id);
+    }
+
+    let contribution_manager = GoalContribution::objects();
+    for contribution in contribution_manager.all().all().await? {
+        if contribution.goal_id == id
+            && let Some(contribution_id) = contribution.id
+        {
+            contribution_manager.delete(contribution_id).await?;
+        }
+    }
+    manager.delete(id).await?;
+
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Link a transaction to a goal without an account as a contribution
+///
+/// POST /goals/{id}/contributions/
+#[post("/{id}/contributions/", name = "goals_contributions_link")]
+pub async fn link_contribution_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(link_req): Json<LinkGoalContributionRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, link_contribution(id, link_req)).await
+}
+
+async fn link_contribution(
+    goal_id: i64,
+    link_req: LinkGoalContributionRequest,
+) -> ViewResult<Response> {
+    let Some(goal) = Goal::objects().get(goal_id).first().await? else {
+        return goal_not_found(goal_id);
+    };
+    if let Some(account_id) = goal.account_id {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!(
+                "Goal {} tracks the balance of account {}; transactions cannot be linked",
+                goal_id, account_id
+            ),
+        );
+    }
+    let transaction_id = link_req.transaction_id;
+    let exists = Transaction::objects()
+        .get(transaction_id)
+        .first()
+        .await?
+        .is_some_and(|t| !t.is_deleted());
+    if !exists {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            &format!("Transaction with id {} not found", transaction_id),
+        );
+    }
+
+    let manager = GoalContribution::objects();
+    if manager
+        .all()
+        .all()
+        .await?
+        .iter()
+        .any(|c| c.transaction_id == transaction_id)
+    {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("Transaction {} is already linked to a goal", transaction_id),
+        );
+    }
+
+    let contribution = GoalContribution {
+        id: None,
+        goal_id,
+        transaction_id,
+        created_at: Utc::now(),
+    };
+    manager.create(&contribution).await?;
+
+    let response = detail_response(&goal).await?;
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Unlink a contribution transaction from a goal
+///
+/// DELETE /goals/{id}/contributions/{transaction_id}/
+#[delete(
+    "/{id}/contributions/{transaction_id}/",
+    name = "goals_contributions_unlink"
+)]
+pub async fn unlink_contribution_view(
+    request: Request,
+    Path((goal_id, transaction_id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    idempotent(&request, unlink_contribution(goal_id, transaction_id)).await
+}
+
+async fn unlink_contribution(goal_id: i64, transaction_id: i64) -> ViewResult<Response> {
+    let manager = GoalContribution::objects();
+    let contribution = manager
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .find(|c| c.goal_id == goal_id && c.transaction_id == transaction_id);
+    let Some(id) = contribution.and_then(|c| c.id) else {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            &format!(
+                "Transaction {} is not linked to goal {}",
+                transaction_id, goal_id
+            ),
+        );
+    };
+
+    manager.delete(id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
@@ -0,0 +1,13 @@
+//! Idempotency app for safely retried writes
+//!
+//! Write endpoints accept an `Idempotency-Key` header. The first response
+//! for a key is stored together with a hash of the request; a retry with
+//! the same key and request gets the stored response back instead of
+//! running the write again. Keys are kept for a retention window
+//! (`REINHARDT_IDEMPOTENCY_RETENTION_HOURS`, default 24 hours).
+
+pub mod guard;
+pub mod models;
+
+pub use guard::{idempotency_key, idempotent};
+pub use models::*;
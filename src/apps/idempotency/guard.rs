@@ -0,0 +1,210 @@
+//! Idempotency-Key handling for write views
+
+use chrono::{DateTime, Duration, Utc};
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{Request, Response, StatusCode};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use crate::apps::errors::error_response;
+use super::models::{IdempotencyKey, IN_PROGRESS_STATUS};
+
+/// Request header carrying the client-generated key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set when a stored response is returned
+pub const REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Environment variable overriding the retention window in hours
+pub const RETENTION_HOURS_ENV: &str = "REINHARDT_IDEMPOTENCY_RETENTION_HOURS";
+
+/// Default retention window in hours
+const DEFAULT_RETENTION_HOURS: i64 = 24;
+
+/// Longest accepted key
+const MAX_KEY_LENGTH: usize = 255;
+
+/// A claim older than this is left over from a request that never finished
+const STALE_CLAIM_MINUTES: i64 = 5;
+
+/// Expired keys are swept from the table at most this often
+const SWEEP_INTERVAL_MINUTES: i64 = 60;
+
+/// Unix time of the last sweep of expired keys
+static LAST_SWEEP: AtomicI64 = AtomicI64::new(0);
+
+/// Returns how long keys and their responses are kept
+pub fn retention() -> Duration {
+    let hours = std::env::var(RETENTION_HOURS_ENV)
+        .ok()
+        .and_then(|v| v.trim().parse::<i64>().ok())
+        .filter(|h| *h > 0)
+        .unwrap_or(DEFAULT_RETENTION_HOURS);
+    Duration::hours(hours)
+}
+
+/// Returns the `Idempotency-Key` header of a request, if present
+pub fn idempotency_key(request: &Request) -> Option<&str> {
+    request
+        .headers
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+}
+
+/// Hashes the parts of a request that must match on replay
+fn request_hash(request: &Request) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(request.method.as_str().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(request.uri.path().as_bytes());
+    hasher.update(b"?");
+    hasher.update(request.uri.query().unwrap_or_default().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(&request.body()[..]);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Deletes expired keys and abandoned claims, at most once per
+/// [`SWEEP_INTERVAL_MINUTES`]
+///
+/// Requests check their own key's age, so this only keeps the table small.
+async fn sweep_expired() -> ViewResult<()> {
+    let now = Utc::now();
+    let last = LAST_SWEEP.load(Ordering::Relaxed);
+    if now.timestamp() - last < SWEEP_INTERVAL_MINUTES * 60
+        || LAST_SWEEP
+            .compare_exchange(last, now.timestamp(), Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+    {
+        return Ok(());
+    }
+
+    let manager = IdempotencyKey::objects();
+    for record in manager.all().all().await? {
+        if is_expired(&record, now)
+            && let Some(id) = record.id
+        {
+            manager.delete(id).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether a record is past the retention window or an abandoned claim
+fn is_expired(record: &IdempotencyKey, now: DateTime<Utc>) -> bool {
+    let abandoned =
+        record.is_in_progress() && record.created_at < now - Duration::minutes(STALE_CLAIM_MINUTES);
+    record.created_at < now - retention() || abandoned
+}
+
+/// Runs `view` at most once per `Idempotency-Key`
+///
+/// Without the header the view simply runs. With it:
+/// - a new key runs the view and stores its response (5xx responses are
+///   not stored, so the request can be retried),
+/// - a known key with the same method, path, query and body returns the
+///   stored response (status, headers and body) with an
+///   `Idempotent-Replayed: true` header,
+/// - a known key with a different request is rejected with 422,
+/// - a key whose first request is still running is rejected with 409.
+///
+/// `view` is not polled unless it needs to run.
+pub async fn idempotent<F>(request: &Request, view: F) -> ViewResult<Response>
+where
+    F: Future<Output = ViewResult<Response>>,
+{
+    let Some(key) = idempotency_key(request) else {
+        return view.await;
+    };
+    if key.len() > MAX_KEY_LENGTH {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("Idempotency-Key must be at most {} characters", MAX_KEY_LENGTH),
+        );
+    }
+
+    sweep_expired().await?;
+
+    let manager = IdempotencyKey::objects();
+    let hash = request_hash(request);
+    let now = Utc::now();
+    let id = IdempotencyKey::id_for(key);
+
+    let existing = match manager.get(id).first().await? {
+        Some(record) if is_expired(&record, now) => {
+            manager.delete(id).await?;
+            None
+        }
+        record => record,
+    };
+
+    if let Some(record) = existing {
+        // A different key sharing the 63-bit id is treated like a reuse
+        if record.key != key || record.request_hash != hash {
+            return error_response(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Idempotency-Key was already used for a different request",
+            );
+        }
+        if record.is_in_progress() {
+            return error_response(
+                StatusCode::CONFLICT,
+                "A request with this Idempotency-Key is still being processed",
+            );
+        }
+        let status = u16::try_from(record.status_code)
+            .ok()
+            .and_then(|s| StatusCode::from_u16(s).ok())
+            .unwrap_or(StatusCode::OK);
+        let mut response = Response::new(status);
+        for (name, value) in record.headers() {
+            response = response.with_header(&name, &value);
+        }
+        return Ok(response
+            .with_header(REPLAYED_HEADER, "true")
+            .with_body(record.response_body.into_bytes()));
+    }
+
+    // Claim the key first; the primary key rejects a concurrent request
+    let claim = IdempotencyKey {
+        id: Some(id),
+        key: key.to_string(),
+        request_hash: hash,
+        status_code: IN_PROGRESS_STATUS,
+        response_body: String::new(),
+        response_headers: "[]".to_string(),
+        created_at: now,
+    };
+    let Ok(mut claim) = manager.create(&claim).await else {
+        return error_response(
+            StatusCode::CONFLICT,
+            "A request with this Idempotency-Key is still being processed",
+        );
+    };
+
+    let result = view.await;
+    match &result {
+        Ok(response) if !response.status.is_server_error() => {
+            claim.status_code = i32::from(response.status.as_u16());
+            claim.response_body = String::from_utf8_lossy(&response.body).into_owned();
+            let headers: Vec<(&str, &str)> = response
+                .headers
+                .iter()
+                .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+                .collect();
+            claim.response_headers = serde_json::to_string(&headers)?;
+            manager.update(&claim).await?;
+        }
+        // Let the client retry after a server error
+        _ => {
+            if let Some(id) = claim.id {
+                manager.delete(id).await?;
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,64 @@
+//! Idempotency key model definitions
+
+use chrono::{DateTime, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Status code stored while the first request for a key is still running
+pub const IN_PROGRESS_STATUS: i32 = 0;
+
+/// A write request made with an `Idempotency-Key` header and its response
+///
+/// The primary key is derived from the key (see [`IdempotencyKey::id_for`]),
+/// so a request finds its record by primary key instead of scanning the
+/// table.
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "idempotency", table_name = "idempotency_keys")]
+pub struct IdempotencyKey {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Client-supplied key (unique)
+    #[field(max_length = 255)]
+    pub key: String,
+
+    /// SHA-256 of the method, path, query and body of the first request
+    #[field(max_length = 64)]
+    pub request_hash: String,
+
+    /// HTTP status of the stored response, or `IN_PROGRESS_STATUS`
+    pub status_code: i32,
+
+    /// Body of the stored response
+    pub response_body: String,
+
+    /// Headers of the stored response as a JSON array of `[name, value]`
+    pub response_headers: String,
+
+    /// Creation timestamp; the key expires after the retention window
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl IdempotencyKey {
+    /// Primary key of the record for `key`: the first 63 bits of its
+    /// SHA-256
+    pub fn id_for(key: &str) -> i64 {
+        let digest = Sha256::digest(key.as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+        i64::from_be_bytes(bytes) & i64::MAX
+    }
+
+    /// Stored response headers as (name, value) pairs
+    pub fn headers(&self) -> Vec<(String, String)> {
+        serde_json::from_str(&self.response_headers).unwrap_or_default()
+    }
+
+    /// Whether the first request for this key has not finished yet
+    pub fn is_in_progress(&self) -> bool {
+        self.status_code == IN_PROGRESS_STATUS
+    }
+}
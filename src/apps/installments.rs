@@ -0,0 +1,19 @@
+//! Installments app for card purchases paid in installments or revolving
+//!
+//! A purchase can be given a payment plan: a fixed number of payments with
+//! a total fee or an interest rate (分割払い), or a fixed monthly principal
+//! plus interest on the balance (リボ払い). The scheduled payments are
+//! generated when the plan is created. Card statements bill the payments
+//! instead of the purchase, and the monthly report can count them on their
+//! due dates.
+
+pub mod models;
+pub mod schedule;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
+pub use views::{schedules, Schedules};
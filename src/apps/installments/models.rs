@@ -0,0 +1,120 @@
+//! Installment plan model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How a card purchase is paid off
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentPlanType {
+    /// 分割払い: a fixed number of monthly payments
+    #[default]
+    Installment,
+    /// リボ払い: a fixed principal every month plus interest on the balance
+    Revolving,
+}
+
+impl std::fmt::Display for PaymentPlanType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaymentPlanType::Installment => write!(f, "installment"),
+            PaymentPlanType::Revolving => write!(f, "revolving"),
+        }
+    }
+}
+
+impl std::str::FromStr for PaymentPlanType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "installment" => Ok(PaymentPlanType::Installment),
+            "revolving" => Ok(PaymentPlanType::Revolving),
+            _ => Err(format!("Invalid payment plan type: {}", s)),
+        }
+    }
+}
+
+/// Payment plan of one purchase
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "installments", table_name = "installment_plans")]
+pub struct InstallmentPlan {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Purchase being paid off (foreign key, one plan per transaction)
+    pub transaction_id: i64,
+
+    /// Installment or revolving - stored as string
+    #[field(max_length = 20)]
+    pub plan_type: String,
+
+    /// Purchase amount when the plan was made
+    pub principal: i64,
+
+    /// Annual interest rate in percent, if the fee is interest
+    #[field(null = true)]
+    pub interest_rate: Option<f64>,
+
+    /// Revolving plans: principal paid every month
+    #[field(null = true)]
+    pub monthly_principal: Option<i64>,
+
+    /// First day of the month of the first payment
+    pub first_payment_month: NaiveDate,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl InstallmentPlan {
+    /// Gets the plan type as enum
+    pub fn get_plan_type(&self) -> PaymentPlanType {
+        self.plan_type.parse().unwrap_or_default()
+    }
+}
+
+/// One scheduled payment of a plan
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "installments", table_name = "installment_payments")]
+pub struct InstallmentPayment {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Plan the payment belongs to (foreign key)
+    pub plan_id: i64,
+
+    /// 1-based payment number
+    pub number: i32,
+
+    /// Day the payment is billed
+    pub due_date: NaiveDate,
+
+    /// Part of the purchase paid off
+    pub principal: i64,
+
+    /// Fee or interest part
+    pub fee: i64,
+}
+
+impl InstallmentPayment {
+    /// Principal plus fee
+    pub fn amount(&self) -> i64 {
+        self.principal + self.fee
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_payment_plan_type_wire_values() {
+        assert_wire_values(&[PaymentPlanType::Installment, PaymentPlanType::Revolving]);
+    }
+}
@@ -0,0 +1,131 @@
+//! Payment schedule arithmetic for installment plans
+
+use chrono::{Months, NaiveDate};
+
+use crate::apps::cards::billing::day_in_month;
+
+/// Principal and fee of one scheduled payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledAmount {
+    pub principal: i64,
+    pub fee: i64,
+}
+
+/// Splits `total` into `count` parts, putting the remainder on the first
+/// (as card companies do)
+pub fn split_evenly(total: i64, count: u32) -> Vec<i64> {
+    let count = i64::from(count.max(1));
+    let base = total / count;
+    let mut parts = vec![base; count as usize];
+    parts[0] += total - base * count;
+    parts
+}
+
+/// `count` payments of the principal with a total `fee` spread over them
+pub fn with_fee(principal: i64, count: u32, fee: i64) -> Vec<ScheduledAmount> {
+    split_evenly(principal, count)
+        .into_iter()
+        .zip(split_evenly(fee, count))
+        .map(|(principal, fee)| ScheduledAmount { principal, fee })
+        .collect()
+}
+
+fn monthly_rate(annual_rate: f64) -> f64 {
+    annual_rate / 100.0 / 12.0
+}
+
+/// `count` equal payments (元利均等) at an annual rate in percent; the last
+/// payment absorbs the rounding
+pub fn with_rate(principal: i64, count: u32, annual_rate: f64) -> Vec<ScheduledAmount> {
+    let rate = monthly_rate(annual_rate);
+    if rate <= 0.0 {
+        return with_fee(principal, count, 0);
+    }
+    let payment =
+        (principal as f64 * rate / (1.0 - (1.0 + rate).powi(-(count as i32)))).round() as i64;
+
+    let mut balance = principal;
+    (0..count)
+        .map(|i| {
+            let fee = (balance as f64 * rate).round() as i64;
+            let principal = if i + 1 == count {
+                balance
+            } else {
+                (payment - fee).min(balance)
+            };
+            balance -= principal;
+            ScheduledAmount { principal, fee }
+        })
+        .collect()
+}
+
+/// Revolving payments (元金定額): `monthly_principal` each month plus
+/// interest on the remaining balance, until it is paid off
+pub fn revolving(principal: i64, monthly_principal: i64, annual_rate: f64) -> Vec<ScheduledAmount> {
+    let rate = monthly_rate(annual_rate);
+    let mut balance = principal;
+    let mut payments = Vec::new();
+    while balance > 0 {
+        let fee = (balance as f64 * rate).round() as i64;
+        let principal = monthly_principal.min(balance);
+        balance -= principal;
+        payments.push(ScheduledAmount { principal, fee });
+    }
+    payments
+}
+
+/// Due dates on `day` of consecutive months from `first_month`
+pub fn due_dates(first_month: NaiveDate, day: u32, count: usize) -> Vec<NaiveDate> {
+    (0..count)
+        .map(|i| day_in_month(first_month + Months::new(i as u32), day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn total(payments: &[ScheduledAmount]) -> (i64, i64) {
+        (
+            payments.iter().map(|p| p.principal).sum(),
+            payments.iter().map(|p| p.fee).sum(),
+        )
+    }
+
+    #[test]
+    fn test_with_fee() {
+        let payments = with_fee(100_000, 3, 2_000);
+        assert_eq!((payments[0].principal, payments[0].fee), (33_334, 668));
+        assert_eq!((payments[2].principal, payments[2].fee), (33_333, 666));
+        assert_eq!(total(&payments), (100_000, 2_000));
+    }
+
+    #[test]
+    fn test_with_rate() {
+        // 12回払い 実質年率15%
+        let payments = with_rate(120_000, 12, 15.0);
+        assert_eq!(payments.len(), 12);
+        assert_eq!(payments[0].fee, 1_500);
+        let (principal, fee) = total(&payments);
+        assert_eq!(principal, 120_000);
+        assert!((9_900..10_100).contains(&fee), "fee {}", fee);
+
+        assert_eq!(total(&with_rate(120_000, 12, 0.0)), (120_000, 0));
+    }
+
+    #[test]
+    fn test_revolving() {
+        let payments = revolving(25_000, 10_000, 12.0);
+        let principals: Vec<i64> = payments.iter().map(|p| p.principal).collect();
+        assert_eq!(principals, [10_000, 10_000, 5_000]);
+        assert_eq!(payments[1].fee, 150);
+    }
+
+    #[test]
+    fn test_due_dates() {
+        let first = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let dates = due_dates(first, 31, 2);
+        assert_eq!(dates[0], NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+        assert_eq!(dates[1], NaiveDate::from_ymd_opt(2026, 2, 28).unwrap());
+    }
+}
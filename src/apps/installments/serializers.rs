@@ -0,0 +1,133 @@
+//! Installment serializers for request/response handling
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::models::{InstallmentPayment, InstallmentPlan, PaymentPlanType};
+
+/// Request payload for paying a transaction in installments
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct CreateInstallmentPlanRequest {
+    #[serde(default)]
+    pub plan_type: PaymentPlanType,
+    /// Installment plans: number of monthly payments
+    #[validate(range(min = 2, max = 120, message = "Count must be between 2 and 120"))]
+    pub count: Option<u32>,
+    /// Installment plans: total fee in yen, instead of `interest_rate`
+    #[validate(range(min = 0, message = "Fee must not be negative"))]
+    pub fee: Option<i64>,
+    /// Annual interest rate in percent (実質年率)
+    #[validate(range(min = 0.0, max = 20.0, message = "Interest rate must be between 0 and 20"))]
+    pub interest_rate: Option<f64>,
+    /// Revolving plans: principal paid every month
+    #[validate(range(min = 1, message = "Monthly principal must be positive"))]
+    pub monthly_principal: Option<i64>,
+    /// First payment month as `YYYY-MM`; defaults to the card's first
+    /// payment after the purchase, or the month after it
+    pub first_payment_month: Option<String>,
+}
+
+/// One scheduled payment of an installment plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallmentPaymentResponse {
+    /// 1-based payment number
+    pub number: u32,
+    /// Due date as `YYYY-MM-DD`
+    pub due_date: String,
+    pub principal: i64,
+    /// Fee or interest part
+    pub fee: i64,
+    /// `principal + fee`
+    pub amount: i64,
+    /// Whether the due date has passed
+    pub paid: bool,
+}
+
+/// Response payload for an installment plan with its schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallmentPlanResponse {
+    pub id: i64,
+    pub transaction_id: i64,
+    pub plan_type: PaymentPlanType,
+    /// Number of payments
+    pub count: u32,
+    /// Purchase amount being paid off
+    pub principal: i64,
+    /// Sum of the fees of all payments
+    pub total_fee: i64,
+    pub interest_rate: Option<f64>,
+    pub monthly_principal: Option<i64>,
+    /// First payment month as `YYYY-MM`
+    pub first_payment_month: String,
+    pub paid_count: u32,
+    /// Principal of the payments not yet due (the remaining liability)
+    pub remaining_principal: i64,
+    /// Principal and fees of the payments not yet due
+    pub remaining_amount: i64,
+    pub payments: Vec<InstallmentPaymentResponse>,
+    pub created_at: String,
+}
+
+/// Response wrapper for list of installment plans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallmentPlanListResponse {
+    pub count: usize,
+    /// Sum of `remaining_principal` over the plans
+    pub remaining_principal: i64,
+    /// Sum of `remaining_amount` over the plans
+    pub remaining_amount: i64,
+    pub results: Vec<InstallmentPlanResponse>,
+}
+
+impl InstallmentPaymentResponse {
+    pub fn new(payment: &InstallmentPayment, today: NaiveDate) -> Self {
+        Self {
+            number: payment.number as u32,
+            due_date: payment.due_date.format("%Y-%m-%d").to_string(),
+            principal: payment.principal,
+            fee: payment.fee,
+            amount: payment.amount(),
+            paid: payment.due_date <= today,
+        }
+    }
+}
+
+impl InstallmentPlanResponse {
+    /// Builds the response; `payments` are the plan's, in order
+    pub fn new(plan: InstallmentPlan, payments: &[InstallmentPayment], today: NaiveDate) -> Self {
+        let remaining: Vec<&InstallmentPayment> =
+            payments.iter().filter(|p| p.due_date > today).collect();
+
+        Self {
+            id: plan.id.unwrap_or(0),
+            transaction_id: plan.transaction_id,
+            plan_type: plan.get_plan_type(),
+            count: payments.len() as u32,
+            principal: plan.principal,
+            total_fee: payments.iter().map(|p| p.fee).sum(),
+            interest_rate: plan.interest_rate,
+            monthly_principal: plan.monthly_principal,
+            first_payment_month: plan.first_payment_month.format("%Y-%m").to_string(),
+            paid_count: (payments.len() - remaining.len()) as u32,
+            remaining_principal: remaining.iter().map(|p| p.principal).sum(),
+            remaining_amount: remaining.iter().map(|p| p.amount()).sum(),
+            payments: payments
+                .iter()
+                .map(|p| InstallmentPaymentResponse::new(p, today))
+                .collect(),
+            created_at: plan.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl InstallmentPlanListResponse {
+    pub fn new(results: Vec<InstallmentPlanResponse>) -> Self {
+        Self {
+            count: results.len(),
+            remaining_principal: results.iter().map(|r| r.remaining_principal).sum(),
+            remaining_amount: results.iter().map(|r| r.remaining_amount).sum(),
+            results,
+        }
+    }
+}
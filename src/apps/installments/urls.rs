@@ -0,0 +1,10 @@
+//! URL routing for installments app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the installments app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new().endpoint(views::list_plans)
+}
@@ -0,0 +1,408 @@
+//! Installment views (API endpoints)
+//!
+//! The plan of a transaction is mounted under
+//! `/api/transactions/{id}/installments/`.
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveTime, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, Json, Path, Request, Response, StatusCode};
+use std::collections::{HashMap, HashSet};
+use validator::Validate;
+
+use crate::apps::accounts::models::Account;
+use crate::apps::cards::billing::{first_of_month, period_containing};
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::periods::reject_if_locked;
+use crate::apps::transactions::models::{Transaction, TransactionType};
+use crate::apps::transactions::views::active_transactions;
+use super::models::{InstallmentPayment, InstallmentPlan, PaymentPlanType};
+use super::schedule::{self, ScheduledAmount};
+use super::serializers::{
+    CreateInstallmentPlanRequest, InstallmentPlanListResponse, InstallmentPlanResponse,
+};
+
+/// Longest schedule a plan may have, in months
+const MAX_PAYMENTS: i64 = 120;
+
+/// Scheduled payments keyed by the id of the transaction they pay off
+pub type Schedules = HashMap<i64, Vec<InstallmentPayment>>;
+
+fn plan_not_found(transaction_id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Transaction {} has no installment plan", transaction_id),
+    )
+}
+
+/// Start of `date`, for checking payment dates against the closed period
+fn start_of(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Returns the plan of a transaction, if any
+async fn plan_for(transaction_id: i64) -> ViewResult<Option<InstallmentPlan>> {
+    let plans = InstallmentPlan::objects().all().all().await?;
+    Ok(plans
+        .into_iter()
+        .find(|p| p.transaction_id == transaction_id))
+}
+
+/// Returns the payments of a plan in order
+async fn payments_of(plan_id: i64) -> ViewResult<Vec<InstallmentPayment>> {
+    let mut payments: Vec<InstallmentPayment> = InstallmentPayment::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|p| p.plan_id == plan_id)
+        .collect();
+    payments.sort_by_key(|p| p.number);
+    Ok(payments)
+}
+
+/// Returns the scheduled payments of every plan, keyed by transaction id
+///
+/// Includes plans of trashed transactions; callers look up the
+/// transactions they are working with.
+pub async fn schedules() -> ViewResult<Schedules> {
+    let transaction_ids: HashMap<i64, i64> = InstallmentPlan::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter_map(|p| p.id.map(|id| (id, p.transaction_id)))
+        .collect();
+
+    let mut schedules = Schedules::new();
+    for payment in InstallmentPayment::objects().all().all().await? {
+        if let Some(transaction_id) = transaction_ids.get(&payment.plan_id) {
+            schedules.entry(*transaction_id).or_default().push(payment);
+        }
+    }
+    for payments in schedules.values_mut() {
+        payments.sort_by_key(|p| p.number);
+    }
+    Ok(schedules)
+}
+
+/// Whether a transaction is paid off by a plan
+pub async fn has_plan(transaction_id: i64) -> ViewResult<bool> {
+    Ok(plan_for(transaction_id).await?.is_some())
+}
+
+/// Deletes the plan of a transaction and its payments, if any
+pub async fn delete_for_transaction(transaction_id: i64) -> ViewResult<()> {
+    let Some(plan) = plan_for(transaction_id).await? else {
+        return Ok(());
+    };
+    let Some(plan_id) = plan.id else {
+        return Ok(());
+    };
+    let manager = InstallmentPayment::objects();
+    for payment in payments_of(plan_id).await? {
+        if let Some(id) = payment.id {
+            manager.delete(id).await?;
+        }
+    }
+    InstallmentPlan::objects().delete(plan_id).await?;
+    Ok(())
+}
+
+/// Computes the payment amounts of a plan, or the reason the request is
+/// invalid
+fn plan_amounts(
+    principal: i64,
+    create_req: &CreateInstallmentPlanRequest,
+) -> Result<Vec<ScheduledAmount>, String> {
+    match create_req.plan_type {
+        PaymentPlanType::Installment => {
+            let count = create_req.count.ok_or("Installment plans need count")?;
+            if create_req.monthly_principal.is_some() {
+                return Err("monthly_principal is only for revolving plans".to_string());
+            }
+            match (create_req.fee, create_req.interest_rate) {
+                (Some(_), Some(_)) => Err("Give either fee or interest_rate, not both".to_string()),
+                (fee, None) => Ok(schedule::with_fee(principal, count, fee.unwrap_or(0))),
+                (None, Some(rate)) => Ok(schedule::with_rate(principal, count, rate)),
+            }
+        }
+        PaymentPlanType::Revolving => {
+            let monthly_principal = create_req
+                .monthly_principal
+                .ok_or("Revolving plans need monthly_principal")?;
+            if create_req.count.is_some() || create_req.fee.is_some() {
+                return Err(
+                    "Revolving plans take monthly_principal and interest_rate only".to_string(),
+                );
+            }
+            // Rounds up without overflowing for a huge monthly_principal
+            if principal > 0 && (principal - 1) / monthly_principal + 1 > MAX_PAYMENTS {
+                return Err(format!(
+                    "monthly_principal is too small to pay off {} within {} months",
+                    principal, MAX_PAYMENTS
+                ));
+            }
+            Ok(schedule::revolving(
+                principal,
+                monthly_principal,
+                create_req.interest_rate.unwrap_or(0.0),
+            ))
+        }
+    }
+}
+
+/// Parses a `YYYY-MM` month into its first day
+fn parse_month(month: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").ok()
+}
+
+/// List installment plans of active transactions with the remaining
+/// liability
+///
+/// GET /installments/
+#[get("/", name = "installments_list")]
+pub async fn list_plans() -> ViewResult<Response> {
+    let transaction_ids: HashSet<i64> = active_transactions()
+        .await?
+        .into_iter()
+        .filter_map(|t| t.id)
+        .collect();
+    let schedules = schedules().await?;
+    let today = Utc::now().date_naive();
+
+    let results: Vec<InstallmentPlanResponse> = InstallmentPlan::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|p| transaction_ids.contains(&p.transaction_id))
+        .map(|p| {
+            let payments = schedules.get(&p.transaction_id).map(Vec::as_slice);
+            InstallmentPlanResponse::new(p, payments.unwrap_or_default(), today)
+        })
+        .collect();
+
+    let response = InstallmentPlanListResponse::new(results);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get the installment plan of a transaction with its schedule
+///
+/// GET /transactions/{id}/installments/
+#[get("/{id}/installments/", name = "transactions_installments_get")]
+pub async fn get_plan(Path(transaction_id): Path<i64>) -> ViewResult<Response> {
+    let Some(plan) = plan_for(transaction_id).await? else {
+        return plan_not_found(transaction_id);
+    };
+    let payments = payments_of(plan.id.unwrap_or(0)).await?;
+
+    let response = InstallmentPlanResponse::new(plan, &payments, Utc::now().date_naive());
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Pay an expense transaction in installments
+///
+/// POST /transactions/{id}/installments/
+///
+/// Payments fall on the card's payment day when the transaction is on a
+/// credit card, otherwise on the day of the month of the purchase.
+#[post("/{id}/installments/", name = "transactions_installments_create")]
+pub async fn create_plan_view(
+    request: Request,
+    Path(transaction_id): Path<i64>,
+    Json(create_req): Json<CreateInstallmentPlanRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_plan(transaction_id, create_req)).await
+}
+
+async fn create_plan(
+    transaction_id: i64,
+    create_req: CreateInstallmentPlanRequest,
+) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+
+    let transaction = Transaction::objects()
+        .get(transaction_id)
+        .first()
+        .await?
+        .filter(|t| !t.is_deleted());
+    let transaction = match transaction {
+        Some(t) if t.get_transaction_type() == TransactionType::Expense => t,
+        Some(_) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Transaction {} is not an expense", transaction_id),
+            );
+        }
+        None => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                &format!("Transaction with id {} not found", transaction_id),
+            );
+        }
+    };
+    if plan_for(transaction_id).await?.is_some() {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!(
+                "Transaction {} already has an installment plan",
+                transaction_id
+            ),
+        );
+    }
+
+    let amounts = match plan_amounts(transaction.amount, &create_req) {
+        Ok(amounts) => amounts,
+        Err(message) => return error_response(StatusCode::BAD_REQUEST, &message),
+    };
+
+    // Card purchases follow the card's billing; anything else is paid
+    // monthly on the day of the purchase, starting the following month
+    let purchase_date = transaction.transaction_date.date_naive();
+    let billing_days = match transaction.account_id {
+        Some(account_id) => Account::objects()
+            .get(account_id)
+            .first()
+            .await?
+            .and_then(|a| a.billing_days()),
+        None => None,
+    };
+    let (default_month, payment_day) = match billing_days {
+        Some((closing_day, payment_day)) => {
+            let period = period_containing(purchase_date, closing_day, payment_day);
+            (first_of_month(period.payment), payment_day)
+        }
+        None => (
+            first_of_month(purchase_date) + Months::new(1),
+            purchase_date.day(),
+        ),
+    };
+    let first_payment_month = match create_req.first_payment_month.as_deref() {
+        Some(month) => match parse_month(month) {
+            Some(first) if first >= first_of_month(purchase_date) => first,
+            Some(_) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    "first_payment_month must not be before the purchase",
+                );
+            }
+            None => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    "first_payment_month must be YYYY-MM",
+                );
+            }
+        },
+        None => default_month,
+    };
+    let due_dates = schedule::due_dates(first_payment_month, payment_day, amounts.len());
+
+    // The plan changes how the purchase is counted from its first payment
+    let mut dates = vec![transaction.transaction_date];
+    dates.extend(due_dates.first().copied().map(start_of));
+    if let Some(locked) = reject_if_locked(&dates).await? {
+        return Ok(locked);
+    }
+
+    let plan = InstallmentPlan {
+        id: None,
+        transaction_id,
+        plan_type: create_req.plan_type.to_string(),
+        principal: transaction.amount,
+        interest_rate: create_req.interest_rate,
+        monthly_principal: create_req.monthly_principal,
+        first_payment_month,
+        created_at: Utc::now(),
+    };
+    let created = InstallmentPlan::objects().create(&plan).await?;
+    let plan_id = created.id.unwrap_or(0);
+
+    let manager = InstallmentPayment::objects();
+    let mut payments = Vec::with_capacity(amounts.len());
+    for (i, (amount, due_date)) in amounts.into_iter().zip(due_dates).enumerate() {
+        let payment = InstallmentPayment {
+            id: None,
+            plan_id,
+            number: i as i32 + 1,
+            due_date,
+            principal: amount.principal,
+            fee: amount.fee,
+        };
+        payments.push(manager.create(&payment).await?);
+    }
+
+    let response = InstallmentPlanResponse::new(created, &payments, Utc::now().date_naive());
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Cancel the installment plan of a transaction
+///
+/// DELETE /transactions/{id}/installments/
+///
+/// The purchase is billed in full again.
+#[delete("/{id}/installments/", name = "transactions_installments_delete")]
+pub async fn delete_plan_view(
+    request: Request,
+    Path(transaction_id): Path<i64>,
+) -> ViewResult<Response> {
+    idempotent(&request, delete_plan(transaction_id)).await
+}
+
+async fn delete_plan(transaction_id: i64) -> ViewResult<Response> {
+    let Some(plan) = plan_for(transaction_id).await? else {
+        return plan_not_found(transaction_id);
+    };
+
+    // Cancelling bills the purchase in full again from its date
+    let mut dates: Vec<DateTime<Utc>> = Transaction::objects()
+        .get(transaction_id)
+        .first()
+        .await?
+        .map(|t| t.transaction_date)
+        .into_iter()
+        .collect();
+    let payments = payments_of(plan.id.unwrap_or(0)).await?;
+    dates.extend(payments.first().map(|p| start_of(p.due_date)));
+    if let Some(locked) = reject_if_locked(&dates).await? {
+        return Ok(locked);
+    }
+    delete_for_transaction(transaction_id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revolving(monthly_principal: i64) -> CreateInstallmentPlanRequest {
+        CreateInstallmentPlanRequest {
+            plan_type: PaymentPlanType::Revolving,
+            count: None,
+            fee: None,
+            interest_rate: Some(15.0),
+            monthly_principal: Some(monthly_principal),
+            first_payment_month: None,
+        }
+    }
+
+    #[test]
+    fn test_revolving_monthly_principal_too_small() {
+        // 120 payments of 1,000 pay off 120,000 exactly
+        assert_eq!(plan_amounts(120_000, &revolving(1_000)).unwrap().len(), 120);
+        let err = plan_amounts(120_001, &revolving(1_000)).unwrap_err();
+        assert!(err.contains("too small"), "{}", err);
+    }
+
+    #[test]
+    fn test_revolving_huge_monthly_principal() {
+        // Paid off at once instead of overflowing
+        assert_eq!(
+            plan_amounts(120_000, &revolving(i64::MAX)).unwrap().len(),
+            1
+        );
+    }
+}
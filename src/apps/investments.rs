@@ -0,0 +1,19 @@
+//! Investments app for stocks and funds held in NISA, iDeCo and other
+//! securities accounts
+//!
+//! Holdings are not stored: they are replayed from the buy, sell, dividend
+//! and fee transactions of each account with the moving average cost
+//! method (移動平均法) used by Japanese brokers. Prices come from CSV files
+//! imported with `manage import_prices`; a holding without an imported
+//! price is valued at its last trade price.
+
+pub mod costing;
+pub mod models;
+pub mod prices;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
@@ -0,0 +1,133 @@
+//! Moving average cost method (移動平均法)
+//!
+//! A buy adds its amount and commission to the cost basis; a sell removes
+//! the average cost of the units sold, and the rest of its proceeds after
+//! commission is the realized gain.
+
+use chrono::NaiveDate;
+
+use super::models::InvestmentTransactionType;
+
+/// Quantities closer than this count as equal, for fractional units
+const EPSILON: f64 = 1e-9;
+
+/// An investment transaction as far as costing is concerned
+#[derive(Debug, Clone, Copy)]
+pub struct Trade {
+    pub kind: InvestmentTransactionType,
+    pub date: NaiveDate,
+    pub quantity: f64,
+    pub price: f64,
+    pub amount: i64,
+    pub fee: i64,
+}
+
+/// What an account holds of one security after its trades
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    pub quantity: f64,
+    pub cost_basis: i64,
+    pub realized_gain: i64,
+    pub dividends: i64,
+    pub fees: i64,
+    /// Date and price of the latest buy or sell
+    pub last_trade: Option<(NaiveDate, f64)>,
+}
+
+impl Position {
+    /// Applies one trade; fails when selling more than is held
+    pub fn apply(&mut self, trade: &Trade) -> Result<(), String> {
+        match trade.kind {
+            InvestmentTransactionType::Buy => {
+                self.quantity += trade.quantity;
+                self.cost_basis += trade.amount + trade.fee;
+                self.last_trade = Some((trade.date, trade.price));
+            }
+            InvestmentTransactionType::Sell => {
+                if trade.quantity > self.quantity + EPSILON {
+                    return Err(format!(
+                        "Cannot sell {} units on {}; only {} are held",
+                        trade.quantity, trade.date, self.quantity
+                    ));
+                }
+                let remaining = self.quantity - trade.quantity;
+                let removed = if remaining.abs() < EPSILON {
+                    self.quantity = 0.0;
+                    self.cost_basis
+                } else {
+                    let removed = self.cost_basis as f64 * trade.quantity / self.quantity;
+                    self.quantity = remaining;
+                    removed.round() as i64
+                };
+                self.cost_basis -= removed;
+                self.realized_gain += trade.amount - trade.fee - removed;
+                self.last_trade = Some((trade.date, trade.price));
+            }
+            InvestmentTransactionType::Dividend => self.dividends += trade.amount,
+            InvestmentTransactionType::Fee => self.fees += trade.amount,
+        }
+        Ok(())
+    }
+
+    /// Cost basis per price unit (取得単価), 0 when nothing is held
+    pub fn average_cost(&self, price_unit: i64) -> f64 {
+        if self.quantity < EPSILON {
+            return 0.0;
+        }
+        self.cost_basis as f64 / self.quantity * price_unit as f64
+    }
+}
+
+/// Replays trades in date order into a position
+pub fn replay<'a>(trades: impl IntoIterator<Item = &'a Trade>) -> Result<Position, String> {
+    let mut position = Position::default();
+    for trade in trades {
+        position.apply(trade)?;
+    }
+    Ok(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(kind: InvestmentTransactionType, quantity: f64, price: f64, fee: i64) -> Trade {
+        Trade {
+            kind,
+            date: NaiveDate::from_ymd_opt(2026, 1, 5).unwrap(),
+            quantity,
+            price,
+            amount: (quantity * price).round() as i64,
+            fee,
+        }
+    }
+
+    #[test]
+    fn test_sell_removes_average_cost() {
+        let trades = [
+            trade(InvestmentTransactionType::Buy, 100.0, 1000.0, 500),
+            trade(InvestmentTransactionType::Buy, 100.0, 1200.0, 500),
+            trade(InvestmentTransactionType::Sell, 50.0, 1500.0, 300),
+        ];
+        let position = replay(&trades).unwrap();
+
+        // Average cost (100,500 + 120,500) / 200 = 1,105
+        assert_eq!(position.quantity, 150.0);
+        assert_eq!(position.cost_basis, 165_750);
+        assert_eq!(position.realized_gain, 75_000 - 300 - 55_250);
+        assert_eq!(position.average_cost(1), 1105.0);
+    }
+
+    #[test]
+    fn test_selling_everything_clears_cost() {
+        let trades = [
+            trade(InvestmentTransactionType::Buy, 3.0, 1000.0, 0),
+            trade(InvestmentTransactionType::Sell, 3.0, 900.0, 0),
+        ];
+        let position = replay(&trades).unwrap();
+
+        assert_eq!(position.cost_basis, 0);
+        assert_eq!(position.realized_gain, -300);
+        assert!(replay(&[trades[1]]).is_err());
+    }
+}
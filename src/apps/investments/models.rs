@@ -0,0 +1,172 @@
+//! Investment model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::costing::Trade;
+
+/// Kind of investment transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InvestmentTransactionType {
+    Buy,
+    Sell,
+    /// 分配金・配当金 received
+    Dividend,
+    /// Fee not included in a trade (e.g. 口座管理料)
+    Fee,
+}
+
+impl std::fmt::Display for InvestmentTransactionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InvestmentTransactionType::Buy => write!(f, "buy"),
+            InvestmentTransactionType::Sell => write!(f, "sell"),
+            InvestmentTransactionType::Dividend => write!(f, "dividend"),
+            InvestmentTransactionType::Fee => write!(f, "fee"),
+        }
+    }
+}
+
+impl std::str::FromStr for InvestmentTransactionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "buy" => Ok(InvestmentTransactionType::Buy),
+            "sell" => Ok(InvestmentTransactionType::Sell),
+            "dividend" => Ok(InvestmentTransactionType::Dividend),
+            "fee" => Ok(InvestmentTransactionType::Fee),
+            _ => Err(format!("Invalid investment transaction type: {}", s)),
+        }
+    }
+}
+
+/// A stock, ETF or fund
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "investments", table_name = "securities")]
+pub struct Security {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Code matched against price files (unique, e.g. "7203" or "0331418A")
+    #[field(max_length = 20)]
+    pub code: String,
+
+    /// Security name (e.g., "eMAXIS Slim 全世界株式")
+    #[field(max_length = 100)]
+    pub name: String,
+
+    /// Units a price is quoted for (10000 for funds priced per 1万口)
+    pub price_unit: i64,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Security {
+    /// Yen value of `quantity` units at `price`
+    pub fn value_of(&self, quantity: f64, price: f64) -> i64 {
+        (quantity * price / self.price_unit.max(1) as f64).round() as i64
+    }
+}
+
+/// The closing price of a security on a day
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "investments", table_name = "security_prices")]
+pub struct SecurityPrice {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Security priced (foreign key)
+    pub security_id: i64,
+
+    /// Day of the price (unique per security)
+    pub price_date: NaiveDate,
+
+    /// Price per price unit
+    pub price: f64,
+}
+
+/// A buy, sell, dividend or fee in an investment account
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "investments", table_name = "investment_transactions")]
+pub struct InvestmentTransaction {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Investment account (foreign key)
+    pub account_id: i64,
+
+    /// Security traded (foreign key)
+    pub security_id: i64,
+
+    /// Buy, sell, dividend or fee - stored as string
+    #[field(max_length = 20)]
+    pub transaction_type: String,
+
+    /// Trade date (約定日)
+    pub trade_date: NaiveDate,
+
+    /// Units bought or sold (for dividends, optionally the units held)
+    pub quantity: f64,
+
+    /// Price per price unit (for dividends, optionally the dividend per unit)
+    pub price: f64,
+
+    /// Amount in yen, excluding the fee
+    pub amount: i64,
+
+    /// Commission paid on the trade
+    pub fee: i64,
+
+    /// Description or memo
+    #[field(max_length = 500)]
+    pub description: String,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl InvestmentTransaction {
+    /// Gets the transaction type as enum
+    pub fn get_transaction_type(&self) -> InvestmentTransactionType {
+        self.transaction_type
+            .parse()
+            .unwrap_or(InvestmentTransactionType::Buy)
+    }
+
+    /// The transaction for costing
+    pub fn to_trade(&self) -> Trade {
+        Trade {
+            kind: self.get_transaction_type(),
+            date: self.trade_date,
+            quantity: self.quantity,
+            price: self.price,
+            amount: self.amount,
+            fee: self.fee,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_investment_transaction_type_wire_values() {
+        assert_wire_values(&[
+            InvestmentTransactionType::Buy,
+            InvestmentTransactionType::Sell,
+            InvestmentTransactionType::Dividend,
+            InvestmentTransactionType::Fee,
+        ]);
+    }
+}
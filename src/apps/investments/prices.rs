@@ -0,0 +1,173 @@
+//! Price history imported from CSV files (shared with `manage import_prices`)
+//!
+//! Each line holds a security code, a date and a price:
+//!
+//! ```text
+//! code,date,price
+//! 7203,2026-01-05,2850.5
+//! 0331418A,2026/01/05,"31,245"
+//! ```
+//!
+//! The header line is optional, dates may use `-` or `/`, and quoted
+//! prices may contain thousands separators. Blank lines and lines starting
+//! with `#` are skipped.
+
+use chrono::NaiveDate;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use std::collections::{BTreeSet, HashMap};
+
+use super::models::{Security, SecurityPrice};
+
+/// One price read from a CSV file
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceRow {
+    pub code: String,
+    pub date: NaiveDate,
+    pub price: f64,
+}
+
+/// What an import changed
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    /// Prices added
+    pub created: usize,
+    /// Prices that replaced a different price on the same day
+    pub updated: usize,
+    /// Prices already recorded
+    pub unchanged: usize,
+    /// Codes without a security, whose rows were skipped
+    pub unknown_codes: BTreeSet<String>,
+}
+
+/// Splits a CSV line on commas outside double quotes
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    for c in line.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            _ => {
+                if let Some(field) = fields.last_mut() {
+                    field.push(c);
+                }
+            }
+        }
+    }
+    fields.into_iter().map(|f| f.trim().to_string()).collect()
+}
+
+fn parse_date(field: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(field, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(field, "%Y/%m/%d"))
+        .ok()
+}
+
+/// Parses the rows of a price file; errors name the 1-based line
+pub fn parse_csv(text: &str) -> Result<Vec<PriceRow>, String> {
+    let mut rows = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_start_matches('\u{feff}').trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields = split_fields(line);
+        let [code, date, price] = fields.as_slice() else {
+            return Err(format!(
+                "Line {}: expected code,date,price but found {} field(s)",
+                index + 1,
+                fields.len()
+            ));
+        };
+        if index == 0 && code.eq_ignore_ascii_case("code") {
+            continue;
+        }
+
+        let date = parse_date(date)
+            .ok_or_else(|| format!("Line {}: invalid date '{}'", index + 1, date))?;
+        let price: f64 = price
+            .replace(',', "")
+            .parse()
+            .ok()
+            .filter(|p: &f64| p.is_finite() && *p >= 0.0)
+            .ok_or_else(|| format!("Line {}: invalid price '{}'", index + 1, price))?;
+        rows.push(PriceRow {
+            code: code.clone(),
+            date,
+            price,
+        });
+    }
+    Ok(rows)
+}
+
+/// Saves prices, replacing the price of a security on the same day
+pub async fn import_rows(rows: &[PriceRow]) -> ViewResult<ImportSummary> {
+    let securities: HashMap<String, i64> = Security::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter_map(|s| Some((s.code, s.id?)))
+        .collect();
+    let manager = SecurityPrice::objects();
+    let mut existing: HashMap<(i64, NaiveDate), SecurityPrice> = manager
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .map(|p| ((p.security_id, p.price_date), p))
+        .collect();
+
+    let mut summary = ImportSummary::default();
+    for row in rows {
+        let Some(&security_id) = securities.get(&row.code) else {
+            summary.unknown_codes.insert(row.code.clone());
+            continue;
+        };
+        match existing.get_mut(&(security_id, row.date)) {
+            Some(price) if price.price == row.price => summary.unchanged += 1,
+            Some(price) => {
+                price.price = row.price;
+                manager.update(price).await?;
+                summary.updated += 1;
+            }
+            None => {
+                let price = SecurityPrice {
+                    id: None,
+                    security_id,
+                    price_date: row.date,
+                    price: row.price,
+                };
+                let created = manager.create(&price).await?;
+                existing.insert((security_id, row.date), created);
+                summary.created += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_accepts_header_slashes_and_separators() {
+        let rows = parse_csv(
+            "code,date,price\n7203,2026-01-05,2850.5\n\n0331418A,2026/01/05,\"31,245\"\n",
+        )
+        .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].price, 2850.5);
+        assert_eq!(rows[1].code, "0331418A");
+        assert_eq!(rows[1].date, NaiveDate::from_ymd_opt(2026, 1, 5).unwrap());
+        assert_eq!(rows[1].price, 31245.0);
+    }
+
+    #[test]
+    fn test_parse_csv_reports_line() {
+        let err = parse_csv("7203,2026-01-05,2850\n7203,yesterday,2850\n").unwrap_err();
+        assert!(err.starts_with("Line 2:"));
+    }
+}
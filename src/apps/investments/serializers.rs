@@ -0,0 +1,348 @@
+//! Investment serializers for request/response handling
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use validator::Validate;
+
+use super::costing::Position;
+use super::models::{InvestmentTransaction, InvestmentTransactionType, Security, SecurityPrice};
+use crate::apps::accounts::models::Account;
+
+/// Request payload for creating a security
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateSecurityRequest {
+    /// Code used by price files (銘柄コード or 協会コード, 1-20 characters)
+    #[validate(length(min = 1, max = 20, message = "Code must be between 1 and 20 characters"))]
+    pub code: String,
+    /// Security name (1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    /// Units a price is quoted for: 1 for stocks, 10000 for funds whose
+    /// 基準価額 is per 1万口
+    #[validate(range(min = 1, message = "Price unit must be positive"))]
+    #[serde(default = "default_price_unit")]
+    pub price_unit: i64,
+}
+
+fn default_price_unit() -> i64 {
+    1
+}
+
+/// Request payload for updating a security
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateSecurityRequest {
+    #[validate(length(min = 1, max = 20, message = "Code must be between 1 and 20 characters"))]
+    pub code: Option<String>,
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+}
+
+/// Response payload for a security with its latest price
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityResponse {
+    pub id: i64,
+    pub code: String,
+    pub name: String,
+    pub price_unit: i64,
+    pub latest_price: Option<f64>,
+    /// Date of the latest price as `YYYY-MM-DD`
+    pub latest_price_date: Option<String>,
+    pub created_at: String,
+}
+
+/// Response payload for security list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityListResponse {
+    pub count: usize,
+    pub results: Vec<SecurityResponse>,
+}
+
+/// A price of a security on a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityPriceResponse {
+    /// Date as `YYYY-MM-DD`
+    pub price_date: String,
+    pub price: f64,
+}
+
+/// Price history of a security, oldest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityPriceListResponse {
+    pub security_id: i64,
+    pub count: usize,
+    pub results: Vec<SecurityPriceResponse>,
+}
+
+/// Request payload for recording an investment transaction
+///
+/// Buys and sells need `quantity` and `price`; their `amount` defaults to
+/// quantity × price ÷ price unit. Dividends and fees need `amount`.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateInvestmentTransactionRequest {
+    pub account_id: i64,
+    pub security_id: i64,
+    pub transaction_type: InvestmentTransactionType,
+    pub trade_date: NaiveDate,
+    #[validate(range(min = 0.0, message = "Quantity must not be negative"))]
+    #[serde(default)]
+    pub quantity: f64,
+    #[validate(range(min = 0.0, message = "Price must not be negative"))]
+    #[serde(default)]
+    pub price: f64,
+    /// Trade amount in yen, excluding the fee
+    #[validate(range(min = 0, message = "Amount must not be negative"))]
+    #[serde(default)]
+    pub amount: Option<i64>,
+    /// Commission paid on the trade (売買手数料)
+    #[validate(range(min = 0, message = "Fee must not be negative"))]
+    #[serde(default)]
+    pub fee: i64,
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Response payload for an investment transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvestmentTransactionResponse {
+    pub id: i64,
+    pub account_id: i64,
+    pub security_id: i64,
+    pub transaction_type: InvestmentTransactionType,
+    /// Date as `YYYY-MM-DD`
+    pub trade_date: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub amount: i64,
+    pub fee: i64,
+    pub description: String,
+    pub created_at: String,
+}
+
+/// Response payload for investment transaction list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvestmentTransactionListResponse {
+    pub count: usize,
+    pub results: Vec<InvestmentTransactionResponse>,
+}
+
+/// A security held in an account, valued with the average cost method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingResponse {
+    pub account_id: i64,
+    pub security_id: i64,
+    pub code: String,
+    pub name: String,
+    pub quantity: f64,
+    /// Cost basis per price unit (取得単価)
+    pub average_cost: f64,
+    /// Cost of the units held, including buy fees (取得価額)
+    pub cost_basis: i64,
+    /// Latest price on or before the valuation date, if any
+    pub price: Option<f64>,
+    /// Date of `price` as `YYYY-MM-DD`
+    pub price_date: Option<String>,
+    /// Value at `price`, or the cost basis when there is no price
+    pub market_value: i64,
+    /// 評価損益
+    pub unrealized_gain: i64,
+    /// 実現損益 of the sells so far, after their fees
+    pub realized_gain: i64,
+    pub dividends: i64,
+    /// Fee transactions; trade commissions are in the cost basis or the
+    /// realized gain
+    pub fees: i64,
+}
+
+/// Holdings of one account with their totals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountHoldingsResponse {
+    pub account_id: i64,
+    pub account_name: String,
+    pub cost_basis: i64,
+    pub market_value: i64,
+    pub unrealized_gain: i64,
+    pub realized_gain: i64,
+    pub dividends: i64,
+    pub holdings: Vec<HoldingResponse>,
+}
+
+/// Holdings of every investment account on a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingsReportResponse {
+    /// Valuation date as `YYYY-MM-DD`
+    pub date: String,
+    pub cost_basis: i64,
+    pub market_value: i64,
+    pub unrealized_gain: i64,
+    pub accounts: Vec<AccountHoldingsResponse>,
+}
+
+impl SecurityResponse {
+    /// Builds the response with the latest of the security's `prices`
+    pub fn new(security: &Security, prices: &[SecurityPrice]) -> Self {
+        let latest = prices.iter().max_by_key(|p| p.price_date);
+        Self {
+            id: security.id.unwrap_or(0),
+            code: security.code.clone(),
+            name: security.name.clone(),
+            price_unit: security.price_unit,
+            latest_price: latest.map(|p| p.price),
+            latest_price_date: latest.map(|p| p.price_date.format("%Y-%m-%d").to_string()),
+            created_at: security.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl SecurityListResponse {
+    pub fn new(results: Vec<SecurityResponse>) -> Self {
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
+
+impl From<&SecurityPrice> for SecurityPriceResponse {
+    fn from(price: &SecurityPrice) -> Self {
+        Self {
+            price_date: price.price_date.format("%Y-%m-%d").to_string(),
+            price: price.price,
+        }
+    }
+}
+
+impl From<&InvestmentTransaction> for InvestmentTransactionResponse {
+    fn from(transaction: &InvestmentTransaction) -> Self {
+        Self {
+            id: transaction.id.unwrap_or(0),
+            account_id: transaction.account_id,
+            security_id: transaction.security_id,
+            transaction_type: transaction.get_transaction_type(),
+            trade_date: transaction.trade_date.format("%Y-%m-%d").to_string(),
+            quantity: transaction.quantity,
+            price: transaction.price,
+            amount: transaction.amount,
+            fee: transaction.fee,
+            description: transaction.description.clone(),
+            created_at: transaction.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl InvestmentTransactionListResponse {
+    pub fn new(transactions: &[InvestmentTransaction]) -> Self {
+        let results: Vec<InvestmentTransactionResponse> =
+            transactions.iter().map(Into::into).collect();
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
+
+impl HoldingResponse {
+    /// Values a position at the latest price on or before `date`, or at
+    /// its last trade price
+    fn new(
+        account_id: i64,
+        security: &Security,
+        position: &Position,
+        prices: &[SecurityPrice],
+        date: NaiveDate,
+    ) -> Self {
+        let imported = prices
+            .iter()
+            .filter(|p| p.price_date <= date)
+            .max_by_key(|p| p.price_date)
+            .map(|p| (p.price_date, p.price));
+        let price = imported.or(position.last_trade);
+        let market_value = match price {
+            Some((_, price)) => security.value_of(position.quantity, price),
+            None => position.cost_basis,
+        };
+
+        Self {
+            account_id,
+            security_id: security.id.unwrap_or(0),
+            code: security.code.clone(),
+            name: security.name.clone(),
+            quantity: position.quantity,
+            average_cost: position.average_cost(security.price_unit),
+            cost_basis: position.cost_basis,
+            price: price.map(|(_, price)| price),
+            price_date: price.map(|(date, _)| date.format("%Y-%m-%d").to_string()),
+            market_value,
+            unrealized_gain: market_value - position.cost_basis,
+            realized_gain: position.realized_gain,
+            dividends: position.dividends,
+            fees: position.fees,
+        }
+    }
+}
+
+impl HoldingsReportResponse {
+    /// Replays the `transactions` traded by `date` into the holdings of
+    /// each of the investment `accounts`
+    ///
+    /// `prices` must hold each security's prices; transactions are applied
+    /// in date order.
+    pub fn new(
+        accounts: &[&Account],
+        securities: &HashMap<i64, Security>,
+        transactions: &[InvestmentTransaction],
+        prices: &HashMap<i64, Vec<SecurityPrice>>,
+        date: NaiveDate,
+    ) -> Self {
+        let mut traded: Vec<&InvestmentTransaction> = transactions
+            .iter()
+            .filter(|t| t.trade_date <= date)
+            .collect();
+        traded.sort_by_key(|t| (t.trade_date, t.id));
+
+        // Writes are checked against the replay, so no trade fails here
+        let mut positions: BTreeMap<(i64, i64), Position> = BTreeMap::new();
+        for t in traded {
+            let _ = positions
+                .entry((t.account_id, t.security_id))
+                .or_default()
+                .apply(&t.to_trade());
+        }
+
+        let accounts: Vec<AccountHoldingsResponse> = accounts
+            .iter()
+            .map(|account| {
+                let account_id = account.id.unwrap_or(0);
+                let holdings: Vec<HoldingResponse> = positions
+                    .range((account_id, i64::MIN)..=(account_id, i64::MAX))
+                    .filter_map(|(&(_, security_id), position)| {
+                        let security = securities.get(&security_id)?;
+                        let own_prices = prices.get(&security_id).map_or(&[][..], Vec::as_slice);
+                        Some(HoldingResponse::new(
+                            account_id, security, position, own_prices, date,
+                        ))
+                    })
+                    .collect();
+                AccountHoldingsResponse {
+                    account_id,
+                    account_name: account.name.clone(),
+                    cost_basis: holdings.iter().map(|h| h.cost_basis).sum(),
+                    market_value: holdings.iter().map(|h| h.market_value).sum(),
+                    unrealized_gain: holdings.iter().map(|h| h.unrealized_gain).sum(),
+                    realized_gain: holdings.iter().map(|h| h.realized_gain).sum(),
+                    dividends: holdings.iter().map(|h| h.dividends).sum(),
+                    holdings,
+                }
+            })
+            .collect();
+
+        Self {
+            date: date.format("%Y-%m-%d").to_string(),
+            cost_basis: accounts.iter().map(|a| a.cost_basis).sum(),
+            market_value: accounts.iter().map(|a| a.market_value).sum(),
+            unrealized_gain: accounts.iter().map(|a| a.unrealized_gain).sum(),
+            accounts,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! URL routing for investments app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the investments app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_securities)
+        .endpoint(views::get_security)
+        .endpoint(views::create_security_view)
+        .endpoint(views::update_security_view)
+        .endpoint(views::delete_security_view)
+        .endpoint(views::list_prices)
+        .endpoint(views::list_investment_transactions)
+        .endpoint(views::get_investment_transaction)
+        .endpoint(views::create_investment_transaction_view)
+        .endpoint(views::delete_investment_transaction_view)
+        .endpoint(views::list_holdings)
+}
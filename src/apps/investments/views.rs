@@ -0,0 +1,486 @@
+//! Investment views (API endpoints)
+
+use chrono::{NaiveDate, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Query, Request, Response, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use validator::Validate;
+
+use crate::apps::accounts::models::{Account, AccountType};
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use super::costing::replay;
+use super::models::{InvestmentTransaction, InvestmentTransactionType, Security, SecurityPrice};
+use super::serializers::{
+    CreateInvestmentTransactionRequest, CreateSecurityRequest, HoldingsReportResponse,
+    InvestmentTransactionListResponse, InvestmentTransactionResponse, SecurityListResponse,
+    SecurityPriceListResponse, SecurityResponse, UpdateSecurityRequest,
+};
+
+/// Query parameters for the price history of a security
+#[derive(Debug, Deserialize)]
+pub struct PriceHistoryQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Query parameters for investment transaction list
+#[derive(Debug, Deserialize)]
+pub struct InvestmentTransactionQuery {
+    pub account_id: Option<i64>,
+    pub security_id: Option<i64>,
+}
+
+/// Query parameters for holdings
+#[derive(Debug, Deserialize)]
+pub struct HoldingsQuery {
+    /// Valuation date (default: today)
+    pub date: Option<NaiveDate>,
+    /// Only this investment account
+    pub account_id: Option<i64>,
+}
+
+fn security_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Security with id {} not found", id),
+    )
+}
+
+fn transaction_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Investment transaction with id {} not found", id),
+    )
+}
+
+/// Returns every security by ID
+pub async fn securities_by_id() -> ViewResult<HashMap<i64, Security>> {
+    Ok(Security::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter_map(|s| Some((s.id?, s)))
+        .collect())
+}
+
+/// Returns the prices of every security by security ID, oldest first
+pub async fn prices_by_security() -> ViewResult<HashMap<i64, Vec<SecurityPrice>>> {
+    let mut prices: HashMap<i64, Vec<SecurityPrice>> = HashMap::new();
+    for price in SecurityPrice::objects().all().all().await? {
+        prices.entry(price.security_id).or_default().push(price);
+    }
+    for list in prices.values_mut() {
+        list.sort_by_key(|p| p.price_date);
+    }
+    Ok(prices)
+}
+
+/// Returns the investment accounts
+pub async fn investment_accounts() -> ViewResult<Vec<Account>> {
+    Ok(Account::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|a| a.get_account_type() == AccountType::Investment)
+        .collect())
+}
+
+/// Returns the transactions of one security in one account, in the order
+/// they are replayed
+async fn position_transactions(
+    account_id: i64,
+    security_id: i64,
+) -> ViewResult<Vec<InvestmentTransaction>> {
+    let mut transactions: Vec<InvestmentTransaction> = InvestmentTransaction::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|t| t.account_id == account_id && t.security_id == security_id)
+        .collect();
+    transactions.sort_by_key(|t| (t.trade_date, t.id));
+    Ok(transactions)
+}
+
+/// Whether another security already uses the code
+async fn code_taken(code: &str, except: Option<i64>) -> ViewResult<bool> {
+    let securities = Security::objects().all().all().await?;
+    Ok(securities.iter().any(|s| s.code == code && s.id != except))
+}
+
+fn code_conflict(code: &str) -> ViewResult<Response> {
+    error_response(
+        StatusCode::CONFLICT,
+        &format!("A security with code {} already exists", code),
+    )
+}
+
+/// List all securities with their latest price
+///
+/// GET /investments/securities/
+#[get("/securities/", name = "investments_securities_list")]
+pub async fn list_securities() -> ViewResult<Response> {
+    let securities = Security::objects().all().all().await?;
+    let prices = prices_by_security().await?;
+
+    let results: Vec<SecurityResponse> = securities
+        .iter()
+        .map(|security| {
+            let own = security
+                .id
+                .and_then(|id| prices.get(&id))
+                .map_or(&[][..], Vec::as_slice);
+            SecurityResponse::new(security, own)
+        })
+        .collect();
+
+    let response = SecurityListResponse::new(results);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get a security with its latest price
+///
+/// GET /investments/securities/{id}/
+#[get("/securities/{id}/", name = "investments_securities_get")]
+pub async fn get_security(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(security) = Security::objects().get(id).first().await? else {
+        return security_not_found(id);
+    };
+    let prices = prices_by_security().await?.remove(&id).unwrap_or_default();
+
+    let response = SecurityResponse::new(&security, &prices);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Create a new security
+///
+/// POST /investments/securities/
+#[post("/securities/", name = "investments_securities_create")]
+pub async fn create_security_view(
+    request: Request,
+    Json(create_req): Json<CreateSecurityRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_security(create_req)).await
+}
+
+async fn create_security(create_req: CreateSecurityRequest) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+    let code = create_req.code.trim().to_string();
+    if code_taken(&code, None).await? {
+        return code_conflict(&code);
+    }
+
+    let security = Security {
+        id: None,
+        code,
+        name: create_req.name,
+        price_unit: create_req.price_unit,
+        created_at: Utc::now(),
+    };
+    let created = Security::objects().create(&security).await?;
+
+    // A new security has no prices yet
+    let response = SecurityResponse::new(&created, &[]);
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Update the code or name of a security
+///
+/// PUT /investments/securities/{id}/
+///
+/// The price unit is fixed, since recorded prices are quoted in it.
+#[put("/securities/{id}/", name = "investments_securities_update")]
+pub async fn update_security_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateSecurityRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, update_security(id, update_req)).await
+}
+
+async fn update_security(id: i64, update_req: UpdateSecurityRequest) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = Security::objects();
+    let Some(mut security) = manager.get(id).first().await? else {
+        return security_not_found(id);
+    };
+
+    if let Some(code) = update_req.code {
+        let code = code.trim().to_string();
+        if code_taken(&code, Some(id)).await? {
+            return code_conflict(&code);
+        }
+        security.code = code;
+    }
+    if let Some(n) = update_req.name {
+        security.name = n;
+    }
+    let updated = manager.update(&security).await?;
+
+    let prices = prices_by_security().await?.remove(&id).unwrap_or_default();
+    let response = SecurityResponse::new(&updated, &prices);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Delete a security that no transaction uses, with its prices
+///
+/// DELETE /investments/securities/{id}/
+#[delete("/securities/{id}/", name = "investments_securities_delete")]
+pub async fn delete_security_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    idempotent(&request, delete_security(id)).await
+}
+
+async fn delete_security(id: i64) -> ViewResult<Response> {
+    let manager = Security::objects();
+    if manager.get(id).first().await?.is_none() {
+        return security_not_found(id);
+    }
+    let transactions = InvestmentTransaction::objects().all().all().await?;
+    if transactions.iter().any(|t| t.security_id == id) {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("Security {} is still used by transactions", id),
+        );
+    }
+
+    let price_manager = SecurityPrice::objects();
+    for price in prices_by_security().await?.remove(&id).unwrap_or_default() {
+        if let Some(price_id) = price.id {
+            price_manager.delete(price_id).await?;
+        }
+    }
+    manager.delete(id).await?;
+
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Price history of a security, oldest first
+///
+/// GET /investments/securities/{id}/prices/?from=2026-01-01&to=2026-03-31
+#[get("/securities/{id}/prices/", name = "investments_securities_prices")]
+pub async fn list_prices(
+    Path(id): Path<i64>,
+    Query(params): Query<PriceHistoryQuery>,
+) -> ViewResult<Response> {
+    if Security::objects().get(id).first().await?.is_none() {
+        return security_not_found(id);
+    }
+
+    let results = prices_by_security()
+        .await?
+        .remove(&id)
+        .unwrap_or_default()
+        .iter()
+        .filter(|p| params.from.is_none_or(|from| p.price_date >= from))
+        .filter(|p| params.to.is_none_or(|to| p.price_date <= to))
+        .map(Into::into)
+        .collect::<Vec<_>>();
+
+    let response = SecurityPriceListResponse {
+        security_id: id,
+        count: results.len(),
+        results,
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// List investment transactions, oldest first
+///
+/// GET /investments/transactions/?account_id=3&security_id=1
+#[get("/transactions/", name = "investments_transactions_list")]
+pub async fn list_investment_transactions(
+    Query(params): Query<InvestmentTransactionQuery>,
+) -> ViewResult<Response> {
+    let mut transactions: Vec<InvestmentTransaction> = InvestmentTransaction::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|t| params.account_id.is_none_or(|id| t.account_id == id))
+        .filter(|t| params.security_id.is_none_or(|id| t.security_id == id))
+        .collect();
+    transactions.sort_by_key(|t| (t.trade_date, t.id));
+
+    let response = InvestmentTransactionListResponse::new(&transactions);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get an investment transaction
+///
+/// GET /investments/transactions/{id}/
+#[get("/transactions/{id}/", name = "investments_transactions_get")]
+pub async fn get_investment_transaction(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(transaction) = InvestmentTransaction::objects().get(id).first().await? else {
+        return transaction_not_found(id);
+    };
+
+    let response = InvestmentTransactionResponse::from(&transaction);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Record a buy, sell, dividend or fee
+///
+/// POST /investments/transactions/
+///
+/// Fails with 409 if a sell, on its date, exceeds the units held.
+#[post("/transactions/", name = "investments_transactions_create")]
+pub async fn create_investment_transaction_view(
+    request: Request,
+    Json(create_req): Json<CreateInvestmentTransactionRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_investment_transaction(create_req)).await
+}
+
+async fn create_investment_transaction(
+    create_req: CreateInvestmentTransactionRequest,
+) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+    let account = Account::objects()
+        .get(create_req.account_id)
+        .first()
+        .await?;
+    if !account.is_some_and(|a| a.get_account_type() == AccountType::Investment) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!(
+                "Account {} is not an investment account",
+                create_req.account_id
+            ),
+        );
+    }
+    let Some(security) = Security::objects()
+        .get(create_req.security_id)
+        .first()
+        .await?
+    else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("Security with id {} not found", create_req.security_id),
+        );
+    };
+
+    let is_trade = matches!(
+        create_req.transaction_type,
+        InvestmentTransactionType::Buy | InvestmentTransactionType::Sell
+    );
+    if is_trade && (create_req.quantity <= 0.0 || create_req.price <= 0.0) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "Buys and sells need a positive quantity and price",
+        );
+    }
+    let amount = create_req
+        .amount
+        .unwrap_or_else(|| security.value_of(create_req.quantity, create_req.price));
+    if amount <= 0 {
+        return error_response(StatusCode::BAD_REQUEST, "Amount must be positive");
+    }
+
+    let transaction = InvestmentTransaction {
+        id: None,
+        account_id: create_req.account_id,
+        security_id: create_req.security_id,
+        transaction_type: create_req.transaction_type.to_string(),
+        trade_date: create_req.trade_date,
+        quantity: create_req.quantity,
+        price: create_req.price,
+        amount,
+        fee: create_req.fee,
+        description: create_req.description,
+        created_at: Utc::now(),
+    };
+
+    // Same-day trades replay in the order they were recorded
+    let mut trades: Vec<_> = position_transactions(transaction.account_id, transaction.security_id)
+        .await?
+        .iter()
+        .map(InvestmentTransaction::to_trade)
+        .collect();
+    let at = trades.partition_point(|t| t.date <= transaction.trade_date);
+    trades.insert(at, transaction.to_trade());
+    if let Err(e) = replay(&trades) {
+        return error_response(StatusCode::CONFLICT, &e);
+    }
+
+    let created = InvestmentTransaction::objects()
+        .create(&transaction)
+        .await?;
+
+    let response = InvestmentTransactionResponse::from(&created);
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Delete an investment transaction
+///
+/// DELETE /investments/transactions/{id}/
+///
+/// Fails with 409 if a later sell would then exceed the units held.
+#[delete("/transactions/{id}/", name = "investments_transactions_delete")]
+pub async fn delete_investment_transaction_view(
+    request: Request,
+    Path(id): Path<i64>,
+) -> ViewResult<Response> {
+    idempotent(&request, delete_investment_transaction(id)).await
+}
+
+async fn delete_investment_transaction(id: i64) -> ViewResult<Response> {
+    let manager = InvestmentTransaction::objects();
+    let Some(transaction) = manager.get(id).first().await? else {
+        return transaction_not_found(id);
+    };
+
+    let trades: Vec<_> = position_transactions(transaction.account_id, transaction.security_id)
+        .await?
+        .iter()
+        .filter(|t| t.id != Some(id))
+        .map(InvestmentTransaction::to_trade)
+        .collect();
+    if let Err(e) = replay(&trades) {
+        return error_response(StatusCode::CONFLICT, &e);
+    }
+
+    manager.delete(id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Holdings of every investment account with market value, cost basis and
+/// unrealized gain
+///
+/// GET /investments/holdings/?date=2026-03-31&account_id=3
+#[get("/holdings/", name = "investments_holdings")]
+pub async fn list_holdings(Query(params): Query<HoldingsQuery>) -> ViewResult<Response> {
+    let date = params.date.unwrap_or_else(|| Utc::now().date_naive());
+    let all_accounts = investment_accounts().await?;
+    let accounts: Vec<&Account> = all_accounts
+        .iter()
+        .filter(|a| params.account_id.is_none_or(|id| a.id == Some(id)))
+        .collect();
+    if let Some(account_id) = params.account_id
+        && accounts.is_empty()
+    {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            &format!("Investment account with id {} not found", account_id),
+        );
+    }
+
+    let transactions = InvestmentTransaction::objects().all().all().await?;
+    let response = HoldingsReportResponse::new(
+        &accounts,
+        &securities_by_id().await?,
+        &transactions,
+        &prices_by_security().await?,
+        date,
+    );
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
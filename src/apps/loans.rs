@@ -0,0 +1,17 @@
+//! Loans app for mortgages (住宅ローン), car loans and other debts
+//!
+//! A loan holds its contract terms; the amortization schedule is computed
+//! from them and any prepayments (繰上返済). Repayment transactions are
+//! linked to the loan, which splits each of them into principal and
+//! interest for the yearly report and the remaining balance.
+
+pub mod amortization;
+pub mod models;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
+pub use views::{active_repayments, loan_summaries};
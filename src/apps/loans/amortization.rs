@@ -0,0 +1,183 @@
+//! Amortization schedule arithmetic for loans
+
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::apps::cards::billing::{day_in_month, first_of_month};
+use super::models::{PrepaymentType, RepaymentMethod};
+
+/// Contract terms of a loan
+#[derive(Debug, Clone, Copy)]
+pub struct LoanTerms {
+    pub principal: i64,
+    /// Annual rate in percent
+    pub annual_rate: f64,
+    pub term_months: u32,
+    /// First payment date; later ones fall on the same day of the month
+    pub first_due: NaiveDate,
+    pub method: RepaymentMethod,
+}
+
+/// A lump-sum prepayment (繰上返済)
+#[derive(Debug, Clone, Copy)]
+pub struct Prepayment {
+    pub date: NaiveDate,
+    pub amount: i64,
+    pub kind: PrepaymentType,
+}
+
+/// One scheduled payment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduleRow {
+    /// 1-based payment number
+    pub number: u32,
+    pub due_date: NaiveDate,
+    pub principal: i64,
+    pub interest: i64,
+    /// Prepaid just before this payment
+    pub prepayment: i64,
+    /// Balance after the payment
+    pub balance: i64,
+}
+
+impl ScheduleRow {
+    /// Principal plus interest
+    pub fn payment(&self) -> i64 {
+        self.principal + self.interest
+    }
+}
+
+/// Monthly payment (元利均等) or monthly principal (元金均等) that pays off
+/// `balance` in `months`
+fn level_amount(method: RepaymentMethod, balance: i64, rate: f64, months: u32) -> i64 {
+    let months = months.max(1);
+    match method {
+        RepaymentMethod::EqualPayment if rate > 0.0 => {
+            (balance as f64 * rate / (1.0 - (1.0 + rate).powi(-(months as i32)))).round() as i64
+        }
+        _ => balance / i64::from(months),
+    }
+}
+
+/// Due date of the `index`-th payment (0-based)
+pub fn due_date(first_due: NaiveDate, index: u32) -> NaiveDate {
+    day_in_month(
+        first_of_month(first_due) + Months::new(index),
+        first_due.day(),
+    )
+}
+
+/// Builds the amortization schedule
+///
+/// Interest is charged monthly on the balance and rounded down to the yen,
+/// as Japanese lenders do; the last payment absorbs the rounding. A
+/// prepayment is applied before the first payment due on or after its date.
+pub fn schedule(terms: &LoanTerms, prepayments: &[Prepayment]) -> Vec<ScheduleRow> {
+    let rate = terms.annual_rate / 100.0 / 12.0;
+    let months = terms.term_months;
+    let mut prepayments = prepayments.to_vec();
+    prepayments.sort_by_key(|p| p.date);
+    let mut prepayments = prepayments.into_iter().peekable();
+
+    let mut balance = terms.principal;
+    let mut level = level_amount(terms.method, balance, rate, months);
+    let mut rows = Vec::new();
+    for index in 0..months {
+        if balance <= 0 {
+            break;
+        }
+        let due = due_date(terms.first_due, index);
+
+        let mut prepaid = 0;
+        while let Some(p) = prepayments.next_if(|p| p.date <= due) {
+            let amount = p.amount.min(balance);
+            balance -= amount;
+            prepaid += amount;
+            if p.kind == PrepaymentType::ReducePayment {
+                level = level_amount(terms.method, balance, rate, months - index);
+            }
+        }
+
+        let interest = (balance as f64 * rate).floor() as i64;
+        let principal = if index + 1 == months {
+            balance
+        } else {
+            match terms.method {
+                RepaymentMethod::EqualPayment => (level - interest).clamp(0, balance),
+                RepaymentMethod::EqualPrincipal => level.min(balance),
+            }
+        };
+        balance -= principal;
+        rows.push(ScheduleRow {
+            number: index + 1,
+            due_date: due,
+            principal,
+            interest,
+            prepayment: prepaid,
+            balance,
+        });
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(method: RepaymentMethod) -> LoanTerms {
+        LoanTerms {
+            principal: 30_000_000,
+            annual_rate: 1.5,
+            term_months: 420,
+            first_due: NaiveDate::from_ymd_opt(2026, 1, 27).unwrap(),
+            method,
+        }
+    }
+
+    #[test]
+    fn test_equal_payment() {
+        let rows = schedule(&terms(RepaymentMethod::EqualPayment), &[]);
+        assert_eq!(rows.len(), 420);
+        assert_eq!(rows[0].payment(), 91_855);
+        assert_eq!(rows[0].interest, 37_500);
+        assert_eq!(rows[419].balance, 0);
+        assert_eq!(rows.iter().map(|r| r.principal).sum::<i64>(), 30_000_000);
+        assert_eq!(
+            rows[1].due_date,
+            NaiveDate::from_ymd_opt(2026, 2, 27).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_equal_principal() {
+        let rows = schedule(&terms(RepaymentMethod::EqualPrincipal), &[]);
+        assert_eq!(rows[0].principal, 71_428);
+        assert_eq!(rows[0].interest, 37_500);
+        assert!(rows[1].payment() < rows[0].payment());
+        assert_eq!(rows[419].balance, 0);
+    }
+
+    #[test]
+    fn test_prepayments() {
+        let prepayment = |kind| Prepayment {
+            date: NaiveDate::from_ymd_opt(2030, 1, 1).unwrap(),
+            amount: 3_000_000,
+            kind,
+        };
+        let base = schedule(&terms(RepaymentMethod::EqualPayment), &[]);
+
+        let shorter = schedule(
+            &terms(RepaymentMethod::EqualPayment),
+            &[prepayment(PrepaymentType::ShortenTerm)],
+        );
+        assert!(shorter.len() < base.len());
+        assert_eq!(shorter[48].prepayment, 3_000_000);
+        assert_eq!(shorter[49].payment(), base[49].payment());
+
+        let lighter = schedule(
+            &terms(RepaymentMethod::EqualPayment),
+            &[prepayment(PrepaymentType::ReducePayment)],
+        );
+        assert_eq!(lighter.len(), base.len());
+        assert!(lighter[49].payment() < base[49].payment());
+    }
+}
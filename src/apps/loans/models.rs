@@ -0,0 +1,212 @@
+//! Loan model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::amortization::{LoanTerms, Prepayment};
+
+/// How a loan is repaid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepaymentMethod {
+    /// 元利均等: the same payment every month
+    #[default]
+    EqualPayment,
+    /// 元金均等: the same principal every month plus interest on the balance
+    EqualPrincipal,
+}
+
+impl std::fmt::Display for RepaymentMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepaymentMethod::EqualPayment => write!(f, "equal_payment"),
+            RepaymentMethod::EqualPrincipal => write!(f, "equal_principal"),
+        }
+    }
+}
+
+impl std::str::FromStr for RepaymentMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "equal_payment" => Ok(RepaymentMethod::EqualPayment),
+            "equal_principal" => Ok(RepaymentMethod::EqualPrincipal),
+            _ => Err(format!("Invalid repayment method: {}", s)),
+        }
+    }
+}
+
+/// What a loan prepayment (繰上返済) reduces
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrepaymentType {
+    /// 期間短縮型: keep the payment, finish earlier
+    #[default]
+    ShortenTerm,
+    /// 返済額軽減型: keep the term, pay less every month
+    ReducePayment,
+}
+
+impl std::fmt::Display for PrepaymentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrepaymentType::ShortenTerm => write!(f, "shorten_term"),
+            PrepaymentType::ReducePayment => write!(f, "reduce_payment"),
+        }
+    }
+}
+
+impl std::str::FromStr for PrepaymentType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "shorten_term" => Ok(PrepaymentType::ShortenTerm),
+            "reduce_payment" => Ok(PrepaymentType::ReducePayment),
+            _ => Err(format!("Invalid prepayment type: {}", s)),
+        }
+    }
+}
+
+/// A loan and its contract terms
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "loans", table_name = "loans")]
+pub struct Loan {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Loan name (e.g., "住宅ローン")
+    #[field(max_length = 100)]
+    pub name: String,
+
+    /// Amount borrowed in yen
+    pub principal: i64,
+
+    /// Annual interest rate in percent
+    pub annual_rate: f64,
+
+    /// Number of monthly payments
+    pub term_months: i32,
+
+    /// Date of the first payment
+    pub start_date: NaiveDate,
+
+    /// Equal payment or equal principal - stored as string
+    #[field(max_length = 20)]
+    pub repayment_method: String,
+
+    /// Account the repayments are withdrawn from (foreign key, optional)
+    #[field(null = true)]
+    pub account_id: Option<i64>,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Loan {
+    /// Gets the repayment method as enum
+    pub fn get_repayment_method(&self) -> RepaymentMethod {
+        self.repayment_method.parse().unwrap_or_default()
+    }
+
+    /// Terms for computing the schedule
+    pub fn terms(&self) -> LoanTerms {
+        LoanTerms {
+            principal: self.principal,
+            annual_rate: self.annual_rate,
+            term_months: u32::try_from(self.term_months).unwrap_or(0),
+            first_due: self.start_date,
+            method: self.get_repayment_method(),
+        }
+    }
+}
+
+/// A prepayment (繰上返済) of a loan
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "loans", table_name = "loan_prepayments")]
+pub struct LoanPrepayment {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Loan prepaid (foreign key)
+    pub loan_id: i64,
+
+    /// Day of the prepayment
+    pub prepayment_date: NaiveDate,
+
+    /// Amount in yen
+    pub amount: i64,
+
+    /// Shorten term or reduce payment - stored as string
+    #[field(max_length = 20)]
+    pub prepayment_type: String,
+}
+
+impl LoanPrepayment {
+    /// Gets the prepayment type as enum
+    pub fn get_prepayment_type(&self) -> PrepaymentType {
+        self.prepayment_type.parse().unwrap_or_default()
+    }
+
+    /// Prepayment for computing the schedule
+    pub fn to_prepayment(&self) -> Prepayment {
+        Prepayment {
+            date: self.prepayment_date,
+            amount: self.amount,
+            kind: self.get_prepayment_type(),
+        }
+    }
+}
+
+/// A repayment transaction linked to a loan, split into principal and
+/// interest
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "loans", table_name = "loan_repayments")]
+pub struct LoanRepayment {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Loan repaid (foreign key)
+    pub loan_id: i64,
+
+    /// Repayment transaction (foreign key, linked to one loan at most)
+    pub transaction_id: i64,
+
+    /// Scheduled payment the transaction was matched to
+    pub number: i32,
+
+    /// Part of the transaction that repaid principal
+    pub principal: i64,
+
+    /// Part of the transaction that was interest
+    pub interest: i64,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_repayment_method_wire_values() {
+        assert_wire_values(&[
+            RepaymentMethod::EqualPayment,
+            RepaymentMethod::EqualPrincipal,
+        ]);
+    }
+
+    #[test]
+    fn test_prepayment_type_wire_values() {
+        assert_wire_values(&[PrepaymentType::ShortenTerm, PrepaymentType::ReducePayment]);
+    }
+}
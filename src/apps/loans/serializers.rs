@@ -0,0 +1,327 @@
+//! Loan serializers for request/response handling
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use validator::Validate;
+
+use super::amortization::{schedule, ScheduleRow};
+use super::models::{Loan, LoanPrepayment, LoanRepayment, PrepaymentType, RepaymentMethod};
+
+/// Request payload for creating a loan
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateLoanRequest {
+    /// Loan name (1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    /// Amount borrowed in yen
+    #[validate(range(min = 1, message = "Principal must be positive"))]
+    pub principal: i64,
+    /// Annual interest rate in percent
+    #[validate(range(min = 0.0, max = 20.0, message = "Interest rate must be between 0 and 20"))]
+    pub annual_rate: f64,
+    /// Number of monthly payments
+    #[validate(range(min = 1, max = 600, message = "Term must be between 1 and 600 months"))]
+    pub term_months: u32,
+    /// Date of the first payment; later payments fall on the same day
+    pub start_date: NaiveDate,
+    #[serde(default)]
+    pub repayment_method: RepaymentMethod,
+    /// Account the repayments are withdrawn from
+    #[serde(default)]
+    pub account_id: Option<i64>,
+}
+
+/// Request payload for updating a loan
+///
+/// The terms are fixed; record changes as prepayments.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateLoanRequest {
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    pub account_id: Option<i64>,
+}
+
+/// Response payload for a loan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanResponse {
+    pub id: i64,
+    pub name: String,
+    pub principal: i64,
+    pub annual_rate: f64,
+    pub term_months: u32,
+    /// First payment date as `YYYY-MM-DD`
+    pub start_date: String,
+    pub repayment_method: RepaymentMethod,
+    pub account_id: Option<i64>,
+    /// Principal repaid by the linked repayment transactions
+    pub paid_principal: i64,
+    /// Interest paid by the linked repayment transactions
+    pub paid_interest: i64,
+    /// Principal minus linked repayments and prepayments made so far
+    pub remaining_principal: i64,
+    /// Balance the schedule expects after today's payments
+    pub scheduled_remaining_principal: i64,
+    pub created_at: String,
+}
+
+/// Principal and interest paid in one year
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanYearResponse {
+    pub year: i32,
+    pub principal: i64,
+    pub interest: i64,
+}
+
+/// A prepayment (繰上返済) of a loan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanPrepaymentResponse {
+    pub id: i64,
+    pub loan_id: i64,
+    /// Date as `YYYY-MM-DD`
+    pub prepayment_date: String,
+    pub amount: i64,
+    pub prepayment_type: PrepaymentType,
+}
+
+/// A repayment transaction linked to a loan, with its split
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanRepaymentResponse {
+    pub loan_id: i64,
+    pub transaction_id: i64,
+    /// Scheduled payment it was matched to
+    pub number: u32,
+    /// Transaction date as `YYYY-MM-DD`
+    pub transaction_date: String,
+    pub principal: i64,
+    pub interest: i64,
+}
+
+/// A loan with its prepayments, repayments and yearly totals
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanDetailResponse {
+    #[serde(flatten)]
+    pub loan: LoanResponse,
+    pub prepayments: Vec<LoanPrepaymentResponse>,
+    pub repayments: Vec<LoanRepaymentResponse>,
+    /// Linked repayments by year of the transaction date
+    pub by_year: Vec<LoanYearResponse>,
+}
+
+/// Response wrapper for list of loans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoanListResponse {
+    pub count: usize,
+    /// Sum of `remaining_principal`
+    pub remaining_principal: i64,
+    pub results: Vec<LoanResponse>,
+}
+
+/// Request payload for adding a prepayment
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateLoanPrepaymentRequest {
+    /// Applied before the first payment due on or after this date
+    pub prepayment_date: NaiveDate,
+    #[validate(range(min = 1, message = "Amount must be positive"))]
+    pub amount: i64,
+    #[serde(default)]
+    pub prepayment_type: PrepaymentType,
+}
+
+/// Request payload for linking a repayment transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkLoanRepaymentRequest {
+    pub transaction_id: i64,
+}
+
+/// One row of an amortization schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortizationRowResponse {
+    /// 1-based payment number
+    pub number: u32,
+    /// Due date as `YYYY-MM-DD`
+    pub due_date: String,
+    /// `principal + interest`
+    pub payment: i64,
+    pub principal: i64,
+    pub interest: i64,
+    /// Prepaid just before this payment
+    pub prepayment: i64,
+    /// Balance after the payment
+    pub balance: i64,
+}
+
+/// Amortization schedule of a loan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmortizationScheduleResponse {
+    pub loan_id: i64,
+    pub count: usize,
+    pub total_payment: i64,
+    pub total_interest: i64,
+    pub rows: Vec<AmortizationRowResponse>,
+}
+
+/// A linked repayment with the date of its transaction
+pub struct DatedRepayment {
+    pub repayment: LoanRepayment,
+    pub date: NaiveDate,
+}
+
+/// Principal still owed on `date`: the loan minus the principal of the
+/// repayments and the prepayments made by then
+pub fn remaining_principal_on(
+    loan: &Loan,
+    prepayments: &[LoanPrepayment],
+    repayments: &[&DatedRepayment],
+    date: NaiveDate,
+) -> i64 {
+    let repaid: i64 = repayments
+        .iter()
+        .filter(|r| r.date <= date)
+        .map(|r| r.repayment.principal)
+        .sum();
+    let prepaid: i64 = prepayments
+        .iter()
+        .filter(|p| p.prepayment_date <= date)
+        .map(|p| p.amount)
+        .sum();
+    (loan.principal - repaid - prepaid).max(0)
+}
+
+impl LoanResponse {
+    /// Builds the response from the loan's prepayments and its repayments
+    /// of active transactions
+    pub fn new(
+        loan: &Loan,
+        prepayments: &[LoanPrepayment],
+        repayments: &[&DatedRepayment],
+        today: NaiveDate,
+    ) -> Self {
+        let paid_principal: i64 = repayments.iter().map(|r| r.repayment.principal).sum();
+        let paid_interest: i64 = repayments.iter().map(|r| r.repayment.interest).sum();
+        let prepaid: i64 = prepayments
+            .iter()
+            .filter(|p| p.prepayment_date <= today)
+            .map(|p| p.amount)
+            .sum();
+
+        let planned: Vec<_> = prepayments
+            .iter()
+            .map(LoanPrepayment::to_prepayment)
+            .collect();
+        let scheduled_remaining_principal = schedule(&loan.terms(), &planned)
+            .iter()
+            .rev()
+            .find(|r| r.due_date <= today)
+            .map(|r| r.balance)
+            .unwrap_or(loan.principal - prepaid)
+            .max(0);
+
+        Self {
+            id: loan.id.unwrap_or(0),
+            name: loan.name.clone(),
+            principal: loan.principal,
+            annual_rate: loan.annual_rate,
+            term_months: u32::try_from(loan.term_months).unwrap_or(0),
+            start_date: loan.start_date.format("%Y-%m-%d").to_string(),
+            repayment_method: loan.get_repayment_method(),
+            account_id: loan.account_id,
+            paid_principal,
+            paid_interest,
+            remaining_principal: remaining_principal_on(loan, prepayments, repayments, today),
+            scheduled_remaining_principal,
+            created_at: loan.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<&LoanPrepayment> for LoanPrepaymentResponse {
+    fn from(prepayment: &LoanPrepayment) -> Self {
+        Self {
+            id: prepayment.id.unwrap_or(0),
+            loan_id: prepayment.loan_id,
+            prepayment_date: prepayment.prepayment_date.format("%Y-%m-%d").to_string(),
+            amount: prepayment.amount,
+            prepayment_type: prepayment.get_prepayment_type(),
+        }
+    }
+}
+
+impl From<&DatedRepayment> for LoanRepaymentResponse {
+    fn from(dated: &DatedRepayment) -> Self {
+        Self {
+            loan_id: dated.repayment.loan_id,
+            transaction_id: dated.repayment.transaction_id,
+            number: u32::try_from(dated.repayment.number).unwrap_or(0),
+            transaction_date: dated.date.format("%Y-%m-%d").to_string(),
+            principal: dated.repayment.principal,
+            interest: dated.repayment.interest,
+        }
+    }
+}
+
+impl LoanDetailResponse {
+    pub fn new(
+        loan: &Loan,
+        prepayments: &[LoanPrepayment],
+        repayments: &[&DatedRepayment],
+        today: NaiveDate,
+    ) -> Self {
+        let mut by_year: BTreeMap<i32, (i64, i64)> = BTreeMap::new();
+        for r in repayments {
+            let entry = by_year.entry(r.date.year()).or_insert((0, 0));
+            entry.0 += r.repayment.principal;
+            entry.1 += r.repayment.interest;
+        }
+
+        Self {
+            loan: LoanResponse::new(loan, prepayments, repayments, today),
+            prepayments: prepayments.iter().map(Into::into).collect(),
+            repayments: repayments.iter().map(|r| (*r).into()).collect(),
+            by_year: by_year
+                .into_iter()
+                .map(|(year, (principal, interest))| LoanYearResponse {
+                    year,
+                    principal,
+                    interest,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&ScheduleRow> for AmortizationRowResponse {
+    fn from(row: &ScheduleRow) -> Self {
+        Self {
+            number: row.number,
+            due_date: row.due_date.format("%Y-%m-%d").to_string(),
+            payment: row.payment(),
+            principal: row.principal,
+            interest: row.interest,
+            prepayment: row.prepayment,
+            balance: row.balance,
+        }
+    }
+}
+
+impl AmortizationScheduleResponse {
+    pub fn new(loan_id: i64, rows: &[ScheduleRow]) -> Self {
+        Self {
+            loan_id,
+            count: rows.len(),
+            total_payment: rows.iter().map(ScheduleRow::payment).sum(),
+            total_interest: rows.iter().map(|r| r.interest).sum(),
+            rows: rows.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl LoanListResponse {
+    pub fn new(results: Vec<LoanResponse>) -> Self {
+        Self {
+            count: results.len(),
+            remaining_principal: results.iter().map(|r| r.remaining_principal).sum(),
+            results,
+        }
+    }
+}
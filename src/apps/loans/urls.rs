@@ -0,0 +1,20 @@
+//! URL routing for loans app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the loans app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_loans)
+        .endpoint(views::get_loan)
+        .endpoint(views::create_loan_view)
+        .endpoint(views::update_loan_view)
+        .endpoint(views::delete_loan_view)
+        .endpoint(views::get_schedule)
+        .endpoint(views::create_prepayment_view)
+        .endpoint(views::delete_prepayment_view)
+        .endpoint(views::link_repayment_view)
+        .endpoint(views::unlink_repayment_view)
+}
@@ -0,0 +1,498 @@
+//! Loan views (API endpoints)
+
+use chrono::{Datelike, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Request, Response, StatusCode};
+use std::collections::HashMap;
+use validator::Validate;
+
+use crate::apps::accounts::views::account_exists;
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::transactions::models::{Transaction, TransactionType};
+use crate::apps::transactions::views::active_transactions;
+use super::amortization::schedule;
+use super::models::{Loan, LoanPrepayment, LoanRepayment};
+use super::serializers::{
+    AmortizationScheduleResponse, CreateLoanPrepaymentRequest, CreateLoanRequest, DatedRepayment,
+    LinkLoanRepaymentRequest, LoanDetailResponse, LoanListResponse, LoanPrepaymentResponse,
+    LoanRepaymentResponse, LoanResponse, UpdateLoanRequest,
+};
+
+fn loan_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Loan with id {} not found", id),
+    )
+}
+
+fn account_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::BAD_REQUEST,
+        &format!("Account with id {} not found", id),
+    )
+}
+
+/// Returns the prepayments of a loan, oldest first
+async fn prepayments_of(loan_id: i64) -> ViewResult<Vec<LoanPrepayment>> {
+    let mut prepayments: Vec<LoanPrepayment> = LoanPrepayment::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|p| p.loan_id == loan_id)
+        .collect();
+    prepayments.sort_by_key(|p| (p.prepayment_date, p.id));
+    Ok(prepayments)
+}
+
+/// Returns the repayments linked to active transactions, with their dates,
+/// oldest first
+///
+/// Repayments of trashed transactions are left out until they are restored.
+pub async fn active_repayments() -> ViewResult<Vec<DatedRepayment>> {
+    let dates: HashMap<i64, chrono::NaiveDate> = active_transactions()
+        .await?
+        .into_iter()
+        .filter_map(|t| t.id.map(|id| (id, t.transaction_date.date_naive())))
+        .collect();
+
+    let mut repayments: Vec<DatedRepayment> = LoanRepayment::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter_map(|repayment| {
+            let date = *dates.get(&repayment.transaction_id)?;
+            Some(DatedRepayment { repayment, date })
+        })
+        .collect();
+    repayments.sort_by_key(|r| (r.date, r.repayment.id));
+    Ok(repayments)
+}
+
+/// Returns every loan with its summary
+///
+/// Used by the loan list and by net worth.
+pub async fn loan_summaries() -> ViewResult<Vec<LoanResponse>> {
+    let loans = Loan::objects().all().all().await?;
+    let repayments = active_repayments().await?;
+    let today = Utc::now().date_naive();
+
+    let mut prepayments: HashMap<i64, Vec<LoanPrepayment>> = HashMap::new();
+    for prepayment in LoanPrepayment::objects().all().all().await? {
+        prepayments
+            .entry(prepayment.loan_id)
+            .or_default()
+            .push(prepayment);
+    }
+
+    Ok(loans
+        .iter()
+        .map(|loan| {
+            let loan_id = loan.id.unwrap_or(0);
+            let own_prepayments = prepayments.get(&loan_id).map_or(&[][..], Vec::as_slice);
+            let own_repayments: Vec<&DatedRepayment> = repayments
+                .iter()
+                .filter(|r| r.repayment.loan_id == loan_id)
+                .collect();
+            LoanResponse::new(loan, own_prepayments, &own_repayments, today)
+        })
+        .collect())
+}
+
+/// Whether a transaction is linked to a loan as a repayment
+///
+/// The principal/interest split is fixed at link time, so the amount and
+/// date of a linked transaction must not change.
+pub async fn is_repayment(transaction_id: i64) -> ViewResult<bool> {
+    let repayments = LoanRepayment::objects().all().all().await?;
+    Ok(repayments
+        .iter()
+        .any(|r| r.transaction_id == transaction_id))
+}
+
+/// Deletes the loan link of a transaction, if any
+pub async fn delete_for_transaction(transaction_id: i64) -> ViewResult<()> {
+    let manager = LoanRepayment::objects();
+    for repayment in manager.all().all().await? {
+        if repayment.transaction_id == transaction_id
+            && let Some(id) = repayment.id
+        {
+            manager.delete(id).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn detail_response(loan: &Loan) -> ViewResult<LoanDetailResponse> {
+    let loan_id = loan.id.unwrap_or(0);
+    let prepayments = prepayments_of(loan_id).await?;
+    let repayments = active_repayments().await?;
+    let own: Vec<&DatedRepayment> = repayments
+        .iter()
+        .filter(|r| r.repayment.loan_id == loan_id)
+        .collect();
+
+    Ok(LoanDetailResponse::new(
+        loan,
+        &prepayments,
+        &own,
+        Utc::now().date_naive(),
+    ))
+}
+
+/// List all loans with their remaining principal
+///
+/// GET /loans/
+#[get("/", name = "loans_list")]
+pub async fn list_loans() -> ViewResult<Response> {
+    let response = LoanListResponse::new(loan_summaries().await?);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get a loan with its prepayments, repayments and yearly totals
+///
+/// GET /loans/{id}/
+#[get("/{id}/", name = "loans_get")]
+pub async fn get_loan(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(loan) = Loan::objects().get(id).first().await? else {
+        return loan_not_found(id);
+    };
+
+    let response = detail_response(&loan).await?;
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Create a new loan
+///
+/// POST /loans/
+#[post("/", name = "loans_create")]
+pub async fn create_loan_view(
+    request: Request,
+    Json(create_req): Json<CreateLoanRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_loan(create_req)).await
+}
+
+async fn create_loan(create_req: CreateLoanRequest) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+    if let Some(account_id) = create_req.account_id
+        && !account_exists(account_id).await?
+    {
+        return account_not_found(account_id);
+    }
+
+    let loan = Loan {
+        id: None,
+        name: create_req.name,
+        principal: create_req.principal,
+        annual_rate: create_req.annual_rate,
+        term_months: create_req.term_months as i32,
+        start_date: create_req.start_date,
+        repayment_method: create_req.repayment_method.to_string(),
+        account_id: create_req.account_id,
+        created_at: Utc::now(),
+    };
+    let created = Loan::objects().create(&loan).await?;
+
+    let response = detail_response(&created).await?;
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Update the name or account of a loan
+///
+/// PUT /loans/{id}/
+#[put("/{id}/", name = "loans_update")]
+pub async fn update_loan_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateLoanRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, update_loan(id, update_req)).await
+}
+
+async fn update_loan(id: i64, update_req: UpdateLoanRequest) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = Loan::objects();
+    let Some(mut loan) = manager.get(id).first().await? else {
+        return loan_not_found(id);
+    };
+    if let Some(account_id) = update_req.account_id
+        && !account_exists(account_id).await?
+    {
+        return account_not_found(account_id);
+    }
+
+    if let Some(n) = update_req.name {
+        loan.name = n;
+    }
+    if let Some(account_id) = update_req.account_id {
+        loan.account_id = Some(account_id);
+    }
+    let updated = manager.update(&loan).await?;
+
+    let response = detail_response(&updated).await?;
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Delete a loan with its prepayments; linked transactions are kept
+///
+/// DELETE /loans/{id}/
+#[delete("/{id}/", name = "loans_delete")]
+pub async fn delete_loan_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    idempotent(&request, delete_loan(id)).await
+}
+
+async fn delete_loan(id: i64) -> ViewResult<Response> {
+    let manager = Loan::objects();
+    if manager.get(id).first().await?.is_none() {
+        return loan_not_found(id);
+    }
+
+    let prepayment_manager = LoanPrepayment::objects();
+    for prepayment in prepayments_of(id).await? {
+        if let Some(prepayment_id) = prepayment.id {
+            prepayment_manager.delete(prepayment_id).await?;
+        }
+    }
+    let repayment_manager = LoanRepayment::objects();
+    for repayment in repayment_manager.all().all().await? {
+        if repayment.loan_id == id
+            && let Some(repayment_id) = repayment.id
+        {
+            repayment_manager.delete(repayment_id).await?;
+        }
+    }
+    manager.delete(id).await?;
+
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Get the amortization schedule of a loan, including prepayments
+///
+/// GET /loans/{id}/schedule/
+#[get("/{id}/schedule/", name = "loans_schedule")]
+pub async fn get_schedule(Path(id): Path<i64>) -> ViewResult<Response> {
+    let Some(loan) = Loan::objects().get(id).first().await? else {
+        return loan_not_found(id);
+    };
+    let prepayments: Vec<_> = prepayments_of(id)
+        .await?
+        .iter()
+        .map(LoanPrepayment::to_prepayment)
+        .collect();
+
+    let rows = schedule(&loan.terms(), &prepayments);
+    let response = AmortizationScheduleResponse::new(id, &rows);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Add a prepayment (繰上返済) to a loan
+///
+/// POST /loans/{id}/prepayments/
+#[post("/{id}/prepayments/", name = "loans_prepayments_create")]
+pub async fn create_prepayment_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(create_req): Json<CreateLoanPrepaymentRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_prepayment(id, create_req)).await
+}
+
+async fn create_prepayment(
+    loan_id: i64,
+    create_req: CreateLoanPrepaymentRequest,
+) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+    let Some(loan) = Loan::objects().get(loan_id).first().await? else {
+        return loan_not_found(loan_id);
+    };
+    if create_req.prepayment_date < loan.start_date {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "A prepayment cannot be made before the first payment",
+        );
+    }
+
+    let prepayment = LoanPrepayment {
+        id: None,
+        loan_id,
+        prepayment_date: create_req.prepayment_date,
+        amount: create_req.amount,
+        prepayment_type: create_req.prepayment_type.to_string(),
+    };
+    let created = LoanPrepayment::objects().create(&prepayment).await?;
+
+    let response = LoanPrepaymentResponse::from(&created);
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Remove a prepayment from a loan
+///
+/// DELETE /loans/{id}/prepayments/{prepayment_id}/
+#[delete(
+    "/{id}/prepayments/{prepayment_id}/",
+    name = "loans_prepayments_delete"
+)]
+pub async fn delete_prepayment_view(
+    request: Request,
+    Path((loan_id, id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    idempotent(&request, delete_prepayment(loan_id, id)).await
+}
+
+async fn delete_prepayment(loan_id: i64, id: i64) -> ViewResult<Response> {
+    let manager = LoanPrepayment::objects();
+    let prepayment = manager.get(id).first().await?;
+    if !prepayment.is_some_and(|p| p.loan_id == loan_id) {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            &format!("Prepayment with id {} not found", id),
+        );
+    }
+
+    manager.delete(id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Link a repayment transaction to a loan
+///
+/// POST /loans/{id}/repayments/
+///
+/// The transaction is matched to the payment due in its month: the
+/// scheduled interest is taken as interest and the rest as principal.
+#[post("/{id}/repayments/", name = "loans_repayments_link")]
+pub async fn link_repayment_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(link_req): Json<LinkLoanRepaymentRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, link_repayment(id, link_req)).await
+}
+
+async fn link_repayment(loan_id: i64, link_req: LinkLoanRepaymentRequest) -> ViewResult<Response> {
+    let Some(loan) = Loan::objects().get(loan_id).first().await? else {
+        return loan_not_found(loan_id);
+    };
+    let transaction_id = link_req.transaction_id;
+    let transaction = Transaction::objects()
+        .get(transaction_id)
+        .first()
+        .await?
+        .filter(|t| !t.is_deleted());
+    let transaction = match transaction {
+        Some(t) if t.get_transaction_type() == TransactionType::Expense => t,
+        Some(_) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("Transaction {} is not an expense", transaction_id),
+            );
+        }
+        None => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                &format!("Transaction with id {} not found", transaction_id),
+            );
+        }
+    };
+
+    let manager = LoanRepayment::objects();
+    let linked = manager.all().all().await?;
+    if linked.iter().any(|r| r.transaction_id == transaction_id) {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("Transaction {} is already linked to a loan", transaction_id),
+        );
+    }
+
+    let date = transaction.transaction_date.date_naive();
+    let prepayments: Vec<_> = prepayments_of(loan_id)
+        .await?
+        .iter()
+        .map(LoanPrepayment::to_prepayment)
+        .collect();
+    let rows = schedule(&loan.terms(), &prepayments);
+    let Some(row) = rows
+        .iter()
+        .find(|r| r.due_date.year() == date.year() && r.due_date.month() == date.month())
+    else {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!(
+                "No payment of loan {} is due in {}",
+                loan_id,
+                date.format("%Y-%m")
+            ),
+        );
+    };
+    if linked
+        .iter()
+        .any(|r| r.loan_id == loan_id && r.number == row.number as i32)
+    {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!(
+                "Payment {} of loan {} is already linked",
+                row.number, loan_id
+            ),
+        );
+    }
+
+    let interest = row.interest.min(transaction.amount);
+    let repayment = LoanRepayment {
+        id: None,
+        loan_id,
+        transaction_id,
+        number: row.number as i32,
+        principal: transaction.amount - interest,
+        interest,
+        created_at: Utc::now(),
+    };
+    let created = manager.create(&repayment).await?;
+
+    let response = LoanRepaymentResponse::from(&DatedRepayment {
+        repayment: created,
+        date,
+    });
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Unlink a repayment transaction from a loan
+///
+/// DELETE /loans/{id}/repayments/{transaction_id}/
+#[delete("/{id}/repayments/{transaction_id}/", name = "loans_repayments_unlink")]
+pub async fn unlink_repayment_view(
+    request: Request,
+    Path((loan_id, transaction_id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    idempotent(&request, unlink_repayment(loan_id, transaction_id)).await
+}
+
+async fn unlink_repayment(loan_id: i64, transaction_id: i64) -> ViewResult<Response> {
+    let manager = LoanRepayment::objects();
+    let repayment = manager
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .find(|r| r.loan_id == loan_id && r.transaction_id == transaction_id);
+    let Some(id) = repayment.and_then(|r| r.id) else {
+        return error_response(
+            StatusCode::NOT_FOUND,
+            &format!(
+                "Transaction {} is not linked to loan {}",
+                transaction_id, loan_id
+            ),
+        );
+    };
+
+    manager.delete(id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
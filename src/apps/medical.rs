@@ -0,0 +1,13 @@
+//! Medical expenses app for the 医療費控除 (medical expense deduction)
+//!
+//! Records who received care, the provider and any reimbursement for
+//! medical transactions. The deduction report itself lives in the reports app.
+
+pub mod models;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use serializers::*;
+pub use urls::url_patterns;
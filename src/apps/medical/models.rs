@@ -0,0 +1,98 @@
+//! Medical expense model definitions
+
+use chrono::{DateTime, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Medical expense kind, matching the 医療費の区分 columns of the e-Tax form
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MedicalExpenseKind {
+    /// 診療・治療
+    Treatment,
+    /// 医薬品購入
+    Medicine,
+    /// 介護保険サービス
+    CareService,
+    /// その他の医療費
+    Other,
+}
+
+impl MedicalExpenseKind {
+    /// Label used in the e-Tax 医療費集計フォーム
+    pub fn label(&self) -> &'static str {
+        match self {
+            MedicalExpenseKind::Treatment => "診療・治療",
+            MedicalExpenseKind::Medicine => "医薬品購入",
+            MedicalExpenseKind::CareService => "介護保険サービス",
+            MedicalExpenseKind::Other => "その他の医療費",
+        }
+    }
+}
+
+impl std::fmt::Display for MedicalExpenseKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MedicalExpenseKind::Treatment => write!(f, "treatment"),
+            MedicalExpenseKind::Medicine => write!(f, "medicine"),
+            MedicalExpenseKind::CareService => write!(f, "care_service"),
+            MedicalExpenseKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+impl std::str::FromStr for MedicalExpenseKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "treatment" => Ok(MedicalExpenseKind::Treatment),
+            "medicine" => Ok(MedicalExpenseKind::Medicine),
+            "care_service" => Ok(MedicalExpenseKind::CareService),
+            "other" => Ok(MedicalExpenseKind::Other),
+            _ => Err(format!("Invalid medical expense kind: {}", s)),
+        }
+    }
+}
+
+/// Medical details of an expense transaction
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "medical", table_name = "medical_expenses")]
+pub struct MedicalExpense {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Transaction ID (foreign key, one detail per transaction)
+    pub transaction_id: i64,
+
+    /// Family member who received the care (医療を受けた方の氏名)
+    #[field(max_length = 100)]
+    pub patient: String,
+
+    /// Hospital or pharmacy paid (病院・薬局などの支払先の名称)
+    #[field(max_length = 200)]
+    pub provider: String,
+
+    /// Kind of medical expense - stored as string
+    #[field(max_length = 20)]
+    pub kind: String,
+
+    /// Amount reimbursed by insurance etc. (保険金などで補填される金額)
+    pub reimbursement: i64,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+
+    /// Last update timestamp
+    #[field(auto_now = true)]
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MedicalExpense {
+    /// Gets the expense kind as enum
+    pub fn get_kind(&self) -> MedicalExpenseKind {
+        self.kind.parse().unwrap_or(MedicalExpenseKind::Other)
+    }
+}
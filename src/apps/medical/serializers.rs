@@ -0,0 +1,90 @@
+//! Medical expense serializers for request/response handling
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::models::{MedicalExpense, MedicalExpenseKind};
+
+/// Request payload for recording medical details of a transaction
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateMedicalExpenseRequest {
+    /// Transaction ID
+    pub transaction_id: i64,
+    /// Patient name (required, 1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Patient must be between 1 and 100 characters"))]
+    pub patient: String,
+    /// Provider name (required, 1-200 characters)
+    #[validate(length(min = 1, max = 200, message = "Provider must be between 1 and 200 characters"))]
+    pub provider: String,
+    /// Kind of medical expense
+    pub kind: MedicalExpenseKind,
+    /// Reimbursed amount in yen (defaults to 0)
+    #[serde(default)]
+    #[validate(range(min = 0, message = "Reimbursement must not be negative"))]
+    pub reimbursement: i64,
+}
+
+/// Request payload for updating medical details
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UpdateMedicalExpenseRequest {
+    /// Patient name (optional, 1-100 characters if provided)
+    #[validate(length(min = 1, max = 100, message = "Patient must be between 1 and 100 characters"))]
+    pub patient: Option<String>,
+    /// Provider name (optional, 1-200 characters if provided)
+    #[validate(length(min = 1, max = 200, message = "Provider must be between 1 and 200 characters"))]
+    pub provider: Option<String>,
+    /// Kind of medical expense (optional)
+    pub kind: Option<MedicalExpenseKind>,
+    /// Reimbursed amount in yen (optional)
+    #[validate(range(min = 0, message = "Reimbursement must not be negative"))]
+    pub reimbursement: Option<i64>,
+}
+
+/// Response payload for medical details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MedicalExpenseResponse {
+    pub id: i64,
+    pub transaction_id: i64,
+    pub patient: String,
+    pub provider: String,
+    pub kind: MedicalExpenseKind,
+    pub reimbursement: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<MedicalExpense> for MedicalExpenseResponse {
+    fn from(expense: MedicalExpense) -> Self {
+        let kind = expense.get_kind();
+        let created_at = expense.created_at.to_rfc3339();
+        let updated_at = expense.updated_at.to_rfc3339();
+
+        Self {
+            id: expense.id.unwrap_or(0),
+            transaction_id: expense.transaction_id,
+            patient: expense.patient,
+            provider: expense.provider,
+            kind,
+            reimbursement: expense.reimbursement,
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+/// Response wrapper for list of medical details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MedicalExpenseListResponse {
+    pub count: usize,
+    pub results: Vec<MedicalExpenseResponse>,
+}
+
+impl MedicalExpenseListResponse {
+    pub fn new(expenses: Vec<MedicalExpense>) -> Self {
+        let results: Vec<MedicalExpenseResponse> = expenses.into_iter().map(Into::into).collect();
+        Self {
+            count: results.len(),
+            results,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! URL routing for medical app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the medical app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_medical_expenses)
+        .endpoint(views::get_medical_expense)
+        .endpoint(views::create_medical_expense_view)
+        .endpoint(views::update_medical_expense_view)
+        .endpoint(views::delete_medical_expense_view)
+}
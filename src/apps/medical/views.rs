@@ -0,0 +1,222 @@
+//! Medical expense views (API endpoints)
+
+use chrono::Utc;
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, put, Json, Path, Request, Response, StatusCode};
+use std::collections::HashSet;
+use validator::Validate;
+
+use crate::apps::idempotency::idempotent;
+use crate::apps::transactions::models::{Transaction, TransactionType};
+use crate::apps::transactions::views::active_transactions;
+use super::models::MedicalExpense;
+use super::serializers::{
+    CreateMedicalExpenseRequest, MedicalExpenseListResponse, MedicalExpenseResponse,
+    UpdateMedicalExpenseRequest,
+};
+
+/// List all medical expense details
+///
+/// GET /medical/expenses/
+#[get("/expenses/", name = "medical_expenses_list")]
+pub async fn list_medical_expenses() -> ViewResult<Response> {
+    let transaction_ids: HashSet<i64> = active_transactions()
+        .await?
+        .into_iter()
+        .filter_map(|t| t.id)
+        .collect();
+    let manager = MedicalExpense::objects();
+    let expenses: Vec<MedicalExpense> = manager
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .filter(|e| transaction_ids.contains(&e.transaction_id))
+        .collect();
+
+    let response = MedicalExpenseListResponse::new(expenses);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get a single medical expense detail by ID
+///
+/// GET /medical/expenses/{id}/
+#[get("/expenses/{id}/", name = "medical_expenses_get")]
+pub async fn get_medical_expense(Path(id): Path<i64>) -> ViewResult<Response> {
+    match active_medical_expense(id).await? {
+        Some(expense) => {
+            let response: MedicalExpenseResponse = expense.into();
+            Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+        }
+        None => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Medical expense with id {} not found"}}"#, id).into_bytes(),
+        )),
+    }
+}
+
+/// Returns the medical details with `id` unless their transaction is in
+/// the trash
+async fn active_medical_expense(id: i64) -> ViewResult<Option<MedicalExpense>> {
+    let Some(expense) = MedicalExpense::objects().get(id).first().await? else {
+        return Ok(None);
+    };
+    let active = Transaction::objects()
+        .get(expense.transaction_id)
+        .first()
+        .await?
+        .is_some_and(|t| !t.is_deleted());
+    Ok(active.then_some(expense))
+}
+
+/// Record medical details for an expense transaction
+///
+/// POST /medical/expenses/
+#[post("/expenses/", name = "medical_expenses_create")]
+pub async fn create_medical_expense_view(
+    request: Request,
+    Json(create_req): Json<CreateMedicalExpenseRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_medical_expense(create_req)).await
+}
+
+async fn create_medical_expense(create_req: CreateMedicalExpenseRequest) -> ViewResult<Response> {
+    // Validate request
+    create_req.validate()?;
+
+    let transaction_id = create_req.transaction_id;
+    let transaction = Transaction::objects()
+        .get(transaction_id)
+        .first()
+        .await?
+        .filter(|t| !t.is_deleted());
+    match transaction {
+        Some(t) if t.get_transaction_type() == TransactionType::Expense => {}
+        Some(_) => {
+            return Ok(Response::new(StatusCode::BAD_REQUEST).with_body(
+                format!(
+                    r#"{{"error": "Transaction {} is not an expense"}}"#,
+                    transaction_id
+                )
+                .into_bytes(),
+            ));
+        }
+        None => {
+            return Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+                format!(r#"{{"error": "Transaction with id {} not found"}}"#, transaction_id)
+                    .into_bytes(),
+            ));
+        }
+    }
+
+    let manager = MedicalExpense::objects();
+    let existing = manager.all().all().await?;
+    if existing.iter().any(|e| e.transaction_id == transaction_id) {
+        return Ok(Response::new(StatusCode::CONFLICT).with_body(
+            format!(
+                r#"{{"error": "Transaction {} already has medical details"}}"#,
+                transaction_id
+            )
+            .into_bytes(),
+        ));
+    }
+
+    let now = Utc::now();
+    let expense = MedicalExpense {
+        id: None,
+        transaction_id,
+        patient: create_req.patient,
+        provider: create_req.provider,
+        kind: create_req.kind.to_string(),
+        reimbursement: create_req.reimbursement,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let created = manager.create(&expense).await?;
+
+    let response: MedicalExpenseResponse = created.into();
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Update medical details
+///
+/// PUT /medical/expenses/{id}/
+#[put("/expenses/{id}/", name = "medical_expenses_update")]
+pub async fn update_medical_expense_view(
+    request: Request,
+    Path(id): Path<i64>,
+    Json(update_req): Json<UpdateMedicalExpenseRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, update_medical_expense(id, update_req)).await
+}
+
+async fn update_medical_expense(
+    id: i64,
+    update_req: UpdateMedicalExpenseRequest,
+) -> ViewResult<Response> {
+    // Validate request
+    update_req.validate()?;
+
+    let manager = MedicalExpense::objects();
+    match active_medical_expense(id).await? {
+        Some(mut expense) => {
+            // Apply updates
+            if let Some(p) = update_req.patient {
+                expense.patient = p;
+            }
+            if let Some(p) = update_req.provider {
+                expense.provider = p;
+            }
+            if let Some(k) = update_req.kind {
+                expense.kind = k.to_string();
+            }
+            if let Some(r) = update_req.reimbursement {
+                expense.reimbursement = r;
+            }
+            expense.updated_at = Utc::now();
+
+            let updated = manager.update(&expense).await?;
+            let response: MedicalExpenseResponse = updated.into();
+            Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+        }
+        None => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Medical expense with id {} not found"}}"#, id).into_bytes(),
+        )),
+    }
+}
+
+/// Delete medical details
+///
+/// DELETE /medical/expenses/{id}/
+#[delete("/expenses/{id}/", name = "medical_expenses_delete")]
+pub async fn delete_medical_expense_view(
+    request: Request,
+    Path(id): Path<i64>,
+) -> ViewResult<Response> {
+    idempotent(&request, delete_medical_expense(id)).await
+}
+
+async fn delete_medical_expense(id: i64) -> ViewResult<Response> {
+    let manager = MedicalExpense::objects();
+    match manager.delete(id).await {
+        Ok(_) => Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new())),
+        Err(_) => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+            format!(r#"{{"error": "Medical expense with id {} not found"}}"#, id).into_bytes(),
+        )),
+    }
+}
+
+/// Deletes the medical details of a transaction, if any
+pub async fn delete_for_transaction(transaction_id: i64) -> ViewResult<()> {
+    let manager = MedicalExpense::objects();
+    for expense in manager.all().all().await? {
+        if expense.transaction_id == transaction_id
+            && let Some(id) = expense.id
+        {
+            manager.delete(id).await?;
+        }
+    }
+    Ok(())
+}
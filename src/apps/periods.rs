@@ -0,0 +1,19 @@
+//! Periods app for closing the books
+//!
+//! Once a month has been reconciled it can be closed: every transaction
+//! dated on or before the lock date becomes read-only, and creating,
+//! editing, deleting or restoring one is rejected with 409. Purging a
+//! trashed one is still allowed, since it no longer counts towards any
+//! total.
+//! Closing can only move the lock forward; moving it back (reopening)
+//! needs the admin token. Both are recorded in the audit log.
+
+pub mod lock;
+pub mod models;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use lock::reject_if_locked;
+pub use models::*;
+pub use urls::url_patterns;
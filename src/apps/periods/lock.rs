@@ -0,0 +1,44 @@
+//! Checking writes against the period lock
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{Response, StatusCode};
+
+use super::models::PeriodLock;
+
+/// Returns the lock row, if the books were ever closed
+pub async fn current() -> ViewResult<Option<PeriodLock>> {
+    let locks = PeriodLock::objects().all().all().await?;
+    Ok(locks.into_iter().min_by_key(|l| l.id))
+}
+
+/// Returns the last locked day, if any
+pub async fn locked_through() -> ViewResult<Option<NaiveDate>> {
+    Ok(current().await?.and_then(|l| l.locked_through))
+}
+
+/// Returns a 409 response if any of `dates` falls in the closed period
+///
+/// Write views call this with every transaction date the write touches
+/// (e.g. both the old and the new date of an update).
+pub async fn reject_if_locked(dates: &[DateTime<Utc>]) -> ViewResult<Option<Response>> {
+    let Some(through) = locked_through().await? else {
+        return Ok(None);
+    };
+    let Some(locked) = dates.iter().map(|d| d.date_naive()).find(|d| *d <= through) else {
+        return Ok(None);
+    };
+
+    let body = serde_json::json!({
+        "error": format!(
+            "The books are closed through {}; transactions dated {} cannot be changed",
+            through, locked
+        ),
+        "locked_through": through.to_string(),
+    });
+    Ok(Some(
+        Response::new(StatusCode::CONFLICT).with_body(json::to_vec(&body)?),
+    ))
+}
@@ -0,0 +1,22 @@
+//! Period lock model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The closed-books lock of the ledger (a single row)
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "periods", table_name = "period_locks")]
+pub struct PeriodLock {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Last locked day (inclusive); `None` if nothing is locked
+    #[field(null = true)]
+    pub locked_through: Option<NaiveDate>,
+
+    /// Last update timestamp
+    #[field(auto_now = true)]
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,43 @@
+//! Period serializers for request/response handling
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::models::PeriodLock;
+
+/// Request payload for closing the books through a day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClosePeriodRequest {
+    /// Last locked day (inclusive)
+    pub through: NaiveDate,
+}
+
+/// Request payload for reopening closed days
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReopenPeriodRequest {
+    /// New last locked day, earlier than the current one; `None` unlocks all
+    pub through: Option<NaiveDate>,
+}
+
+/// Current period lock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodLockResponse {
+    /// Transactions dated on or before this day (YYYY-MM-DD) are locked;
+    /// `None` if nothing is locked
+    pub locked_through: Option<String>,
+    /// When the lock last changed (RFC 3339)
+    pub updated_at: Option<String>,
+}
+
+impl PeriodLockResponse {
+    /// Builds the response from the lock row, if any
+    pub fn new(lock: Option<PeriodLock>) -> Self {
+        Self {
+            locked_through: lock
+                .as_ref()
+                .and_then(|l| l.locked_through)
+                .map(|d| d.to_string()),
+            updated_at: lock.map(|l| l.updated_at.to_rfc3339()),
+        }
+    }
+}
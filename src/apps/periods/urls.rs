@@ -0,0 +1,13 @@
+//! URL routing for periods app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the periods app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::get_period_lock)
+        .endpoint(views::close_period_view)
+        .endpoint(views::reopen_period_view)
+}
@@ -0,0 +1,180 @@
+//! Period views (API endpoints)
+
+use chrono::{NaiveDate, Utc};
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{get, post, Json, Request, Response, StatusCode};
+use sha2::{Digest, Sha256};
+
+use crate::apps::audit::{self, AuditAction, AuditEntity};
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use super::lock;
+use super::models::PeriodLock;
+use super::serializers::{ClosePeriodRequest, PeriodLockResponse, ReopenPeriodRequest};
+
+/// Environment variable holding the token required to reopen closed books
+pub const ADMIN_TOKEN_ENV: &str = "REINHARDT_ADMIN_TOKEN";
+
+/// Request header carrying the admin token
+pub const ADMIN_TOKEN_HEADER: &str = "x-admin-token";
+
+/// Compares two secrets in time independent of where they differ
+///
+/// Both are hashed first so the lengths do not leak either.
+fn secrets_equal(a: &str, b: &str) -> bool {
+    let (a, b) = (Sha256::digest(a.as_bytes()), Sha256::digest(b.as_bytes()));
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |diff, (x, y)| diff | (x ^ y));
+    diff == 0
+}
+
+/// Whether the request carries the configured admin token
+///
+/// Without `REINHARDT_ADMIN_TOKEN` nobody is an admin.
+fn is_admin(request: &Request) -> bool {
+    let Some(expected) = std::env::var(ADMIN_TOKEN_ENV)
+        .ok()
+        .filter(|t| !t.is_empty())
+    else {
+        return false;
+    };
+    request
+        .headers
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|token| secrets_equal(token, &expected))
+}
+
+/// Moves the lock to `through`, creating the row on first use, and audits it
+async fn set_lock(
+    through: Option<NaiveDate>,
+    action: AuditAction,
+    actor: &str,
+) -> ViewResult<Response> {
+    let manager = PeriodLock::objects();
+    let (old, saved) = match lock::current().await? {
+        Some(mut current) => {
+            let old = audit::snapshot(&current);
+            current.locked_through = through;
+            current.updated_at = Utc::now();
+            (Some(old), manager.update(&current).await?)
+        }
+        None => {
+            let created = PeriodLock {
+                id: None,
+                locked_through: through,
+                updated_at: Utc::now(),
+            };
+            (None, manager.create(&created).await?)
+        }
+    };
+
+    let new = audit::snapshot(&saved);
+    audit::record(
+        AuditEntity::Period,
+        saved.id.unwrap_or(0),
+        action,
+        actor,
+        old.as_ref(),
+        Some(&new),
+    )
+    .await?;
+
+    let response = PeriodLockResponse::new(Some(saved));
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get the current period lock
+///
+/// GET /periods/
+#[get("/", name = "periods_get")]
+pub async fn get_period_lock() -> ViewResult<Response> {
+    let response = PeriodLockResponse::new(lock::current().await?);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Close the books through a day
+///
+/// POST /periods/close/
+///
+/// The lock can only move forward; use reopen to move it back.
+#[post("/close/", name = "periods_close")]
+pub async fn close_period_view(
+    request: Request,
+    Json(close_req): Json<ClosePeriodRequest>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, close_period(close_req, actor)).await
+}
+
+async fn close_period(close_req: ClosePeriodRequest, actor: String) -> ViewResult<Response> {
+    if let Some(current) = lock::locked_through().await?
+        && close_req.through < current
+    {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!(
+                "The books are already closed through {}; reopen to move the lock back",
+                current
+            ),
+        );
+    }
+
+    set_lock(Some(close_req.through), AuditAction::Close, &actor).await
+}
+
+/// Reopen closed days (admin only)
+///
+/// POST /periods/reopen/
+///
+/// Requires the `X-Admin-Token` header to match `REINHARDT_ADMIN_TOKEN`.
+/// `through` must be earlier than the current lock; omit it to unlock
+/// everything.
+#[post("/reopen/", name = "periods_reopen")]
+pub async fn reopen_period_view(
+    request: Request,
+    Json(reopen_req): Json<ReopenPeriodRequest>,
+) -> ViewResult<Response> {
+    if !is_admin(&request) {
+        return error_response(
+            StatusCode::FORBIDDEN,
+            "Reopening closed books requires a valid X-Admin-Token",
+        );
+    }
+    let actor = audit::actor(&request);
+    idempotent(&request, reopen_period(reopen_req, actor)).await
+}
+
+async fn reopen_period(reopen_req: ReopenPeriodRequest, actor: String) -> ViewResult<Response> {
+    let Some(current) = lock::locked_through().await? else {
+        return error_response(StatusCode::CONFLICT, "The books are not closed");
+    };
+    if reopen_req.through.is_some_and(|through| through >= current) {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!(
+                "Reopening must move the lock before {}; use close to move it forward",
+                current
+            ),
+        );
+    }
+
+    set_lock(reopen_req.through, AuditAction::Reopen, &actor).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets_equal() {
+        assert!(secrets_equal("s3cret-token", "s3cret-token"));
+        assert!(!secrets_equal("s3cret-token", "s3cret-tokeN"));
+        assert!(!secrets_equal("s3cret-token", "s3cret"));
+        assert!(!secrets_equal("s3cret-token", ""));
+    }
+}
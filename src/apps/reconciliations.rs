@@ -0,0 +1,14 @@
+//! Reconciliations app for checking accounts against bank statements
+//!
+//! A session is started with the statement date and ending balance. The
+//! user ticks off (clears) the account's transactions that appear on the
+//! statement; once the cleared balance matches the statement the session
+//! is finalized and its cleared transactions become reconciled, after which
+//! their amount, date and account can no longer be changed.
+
+pub mod models;
+pub mod serializers;
+pub mod views;
+
+pub use models::*;
+pub use serializers::*;
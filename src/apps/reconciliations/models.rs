@@ -0,0 +1,38 @@
+//! Reconciliation model definitions
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A reconciliation of an account against one bank statement
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "reconciliations", table_name = "reconciliations")]
+pub struct Reconciliation {
+    /// Unique identifier
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Account being reconciled (foreign key)
+    pub account_id: i64,
+
+    /// Closing date of the statement
+    pub statement_date: NaiveDate,
+
+    /// Ending balance printed on the statement
+    pub ending_balance: i64,
+
+    /// When the session was finalized (`None` while open)
+    #[field(null = true)]
+    pub finalized_at: Option<DateTime<Utc>>,
+
+    /// Creation timestamp
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl Reconciliation {
+    /// Whether the session is finalized
+    pub fn is_finalized(&self) -> bool {
+        self.finalized_at.is_some()
+    }
+}
@@ -0,0 +1,67 @@
+//! Reconciliation serializers for request/response handling
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::models::Reconciliation;
+use crate::apps::transactions::serializers::TransactionResponse;
+
+/// Request payload for starting a reconciliation against a statement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateReconciliationRequest {
+    /// Closing date of the bank statement
+    pub statement_date: NaiveDate,
+    /// Ending balance printed on the statement
+    pub ending_balance: i64,
+}
+
+/// A reconciliation session of an account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationResponse {
+    pub id: i64,
+    pub account_id: i64,
+    /// Statement date as `YYYY-MM-DD`
+    pub statement_date: String,
+    pub ending_balance: i64,
+    /// Opening balance plus reconciled and cleared transactions up to the
+    /// statement date (for a finalized session, as reconciled)
+    pub cleared_balance: i64,
+    /// `ending_balance - cleared_balance`; must be 0 to finalize
+    pub difference: i64,
+    /// When the session was finalized (`None` while open)
+    pub finalized_at: Option<String>,
+    pub created_at: String,
+}
+
+/// A reconciliation session with the transactions to tick off
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationDetailResponse {
+    #[serde(flatten)]
+    pub reconciliation: ReconciliationResponse,
+    /// While open: the account's unreconciled transactions up to the
+    /// statement date. Once finalized: the transactions it reconciled.
+    pub transactions: Vec<TransactionResponse>,
+}
+
+/// Response wrapper for list of reconciliations, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationListResponse {
+    pub count: usize,
+    pub results: Vec<ReconciliationResponse>,
+}
+
+impl ReconciliationResponse {
+    /// Builds the response; `cleared_balance` is computed by the caller
+    pub fn new(reconciliation: Reconciliation, cleared_balance: i64) -> Self {
+        Self {
+            id: reconciliation.id.unwrap_or(0),
+            account_id: reconciliation.account_id,
+            statement_date: reconciliation.statement_date.format("%Y-%m-%d").to_string(),
+            ending_balance: reconciliation.ending_balance,
+            cleared_balance,
+            difference: reconciliation.ending_balance - cleared_balance,
+            finalized_at: reconciliation.finalized_at.map(|t| t.to_rfc3339()),
+            created_at: reconciliation.created_at.to_rfc3339(),
+        }
+    }
+}
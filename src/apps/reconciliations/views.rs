@@ -0,0 +1,358 @@
+//! Reconciliation views (API endpoints)
+//!
+//! Mounted under `/api/accounts/{id}/reconciliations/`.
+
+use chrono::Utc;
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, Json, Path, Request, Response, StatusCode};
+
+use crate::apps::accounts::models::Account;
+use crate::apps::audit::{self, AuditAction, AuditEntity};
+use crate::apps::cards::{card_payments, CardPayment};
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::periods::reject_if_locked;
+use crate::apps::sync::changes;
+use crate::apps::sync::models::{ChangeAction, SyncEntity};
+use crate::apps::transactions::models::{ReconcileStatus, Transaction};
+use crate::apps::transactions::serializers::TransactionResponse;
+use crate::apps::transactions::views::active_transactions;
+use super::models::Reconciliation;
+use super::serializers::{
+    CreateReconciliationRequest, ReconciliationDetailResponse, ReconciliationListResponse,
+    ReconciliationResponse,
+};
+
+fn account_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Account with id {} not found", id),
+    )
+}
+
+fn reconciliation_not_found(id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("Reconciliation with id {} not found", id),
+    )
+}
+
+/// Returns the reconciliations of an account
+async fn reconciliations_for(account_id: i64) -> ViewResult<Vec<Reconciliation>> {
+    let reconciliations = Reconciliation::objects().all().all().await?;
+    Ok(reconciliations
+        .into_iter()
+        .filter(|r| r.account_id == account_id)
+        .collect())
+}
+
+/// Returns a reconciliation if it belongs to the account
+async fn find_reconciliation(account_id: i64, id: i64) -> ViewResult<Option<Reconciliation>> {
+    let reconciliation = Reconciliation::objects().get(id).first().await?;
+    Ok(reconciliation.filter(|r| r.account_id == account_id))
+}
+
+/// Returns the account's transactions that are not in the trash
+async fn account_transactions(account_id: i64) -> ViewResult<Vec<Transaction>> {
+    let transactions = active_transactions().await?;
+    Ok(transactions
+        .into_iter()
+        .filter(|t| t.account_id == Some(account_id))
+        .collect())
+}
+
+/// Whether a transaction is ticked off for this statement
+fn is_cleared_for(transaction: &Transaction, reconciliation: &Reconciliation) -> bool {
+    transaction.get_reconcile_status() == ReconcileStatus::Cleared
+        && transaction.transaction_date.date_naive() <= reconciliation.statement_date
+}
+
+/// Opening balance plus reconciled transactions and the ones cleared for
+/// this statement
+///
+/// Card payments made by the statement date count as cleared, since they
+/// are withdrawn automatically. A finalized session matched its statement,
+/// so this is its ending balance.
+fn cleared_balance(
+    account: &Account,
+    reconciliation: &Reconciliation,
+    transactions: &[Transaction],
+    payments: &[CardPayment],
+) -> i64 {
+    if reconciliation.is_finalized() {
+        return reconciliation.ending_balance;
+    }
+    let account_id = account.id.unwrap_or(0);
+    account.opening_balance
+        + transactions
+            .iter()
+            .filter(|t| t.is_reconciled() || is_cleared_for(t, reconciliation))
+            .map(Transaction::signed_amount)
+            .sum::<i64>()
+        + payments
+            .iter()
+            .filter(|p| p.payment_date <= reconciliation.statement_date)
+            .map(|p| p.balance_change(account_id))
+            .sum::<i64>()
+}
+
+fn detail_response(
+    account: &Account,
+    reconciliation: Reconciliation,
+    transactions: Vec<Transaction>,
+    payments: &[CardPayment],
+) -> ReconciliationDetailResponse {
+    let balance = cleared_balance(account, &reconciliation, &transactions, payments);
+    let mut listed: Vec<Transaction> = transactions
+        .into_iter()
+        .filter(|t| {
+            if reconciliation.is_finalized() {
+                t.reconciliation_id == reconciliation.id
+            } else {
+                !t.is_reconciled()
+                    && t.transaction_date.date_naive() <= reconciliation.statement_date
+            }
+        })
+        .collect();
+    listed.sort_by_key(|t| (t.transaction_date, t.id));
+
+    ReconciliationDetailResponse {
+        reconciliation: ReconciliationResponse::new(reconciliation, balance),
+        transactions: listed.into_iter().map(TransactionResponse::from).collect(),
+    }
+}
+
+/// List reconciliations of an account, newest first
+///
+/// GET /accounts/{id}/reconciliations/
+#[get("/{id}/reconciliations/", name = "reconciliations_list")]
+pub async fn list_reconciliations(Path(account_id): Path<i64>) -> ViewResult<Response> {
+    let Some(account) = Account::objects().get(account_id).first().await? else {
+        return account_not_found(account_id);
+    };
+    let transactions = account_transactions(account_id).await?;
+    let payments = card_payments().await?;
+
+    let mut reconciliations = reconciliations_for(account_id).await?;
+    reconciliations.sort_by(|a, b| b.id.cmp(&a.id));
+    let results: Vec<ReconciliationResponse> = reconciliations
+        .into_iter()
+        .map(|r| {
+            let balance = cleared_balance(&account, &r, &transactions, &payments);
+            ReconciliationResponse::new(r, balance)
+        })
+        .collect();
+
+    let response = ReconciliationListResponse {
+        count: results.len(),
+        results,
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Start reconciling an account against a statement
+///
+/// POST /accounts/{id}/reconciliations/
+///
+/// Only one session per account can be open, and the statement date must
+/// be after the last finalized one.
+#[post("/{id}/reconciliations/", name = "reconciliations_create")]
+pub async fn create_reconciliation_view(
+    request: Request,
+    Path(account_id): Path<i64>,
+    Json(create_req): Json<CreateReconciliationRequest>,
+) -> ViewResult<Response> {
+    idempotent(&request, create_reconciliation(account_id, create_req)).await
+}
+
+async fn create_reconciliation(
+    account_id: i64,
+    create_req: CreateReconciliationRequest,
+) -> ViewResult<Response> {
+    let Some(account) = Account::objects().get(account_id).first().await? else {
+        return account_not_found(account_id);
+    };
+
+    let existing = reconciliations_for(account_id).await?;
+    if let Some(open) = existing.iter().find(|r| !r.is_finalized()) {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!(
+                "Reconciliation {} of this account is still open",
+                open.id.unwrap_or(0)
+            ),
+        );
+    }
+    if let Some(last) = existing.iter().map(|r| r.statement_date).max()
+        && create_req.statement_date <= last
+    {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("The account is already reconciled through {}", last),
+        );
+    }
+
+    let reconciliation = Reconciliation {
+        id: None,
+        account_id,
+        statement_date: create_req.statement_date,
+        ending_balance: create_req.ending_balance,
+        finalized_at: None,
+        created_at: Utc::now(),
+    };
+    let created = Reconciliation::objects().create(&reconciliation).await?;
+
+    let transactions = account_transactions(account_id).await?;
+    let payments = card_payments().await?;
+    let response = detail_response(&account, created, transactions, &payments);
+    Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
+}
+
+/// Get a reconciliation with the difference to the statement and the
+/// transactions to tick off
+///
+/// GET /accounts/{id}/reconciliations/{reconciliation_id}/
+///
+/// Transactions are ticked off with `PUT /api/transactions/{id}/` and
+/// `{"cleared": true}`.
+#[get(
+    "/{id}/reconciliations/{reconciliation_id}/",
+    name = "reconciliations_get"
+)]
+pub async fn get_reconciliation(Path((account_id, id)): Path<(i64, i64)>) -> ViewResult<Response> {
+    let Some(account) = Account::objects().get(account_id).first().await? else {
+        return account_not_found(account_id);
+    };
+    let Some(reconciliation) = find_reconciliation(account_id, id).await? else {
+        return reconciliation_not_found(id);
+    };
+
+    let transactions = account_transactions(account_id).await?;
+    let payments = card_payments().await?;
+    let response = detail_response(&account, reconciliation, transactions, &payments);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Finalize a reconciliation, marking its cleared transactions reconciled
+///
+/// POST /accounts/{id}/reconciliations/{reconciliation_id}/finalize/
+///
+/// Fails with 409 unless the cleared balance matches the statement.
+#[post(
+    "/{id}/reconciliations/{reconciliation_id}/finalize/",
+    name = "reconciliations_finalize"
+)]
+pub async fn finalize_reconciliation_view(
+    request: Request,
+    Path((account_id, id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, finalize_reconciliation(account_id, id, actor)).await
+}
+
+async fn finalize_reconciliation(account_id: i64, id: i64, actor: String) -> ViewResult<Response> {
+    let Some(account) = Account::objects().get(account_id).first().await? else {
+        return account_not_found(account_id);
+    };
+    let Some(mut reconciliation) = find_reconciliation(account_id, id).await? else {
+        return reconciliation_not_found(id);
+    };
+    if reconciliation.is_finalized() {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("Reconciliation {} is already finalized", id),
+        );
+    }
+
+    let transactions = account_transactions(account_id).await?;
+    let payments = card_payments().await?;
+    let difference = reconciliation.ending_balance
+        - cleared_balance(&account, &reconciliation, &transactions, &payments);
+    if difference != 0 {
+        let body = serde_json::json!({
+            "error": "The cleared balance does not match the statement",
+            "difference": difference,
+        });
+        return Ok(Response::new(StatusCode::CONFLICT).with_body(json::to_vec(&body)?));
+    }
+
+    // Reconciling changes the transactions, so none may be in a closed period
+    let dates: Vec<_> = transactions
+        .iter()
+        .filter(|t| is_cleared_for(t, &reconciliation))
+        .map(|t| t.transaction_date)
+        .collect();
+    if let Some(locked) = reject_if_locked(&dates).await? {
+        return Ok(locked);
+    }
+
+    let manager = Transaction::objects();
+    let now = Utc::now();
+    for mut transaction in transactions {
+        if !is_cleared_for(&transaction, &reconciliation) {
+            continue;
+        }
+        let Some(transaction_id) = transaction.id else {
+            continue;
+        };
+        let old = audit::snapshot(&transaction);
+        transaction.reconcile_status = ReconcileStatus::Reconciled.to_string();
+        transaction.reconciliation_id = Some(id);
+        transaction.updated_at = now;
+        let updated = manager.update(&transaction).await?;
+        changes::record(
+            SyncEntity::Transaction,
+            transaction_id,
+            ChangeAction::Upsert,
+        )
+        .await?;
+        let new = audit::snapshot(&updated);
+        audit::record(
+            AuditEntity::Transaction,
+            transaction_id,
+            AuditAction::Reconcile,
+            &actor,
+            Some(&old),
+            Some(&new),
+        )
+        .await?;
+    }
+
+    reconciliation.finalized_at = Some(now);
+    let finalized = Reconciliation::objects().update(&reconciliation).await?;
+
+    let transactions = account_transactions(account_id).await?;
+    let response = detail_response(&account, finalized, transactions, &payments);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Abandon an open reconciliation; cleared marks are kept
+///
+/// DELETE /accounts/{id}/reconciliations/{reconciliation_id}/
+#[delete(
+    "/{id}/reconciliations/{reconciliation_id}/",
+    name = "reconciliations_delete"
+)]
+pub async fn delete_reconciliation_view(
+    request: Request,
+    Path((account_id, id)): Path<(i64, i64)>,
+) -> ViewResult<Response> {
+    idempotent(&request, delete_reconciliation(account_id, id)).await
+}
+
+async fn delete_reconciliation(account_id: i64, id: i64) -> ViewResult<Response> {
+    let Some(reconciliation) = find_reconciliation(account_id, id).await? else {
+        return reconciliation_not_found(id);
+    };
+    if reconciliation.is_finalized() {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("Reconciliation {} is finalized and cannot be deleted", id),
+        );
+    }
+
+    Reconciliation::objects().delete(id).await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
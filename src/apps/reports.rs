@@ -1,8 +1,13 @@
 //! Reports app for financial summaries and analytics
 //!
-//! This module provides endpoints for monthly, yearly, and category-based reports.
+//! This module provides endpoints for monthly, yearly, category-based,
+//! comparison, time series and net worth reports.
 
+pub mod comparison;
+pub mod medical;
+pub mod net_worth;
 pub mod serializers;
+pub mod timeseries;
 pub mod views;
 pub mod urls;
 
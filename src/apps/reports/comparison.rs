@@ -0,0 +1,266 @@
+//! Comparing two months or years category by category
+//!
+//! Either period may be a month (`2026-01`) or a year (`2026`), so the
+//! same endpoint serves month-over-month and year-over-year comparisons.
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+use std::collections::HashMap;
+
+use crate::apps::installments::Schedules;
+use crate::apps::transactions::models::{Transaction, TransactionType};
+use super::serializers::{CategoryComparison, PeriodTotals};
+
+/// An amount counted in a period as (category, type, amount)
+pub type Entry = (i64, TransactionType, i64);
+
+/// A calendar month or year
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Month { year: i32, month: u32 },
+    Year(i32),
+}
+
+impl Period {
+    /// First day of the period, `None` outside the supported dates
+    pub fn start(&self) -> Option<NaiveDate> {
+        let (year, month) = match *self {
+            Period::Month { year, month } => (year, month),
+            Period::Year(year) => (year, 1),
+        };
+        NaiveDate::from_ymd_opt(year, month, 1)
+    }
+
+    /// Last day of the period, `None` outside the supported dates
+    ///
+    /// Periods parsed from a string always have one.
+    pub fn end(&self) -> Option<NaiveDate> {
+        let length = match self {
+            Period::Month { .. } => Months::new(1),
+            Period::Year(_) => Months::new(12),
+        };
+        self.start()?
+            .checked_add_months(length)?
+            .checked_sub_days(Days::new(1))
+    }
+
+    /// The month or year before, `None` before the earliest date
+    pub fn previous(&self) -> Option<Period> {
+        let previous = match *self {
+            Period::Month { year, month: 1 } => Period::Month {
+                year: year - 1,
+                month: 12,
+            },
+            Period::Month { year, month } => Period::Month {
+                year,
+                month: month - 1,
+            },
+            Period::Year(year) => Period::Year(year - 1),
+        };
+        previous.start().map(|_| previous)
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        match *self {
+            Period::Month { year, month } => date.year() == year && date.month() == month,
+            Period::Year(year) => date.year() == year,
+        }
+    }
+
+    /// Totals of the entries counted in the period
+    pub fn totals(&self, entries: &[Entry]) -> PeriodTotals {
+        let sum = |kind: TransactionType| -> i64 {
+            entries
+                .iter()
+                .filter(|(_, k, _)| *k == kind)
+                .map(|(_, _, amount)| amount)
+                .sum()
+        };
+        let total_income = sum(TransactionType::Income);
+        let total_expense = sum(TransactionType::Expense);
+        let format = |date: Option<NaiveDate>| {
+            date.map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        };
+        PeriodTotals {
+            period: self.to_string(),
+            start_date: format(self.start()),
+            end_date: format(self.end()),
+            total_income,
+            total_expense,
+            net_balance: total_income - total_expense,
+        }
+    }
+}
+
+impl std::fmt::Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Period::Month { year, month } => write!(f, "{:04}-{:02}", year, month),
+            Period::Year(year) => write!(f, "{:04}", year),
+        }
+    }
+}
+
+impl std::str::FromStr for Period {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("Invalid period: {} (use YYYY or YYYY-MM)", s);
+        let period = match s.trim().split_once('-') {
+            Some((year, month)) => Period::Month {
+                year: year.parse().map_err(|_| invalid())?,
+                month: month.parse().map_err(|_| invalid())?,
+            },
+            None => Period::Year(s.trim().parse().map_err(|_| invalid())?),
+        };
+        // The whole period has to fit in the supported dates
+        if period.end().is_some() {
+            Ok(period)
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+/// Returns the amounts counted in `period`
+///
+/// Purchases with a payment schedule in `schedules` count as their
+/// payments due in the period; pass an empty map to count them in full.
+pub fn entries(transactions: &[Transaction], schedules: &Schedules, period: Period) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for t in transactions {
+        match t.id.and_then(|id| schedules.get(&id)) {
+            Some(payments) => entries.extend(
+                payments
+                    .iter()
+                    .filter(|p| period.contains(p.due_date))
+                    .map(|p| (t.category_id, t.get_transaction_type(), p.amount())),
+            ),
+            None if period.contains(t.transaction_date.date_naive()) => {
+                entries.push((t.category_id, t.get_transaction_type(), t.amount));
+            }
+            None => {}
+        }
+    }
+    entries
+}
+
+/// Change from `base` to `target` in percent, rounded to one decimal
+pub fn change_percent(base: i64, target: i64) -> Option<f64> {
+    if base == 0 {
+        return None;
+    }
+    let percent = (target - base) as f64 / base.abs() as f64 * 100.0;
+    Some((percent * 10.0).round() / 10.0)
+}
+
+/// Totals both periods' entries per category, biggest change first
+pub fn compare(
+    base: &[Entry],
+    target: &[Entry],
+    names: &HashMap<i64, String>,
+) -> Vec<CategoryComparison> {
+    let mut rows: Vec<CategoryComparison> = Vec::new();
+    for (entries, is_target) in [(base, false), (target, true)] {
+        for &(category_id, kind, amount) in entries {
+            let found = rows
+                .iter()
+                .position(|r| r.category_id == category_id && r.transaction_type == kind);
+            let index = match found {
+                Some(index) => index,
+                None => {
+                    rows.push(CategoryComparison {
+                        category_id,
+                        category_name: names
+                            .get(&category_id)
+                            .cloned()
+                            .unwrap_or_else(|| "Unknown".to_string()),
+                        transaction_type: kind,
+                        base_amount: 0,
+                        target_amount: 0,
+                        change: 0,
+                        change_percent: None,
+                    });
+                    rows.len() - 1
+                }
+            };
+            if is_target {
+                rows[index].target_amount += amount;
+            } else {
+                rows[index].base_amount += amount;
+            }
+        }
+    }
+
+    for row in &mut rows {
+        row.change = row.target_amount - row.base_amount;
+        row.change_percent = change_percent(row.base_amount, row.target_amount);
+    }
+    rows.sort_by(|a, b| {
+        b.change
+            .abs()
+            .cmp(&a.change.abs())
+            .then(a.category_id.cmp(&b.category_id))
+    });
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_periods() {
+        let january: Period = "2026-01".parse().unwrap();
+        assert_eq!(january.start(), NaiveDate::from_ymd_opt(2026, 1, 1));
+        assert_eq!(january.previous().unwrap().to_string(), "2025-12");
+        assert_eq!(january.end(), NaiveDate::from_ymd_opt(2026, 1, 31));
+
+        let year: Period = "2025".parse().unwrap();
+        assert_eq!(year.start(), NaiveDate::from_ymd_opt(2025, 1, 1));
+        assert_eq!(year.end(), NaiveDate::from_ymd_opt(2025, 12, 31));
+        assert!("2026-13".parse::<Period>().is_err());
+        assert!("last month".parse::<Period>().is_err());
+    }
+
+    #[test]
+    fn test_periods_at_the_date_limits() {
+        let last_year = NaiveDate::MAX.year();
+        let last_month = format!("{}-{:02}", last_year, NaiveDate::MAX.month());
+        assert!(last_year.to_string().parse::<Period>().is_err());
+        assert!(last_month.parse::<Period>().is_err());
+
+        let first_year = NaiveDate::MIN.year();
+        let first = Period::Month {
+            year: first_year,
+            month: 1,
+        };
+        assert_eq!(first.start(), Some(NaiveDate::MIN));
+        assert_eq!(first.previous(), None);
+        assert_eq!(Period::Year(first_year).previous(), None);
+    }
+
+    #[test]
+    fn test_compare() {
+        let names = HashMap::from([(1, "食費".to_string()), (2, "給与".to_string())]);
+        let base = [
+            (1, TransactionType::Expense, 40_000),
+            (2, TransactionType::Income, 300_000),
+        ];
+        let target = [
+            (1, TransactionType::Expense, 30_000),
+            (1, TransactionType::Expense, 20_000),
+            (2, TransactionType::Income, 300_000),
+            (3, TransactionType::Expense, 5_000),
+        ];
+
+        let rows = compare(&base, &target, &names);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].category_name, "食費");
+        assert_eq!(rows[0].change, 10_000);
+        assert_eq!(rows[0].change_percent, Some(25.0));
+        assert_eq!(rows[1].category_name, "Unknown");
+        assert_eq!(rows[1].change_percent, None);
+        assert_eq!(rows[2].change, 0);
+    }
+}
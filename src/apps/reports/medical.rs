@@ -0,0 +1,303 @@
+//! 医療費控除 (medical expense deduction) calculation and CSV export
+//!
+//! Groups medical payments by patient, provider and expense kind, the same
+//! granularity as the rows of the e-Tax 医療費集計フォーム.
+
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+use crate::apps::medical::models::MedicalExpenseKind;
+use super::serializers::{MedicalDeductionEntry, MedicalDeductionPatientSummary};
+
+/// Fixed threshold of the deduction (10万円)
+pub const THRESHOLD_FIXED: i64 = 100_000;
+
+/// Upper limit of the deduction (200万円)
+pub const DEDUCTION_LIMIT: i64 = 2_000_000;
+
+/// Label used for payments without a recorded patient
+pub const UNKNOWN_PATIENT: &str = "（未設定）";
+
+/// A single medical payment selected for the report
+#[derive(Debug, Clone)]
+pub struct MedicalPayment {
+    pub patient: String,
+    pub provider: String,
+    pub kind: MedicalExpenseKind,
+    pub date: NaiveDate,
+    pub amount: i64,
+    pub reimbursement: i64,
+}
+
+/// Returns the deduction threshold: ¥100,000 or 5% of income if lower
+pub fn threshold(total_income: Option<i64>) -> i64 {
+    match total_income {
+        Some(income) => THRESHOLD_FIXED.min(income.max(0) * 5 / 100),
+        None => THRESHOLD_FIXED,
+    }
+}
+
+/// Returns the deductible amount for a net medical expense total
+pub fn deductible_amount(net_total: i64, threshold: i64) -> i64 {
+    (net_total - threshold).clamp(0, DEDUCTION_LIMIT)
+}
+
+/// Groups payments into report entries ordered by patient, provider and kind
+///
+/// Reimbursements only offset the payment they cover, so a reimbursement
+/// larger than its payment does not reduce other payments.
+pub fn group_entries(payments: &[MedicalPayment]) -> Vec<MedicalDeductionEntry> {
+    let mut groups: BTreeMap<(String, String, MedicalExpenseKind), Vec<&MedicalPayment>> =
+        BTreeMap::new();
+    for p in payments {
+        groups
+            .entry((p.patient.clone(), p.provider.clone(), p.kind))
+            .or_default()
+            .push(p);
+    }
+
+    groups
+        .into_iter()
+        .map(|((patient, provider, kind), items)| {
+            let total_paid: i64 = items.iter().map(|p| p.amount).sum();
+            let reimbursement: i64 = items.iter().map(|p| p.reimbursement.min(p.amount)).sum();
+            // A single payment keeps its date; aggregated rows leave it blank
+            let payment_date = match items.as_slice() {
+                [only] => Some(only.date.format("%Y-%m-%d").to_string()),
+                _ => None,
+            };
+            MedicalDeductionEntry {
+                patient,
+                provider,
+                kind,
+                total_paid,
+                reimbursement,
+                net_amount: total_paid - reimbursement,
+                transaction_count: items.len() as i32,
+                payment_date,
+            }
+        })
+        .collect()
+}
+
+/// Sums report entries per patient
+pub fn summarize_patients(entries: &[MedicalDeductionEntry]) -> Vec<MedicalDeductionPatientSummary> {
+    let mut totals: BTreeMap<&str, (i64, i64)> = BTreeMap::new();
+    for e in entries {
+        let total = totals.entry(e.patient.as_str()).or_insert((0, 0));
+        total.0 += e.total_paid;
+        total.1 += e.reimbursement;
+    }
+
+    totals
+        .into_iter()
+        .map(|(patient, (paid, reimbursement))| MedicalDeductionPatientSummary {
+            patient: patient.to_string(),
+            total_paid: paid,
+            reimbursement,
+            net_amount: paid - reimbursement,
+        })
+        .collect()
+}
+
+/// Renders entries as CSV in the column layout of the e-Tax 医療費集計フォーム
+///
+/// The output starts with a UTF-8 BOM so spreadsheet software detects the
+/// encoding when opening the file.
+pub fn to_csv(entries: &[MedicalDeductionEntry]) -> String {
+    let kinds = [
+        MedicalExpenseKind::Treatment,
+        MedicalExpenseKind::Medicine,
+        MedicalExpenseKind::CareService,
+        MedicalExpenseKind::Other,
+    ];
+
+    let mut header = vec![
+        "医療を受けた方の氏名".to_string(),
+        "病院・薬局などの支払先の名称".to_string(),
+    ];
+    header.extend(kinds.iter().map(|k| format!("医療費の区分（{}）", k.label())));
+    header.push("支払った医療費の金額".to_string());
+    header.push("左のうち、補填される金額".to_string());
+    header.push("支払年月日".to_string());
+
+    let mut out = String::from("\u{feff}");
+    out.push_str(&header.join(","));
+    out.push_str("\r\n");
+
+    for e in entries {
+        let mut row = vec![csv_field(&e.patient), csv_field(&e.provider)];
+        row.extend(kinds.iter().map(|k| {
+            if *k == e.kind {
+                "該当する".to_string()
+            } else {
+                String::new()
+            }
+        }));
+        row.push(e.total_paid.to_string());
+        row.push(e.reimbursement.to_string());
+        row.push(e.payment_date.clone().unwrap_or_default());
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+
+    out
+}
+
+/// Quotes a CSV field when it contains separators, quotes or newlines
+///
+/// Names starting with `=`, `+`, `-` or `@` get a leading `'` so
+/// spreadsheet software shows them as text instead of evaluating a
+/// formula.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payment(
+        patient: &str,
+        provider: &str,
+        kind: MedicalExpenseKind,
+        day: u32,
+        amount: i64,
+        reimbursement: i64,
+    ) -> MedicalPayment {
+        MedicalPayment {
+            patient: patient.to_string(),
+            provider: provider.to_string(),
+            kind,
+            date: NaiveDate::from_ymd_opt(2025, 3, day).unwrap(),
+            amount,
+            reimbursement,
+        }
+    }
+
+    #[test]
+    fn test_threshold() {
+        assert_eq!(threshold(None), 100_000);
+        assert_eq!(threshold(Some(5_000_000)), 100_000);
+        // 5% of income below ¥2,000,000
+        assert_eq!(threshold(Some(1_500_000)), 75_000);
+        assert_eq!(threshold(Some(-300_000)), 0);
+
+        assert_eq!(deductible_amount(180_000, 100_000), 80_000);
+        assert_eq!(deductible_amount(90_000, 100_000), 0);
+        assert_eq!(deductible_amount(10_000_000, 100_000), DEDUCTION_LIMIT);
+    }
+
+    #[test]
+    fn test_group_entries() {
+        let payments = [
+            payment(
+                "山田太郎",
+                "A病院",
+                MedicalExpenseKind::Treatment,
+                1,
+                10_000,
+                0,
+            ),
+            payment(
+                "山田太郎",
+                "A病院",
+                MedicalExpenseKind::Treatment,
+                15,
+                5_000,
+                2_000,
+            ),
+            payment(
+                "山田花子",
+                "B薬局",
+                MedicalExpenseKind::Medicine,
+                20,
+                3_000,
+                0,
+            ),
+            // Reimbursed beyond the payment: only offsets this payment
+            payment(
+                "山田花子",
+                "C病院",
+                MedicalExpenseKind::Treatment,
+                25,
+                8_000,
+                50_000,
+            ),
+        ];
+
+        let entries = group_entries(&payments);
+        assert_eq!(entries.len(), 3);
+
+        let clinic = entries.iter().find(|e| e.provider == "A病院").unwrap();
+        assert_eq!(clinic.total_paid, 15_000);
+        assert_eq!(clinic.reimbursement, 2_000);
+        assert_eq!(clinic.net_amount, 13_000);
+        assert_eq!(clinic.transaction_count, 2);
+        assert_eq!(clinic.payment_date, None);
+
+        let pharmacy = entries.iter().find(|e| e.provider == "B薬局").unwrap();
+        assert_eq!(pharmacy.payment_date.as_deref(), Some("2025-03-20"));
+
+        let hospital = entries.iter().find(|e| e.provider == "C病院").unwrap();
+        assert_eq!(hospital.reimbursement, 8_000);
+        assert_eq!(hospital.net_amount, 0);
+
+        let patients = summarize_patients(&entries);
+        assert_eq!(patients.len(), 2);
+        let taro = patients.iter().find(|p| p.patient == "山田太郎").unwrap();
+        assert_eq!(taro.net_amount, 13_000);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let payments = [
+            payment(
+                "山田, 太郎",
+                "A\"病院\"",
+                MedicalExpenseKind::Medicine,
+                1,
+                1_200,
+                200,
+            ),
+            payment(
+                "=HYPERLINK(\"x\")",
+                "@SUM(A1)",
+                MedicalExpenseKind::Other,
+                2,
+                300,
+                0,
+            ),
+        ];
+        let csv = to_csv(&group_entries(&payments));
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert!(lines[0].starts_with("\u{feff}医療を受けた方の氏名,"));
+        assert_eq!(
+            lines[1],
+            "\"'=HYPERLINK(\"\"x\"\")\",'@SUM(A1),,,,該当する,300,0,2025-03-02"
+        );
+        assert_eq!(
+            lines[2],
+            "\"山田, 太郎\",\"A\"\"病院\"\"\",,該当する,,,1200,200,2025-03-01"
+        );
+        assert_eq!(lines[3], "");
+    }
+
+    #[test]
+    fn test_csv_field_formulas() {
+        assert_eq!(csv_field("+81 3 1234"), "'+81 3 1234");
+        assert_eq!(csv_field("-"), "'-");
+        assert_eq!(csv_field("佐藤"), "佐藤");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+}
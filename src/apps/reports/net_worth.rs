@@ -0,0 +1,260 @@
+//! Net worth (純資産) from account balances, loans and valued assets
+//!
+//! Account balances and loan principal are replayed from their
+//! transactions, so the net worth can be computed for any past day.
+//! Investment accounts add the market value of their holdings on the day.
+//! Manually valued assets count at their latest valuation on or before it.
+
+use chrono::{Days, Months, NaiveDate};
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use std::collections::HashMap;
+
+use crate::apps::accounts::models::{Account, AccountType};
+use crate::apps::assets::models::{valuation_on, Asset, AssetValuation};
+use crate::apps::assets::views::valuations_by_asset;
+use crate::apps::cards::{card_payments, CardPayment};
+use crate::apps::installments::{schedules, Schedules};
+use crate::apps::investments::models::{InvestmentTransaction, Security, SecurityPrice};
+use crate::apps::investments::serializers::HoldingsReportResponse;
+use crate::apps::investments::views::{prices_by_security, securities_by_id};
+use crate::apps::loans::models::{Loan, LoanPrepayment};
+use crate::apps::loans::serializers::{remaining_principal_on, DatedRepayment};
+use crate::apps::loans::active_repayments;
+use crate::apps::transactions::models::Transaction;
+use crate::apps::transactions::views::active_transactions;
+use super::serializers::{NetWorthItem, NetWorthPoint, NetWorthSource, ReportInterval};
+use super::timeseries::{bucket_starts, next_bucket};
+
+/// Returns the last day of each interval from `from` through `to`; the last
+/// point is `to` itself
+pub fn period_ends(from: NaiveDate, to: NaiveDate, interval: ReportInterval) -> Vec<NaiveDate> {
+    bucket_starts(from, to, interval)
+        .into_iter()
+        .map(|start| {
+            next_bucket(start, interval)
+                .and_then(|next| next.checked_sub_days(Days::new(1)))
+                .map_or(to, |end| end.min(to))
+        })
+        .collect()
+}
+
+/// Sums the items of one day into assets and liabilities
+pub fn point(date: NaiveDate, items: &[NetWorthItem]) -> NetWorthPoint {
+    let assets: i64 = items.iter().map(|i| i.value.max(0)).sum();
+    let liabilities: i64 = items.iter().map(|i| (-i.value).max(0)).sum();
+    NetWorthPoint {
+        date: date.format("%Y-%m-%d").to_string(),
+        assets,
+        liabilities,
+        net_worth: assets - liabilities,
+    }
+}
+
+/// Everything the net worth is computed from
+pub struct Ledger {
+    accounts: Vec<Account>,
+    transactions: Vec<Transaction>,
+    payments: Vec<CardPayment>,
+    schedules: Schedules,
+    loans: Vec<Loan>,
+    prepayments: HashMap<i64, Vec<LoanPrepayment>>,
+    repayments: Vec<DatedRepayment>,
+    assets: Vec<Asset>,
+    valuations: HashMap<i64, Vec<AssetValuation>>,
+    securities: HashMap<i64, Security>,
+    investment_transactions: Vec<InvestmentTransaction>,
+    prices: HashMap<i64, Vec<SecurityPrice>>,
+}
+
+impl Ledger {
+    pub async fn load() -> ViewResult<Self> {
+        let mut prepayments: HashMap<i64, Vec<LoanPrepayment>> = HashMap::new();
+        for prepayment in LoanPrepayment::objects().all().all().await? {
+            prepayments
+                .entry(prepayment.loan_id)
+                .or_default()
+                .push(prepayment);
+        }
+
+        Ok(Self {
+            payments: card_payments().await?,
+            accounts: Account::objects().all().all().await?,
+            transactions: active_transactions().await?,
+            schedules: schedules().await?,
+            loans: Loan::objects().all().all().await?,
+            prepayments,
+            repayments: active_repayments().await?,
+            assets: Asset::objects().all().all().await?,
+            valuations: valuations_by_asset().await?,
+            securities: securities_by_id().await?,
+            investment_transactions: InvestmentTransaction::objects().all().all().await?,
+            prices: prices_by_security().await?,
+        })
+    }
+
+    /// Balance of an account at the end of `date`, including card payments
+    /// and the installment fees due by then
+    fn account_balance_on(&self, account: &Account, date: NaiveDate) -> i64 {
+        let id = account.id.unwrap_or(0);
+        let own: Vec<&Transaction> = self
+            .transactions
+            .iter()
+            .filter(|t| t.account_id == Some(id) && t.transaction_date.date_naive() <= date)
+            .collect();
+        let fees_due: i64 = own
+            .iter()
+            .filter_map(|t| t.id.and_then(|id| self.schedules.get(&id)))
+            .flatten()
+            .filter(|p| p.due_date <= date)
+            .map(|p| p.fee)
+            .sum();
+
+        account.opening_balance
+            + own.iter().map(|t| t.signed_amount()).sum::<i64>()
+            + self
+                .payments
+                .iter()
+                .filter(|p| p.payment_date <= date)
+                .map(|p| p.balance_change(id))
+                .sum::<i64>()
+            - fees_due
+    }
+
+    /// Whether an account existed on `date`: from its creation or its
+    /// first transaction, whichever is earlier
+    fn account_opened_by(&self, account: &Account, date: NaiveDate) -> bool {
+        account.created_at.date_naive() <= date
+            || self
+                .transactions
+                .iter()
+                .any(|t| t.account_id == account.id && t.transaction_date.date_naive() <= date)
+    }
+
+    /// Accounts, loans still owed and valued assets at the end of `date`
+    ///
+    /// A loan counts from a month before its first payment, when the money
+    /// is taken to be borrowed.
+    pub fn items_on(&self, date: NaiveDate) -> Vec<NetWorthItem> {
+        let accounts = self
+            .accounts
+            .iter()
+            .filter(|a| self.account_opened_by(a, date))
+            .map(|account| NetWorthItem {
+                source: NetWorthSource::Account,
+                id: account.id.unwrap_or(0),
+                name: account.name.clone(),
+                value: self.account_balance_on(account, date),
+            });
+
+        let loans = self
+            .loans
+            .iter()
+            .filter(|loan| {
+                date.checked_add_months(Months::new(1))
+                    .is_none_or(|month_later| loan.start_date <= month_later)
+            })
+            .filter_map(|loan| {
+                let id = loan.id.unwrap_or(0);
+                let prepayments = self.prepayments.get(&id).map_or(&[][..], Vec::as_slice);
+                let repayments: Vec<&DatedRepayment> = self
+                    .repayments
+                    .iter()
+                    .filter(|r| r.repayment.loan_id == id)
+                    .collect();
+                let remaining = remaining_principal_on(loan, prepayments, &repayments, date);
+                (remaining > 0).then(|| NetWorthItem {
+                    source: NetWorthSource::Loan,
+                    id,
+                    name: loan.name.clone(),
+                    value: -remaining,
+                })
+            });
+
+        let assets = self.assets.iter().filter_map(|asset| {
+            let id = asset.id?;
+            let valuations = self.valuations.get(&id)?;
+            let valuation = valuation_on(valuations, date)?;
+            Some(NetWorthItem {
+                source: NetWorthSource::Asset,
+                id,
+                name: asset.name.clone(),
+                value: asset.signed_value(valuation.value),
+            })
+        });
+
+        let investment_accounts: Vec<&Account> = self
+            .accounts
+            .iter()
+            .filter(|a| a.get_account_type() == AccountType::Investment)
+            .collect();
+        let holdings = HoldingsReportResponse::new(
+            &investment_accounts,
+            &self.securities,
+            &self.investment_transactions,
+            &self.prices,
+            date,
+        );
+        let investments = holdings
+            .accounts
+            .into_iter()
+            .filter(|a| !a.holdings.is_empty())
+            .map(|a| NetWorthItem {
+                source: NetWorthSource::Investment,
+                id: a.account_id,
+                name: a.account_name,
+                value: a.market_value,
+            });
+
+        accounts
+            .chain(investments)
+            .chain(loans)
+            .chain(assets)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_period_ends_by_month_stops_at_to() {
+        let ends = period_ends(date(2026, 1, 15), date(2026, 3, 10), ReportInterval::Month);
+        assert_eq!(
+            ends,
+            [date(2026, 1, 31), date(2026, 2, 28), date(2026, 3, 10)]
+        );
+    }
+
+    #[test]
+    fn test_period_ends_by_year() {
+        let ends = period_ends(date(2024, 6, 1), date(2026, 12, 31), ReportInterval::Year);
+        assert_eq!(
+            ends,
+            [date(2024, 12, 31), date(2025, 12, 31), date(2026, 12, 31)]
+        );
+    }
+
+    #[test]
+    fn test_point_splits_assets_and_liabilities() {
+        let item = |value| NetWorthItem {
+            source: NetWorthSource::Account,
+            id: 1,
+            name: String::new(),
+            value,
+        };
+        let p = point(
+            date(2026, 1, 31),
+            &[item(500_000), item(-120_000), item(30_000)],
+        );
+        assert_eq!(
+            (p.assets, p.liabilities, p.net_worth),
+            (530_000, 120_000, 410_000)
+        );
+    }
+}
@@ -2,44 +2,126 @@
 
 use serde::{Deserialize, Serialize};
 
-/// Category summary in reports
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CategorySummary {
-    pub category_id: i64,
-    pub category_name: String,
-    pub total_amount: i64,
-    pub transaction_count: i32,
+use crate::apps::medical::models::MedicalExpenseKind;
+use crate::apps::transactions::models::TransactionType;
+
+pub use crate::shared::types::{
+    CategoryComparison, CategorySummary, ComparisonReportResponse, MonthlyReportResponse,
+    MonthlySummary, PeriodTotals, ReportBasis, YearlyReportResponse,
+};
+
+/// Spacing of the points of a time series report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportInterval {
+    Day,
+    /// Monday through Sunday
+    Week,
+    #[default]
+    Month,
+    /// January-March, April-June, ...
+    Quarter,
+    Year,
+}
+
+impl std::fmt::Display for ReportInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportInterval::Day => write!(f, "day"),
+            ReportInterval::Week => write!(f, "week"),
+            ReportInterval::Month => write!(f, "month"),
+            ReportInterval::Quarter => write!(f, "quarter"),
+            ReportInterval::Year => write!(f, "year"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(ReportInterval::Day),
+            "week" => Ok(ReportInterval::Week),
+            "month" => Ok(ReportInterval::Month),
+            "quarter" => Ok(ReportInterval::Quarter),
+            "year" => Ok(ReportInterval::Year),
+            _ => Err(format!("Invalid report interval: {}", s)),
+        }
+    }
+}
+
+/// What each series of a time series report totals
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeseriesGroup {
+    #[default]
+    Category,
+    Account,
+}
+
+impl std::fmt::Display for TimeseriesGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeseriesGroup::Category => write!(f, "category"),
+            TimeseriesGroup::Account => write!(f, "account"),
+        }
+    }
+}
+
+impl std::str::FromStr for TimeseriesGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "category" => Ok(TimeseriesGroup::Category),
+            "account" => Ok(TimeseriesGroup::Account),
+            _ => Err(format!("Invalid time series group: {}", s)),
+        }
+    }
+}
+
+/// Where an item of the net worth breakdown comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetWorthSource {
+    Account,
+    Asset,
+    Loan,
+    /// Market value of the holdings of an investment account
+    Investment,
 }
 
-/// Monthly report response
+/// Net worth at the end of one interval
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MonthlyReportResponse {
-    pub year: i32,
-    pub month: u32,
-    pub total_income: i64,
-    pub total_expense: i64,
-    pub net_balance: i64,
-    pub income_by_category: Vec<CategorySummary>,
-    pub expense_by_category: Vec<CategorySummary>,
+pub struct NetWorthPoint {
+    /// Last day of the interval (or `to`) as `YYYY-MM-DD`
+    pub date: String,
+    pub assets: i64,
+    /// Amount owed, as a positive number
+    pub liabilities: i64,
+    pub net_worth: i64,
 }
 
-/// Yearly report response
+/// One account, asset or loan in the net worth breakdown
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct YearlyReportResponse {
-    pub year: i32,
-    pub total_income: i64,
-    pub total_expense: i64,
-    pub net_balance: i64,
-    pub monthly_summary: Vec<MonthlySummary>,
+pub struct NetWorthItem {
+    pub source: NetWorthSource,
+    pub id: i64,
+    pub name: String,
+    /// Positive for assets, negative for liabilities
+    pub value: i64,
 }
 
-/// Monthly summary within yearly report
+/// Net worth report response
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MonthlySummary {
-    pub month: u32,
-    pub total_income: i64,
-    pub total_expense: i64,
-    pub net_balance: i64,
+pub struct NetWorthReportResponse {
+    pub from: String,
+    pub to: String,
+    pub interval: ReportInterval,
+    pub points: Vec<NetWorthPoint>,
+    /// What makes up the last point
+    pub items: Vec<NetWorthItem>,
 }
 
 /// Category report response
@@ -51,3 +133,97 @@ pub struct CategoryReportResponse {
     pub total_income: i64,
     pub total_expense: i64,
 }
+
+/// One category or account of a time series report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeseriesSeries {
+    /// Category or account ID
+    pub id: i64,
+    pub name: String,
+    /// Sum of `values`
+    pub total: i64,
+    /// Amount in each bucket, zero when nothing was recorded
+    pub values: Vec<i64>,
+    /// Trailing average over `moving_average` buckets, `None` until that
+    /// many buckets have passed (only when requested)
+    pub moving_average: Option<Vec<Option<f64>>>,
+    /// Running total through each bucket (only when requested)
+    pub cumulative: Option<Vec<i64>>,
+}
+
+/// Time series report response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeseriesReportResponse {
+    pub from: String,
+    pub to: String,
+    pub interval: ReportInterval,
+    pub group_by: TimeseriesGroup,
+    pub transaction_type: TransactionType,
+    /// First day of each bucket as `YYYY-MM-DD`; the first and last
+    /// buckets only count days from `from` through `to`
+    pub buckets: Vec<String>,
+    /// Largest total first
+    pub series: Vec<TimeseriesSeries>,
+}
+
+/// Medical payments grouped by patient, provider and kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MedicalDeductionEntry {
+    pub patient: String,
+    pub provider: String,
+    pub kind: MedicalExpenseKind,
+    pub total_paid: i64,
+    pub reimbursement: i64,
+    pub net_amount: i64,
+    pub transaction_count: i32,
+    /// Payment date, only set when the entry covers a single payment
+    pub payment_date: Option<String>,
+}
+
+/// Medical payments totalled per patient
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MedicalDeductionPatientSummary {
+    pub patient: String,
+    pub total_paid: i64,
+    pub reimbursement: i64,
+    pub net_amount: i64,
+}
+
+/// Medical expense deduction (医療費控除) report response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MedicalDeductionReportResponse {
+    pub year: i32,
+    pub category_ids: Vec<i64>,
+    pub total_paid: i64,
+    pub total_reimbursement: i64,
+    pub net_medical_expense: i64,
+    /// 総所得金額等 used for the 5% threshold, if given
+    pub total_income: Option<i64>,
+    pub threshold: i64,
+    pub threshold_met: bool,
+    pub deductible_amount: i64,
+    pub by_patient: Vec<MedicalDeductionPatientSummary>,
+    pub entries: Vec<MedicalDeductionEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::types::assert_wire_values;
+
+    #[test]
+    fn test_report_interval_wire_values() {
+        assert_wire_values(&[
+            ReportInterval::Day,
+            ReportInterval::Week,
+            ReportInterval::Month,
+            ReportInterval::Quarter,
+            ReportInterval::Year,
+        ]);
+    }
+
+    #[test]
+    fn test_timeseries_group_wire_values() {
+        assert_wire_values(&[TimeseriesGroup::Category, TimeseriesGroup::Account]);
+    }
+}
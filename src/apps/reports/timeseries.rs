@@ -0,0 +1,167 @@
+//! Bucketing amounts by day, week, month, quarter or year
+//!
+//! Every series of a report shares the same buckets, so values line up
+//! for charting and buckets without transactions are zero.
+
+use chrono::{Datelike, Days, Months, NaiveDate};
+
+use crate::apps::cards::billing::first_of_month;
+use super::serializers::ReportInterval;
+
+/// First day of the bucket holding `date`
+pub fn bucket_start(date: NaiveDate, interval: ReportInterval) -> NaiveDate {
+    match interval {
+        ReportInterval::Day => date,
+        ReportInterval::Week => date
+            .checked_sub_days(Days::new(u64::from(date.weekday().num_days_from_monday())))
+            .unwrap_or(date),
+        ReportInterval::Month => first_of_month(date),
+        ReportInterval::Quarter => {
+            let month = (date.month() - 1) / 3 * 3 + 1;
+            NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+        }
+        ReportInterval::Year => date.with_ordinal(1).unwrap_or(date),
+    }
+}
+
+/// First day of the bucket after the one starting on `start`; `None` past
+/// the last representable date
+pub fn next_bucket(start: NaiveDate, interval: ReportInterval) -> Option<NaiveDate> {
+    match interval {
+        ReportInterval::Day => start.checked_add_days(Days::new(1)),
+        ReportInterval::Week => start.checked_add_days(Days::new(7)),
+        ReportInterval::Month => start.checked_add_months(Months::new(1)),
+        ReportInterval::Quarter => start.checked_add_months(Months::new(3)),
+        ReportInterval::Year => start.checked_add_months(Months::new(12)),
+    }
+}
+
+/// Number of buckets from the one holding `from` through the one holding
+/// `to`, computed without listing them so callers can reject huge ranges
+/// first
+pub fn bucket_count(from: NaiveDate, to: NaiveDate, interval: ReportInterval) -> u64 {
+    let start = bucket_start(from, interval);
+    if start > to {
+        return 0;
+    }
+    let days = (to - start).num_days().unsigned_abs();
+    let years = i64::from(to.year()) - i64::from(start.year());
+    let months = (years * 12 + i64::from(to.month()) - i64::from(start.month())).unsigned_abs();
+    match interval {
+        ReportInterval::Day => days + 1,
+        ReportInterval::Week => days / 7 + 1,
+        ReportInterval::Month => months + 1,
+        ReportInterval::Quarter => months / 3 + 1,
+        ReportInterval::Year => years.unsigned_abs() + 1,
+    }
+}
+
+/// First days of the buckets from the one holding `from` through the one
+/// holding `to`
+///
+/// Allocates one date per bucket; check [`bucket_count`] first for ranges
+/// taken from a request.
+pub fn bucket_starts(from: NaiveDate, to: NaiveDate, interval: ReportInterval) -> Vec<NaiveDate> {
+    let mut next = Some(bucket_start(from, interval));
+    let mut starts = Vec::new();
+    while let Some(start) = next.filter(|s| *s <= to) {
+        starts.push(start);
+        next = next_bucket(start, interval);
+    }
+    starts
+}
+
+/// Index of the bucket holding `date` among sorted bucket `starts`
+pub fn bucket_index(starts: &[NaiveDate], date: NaiveDate) -> Option<usize> {
+    starts.partition_point(|s| *s <= date).checked_sub(1)
+}
+
+/// Trailing average over `window` buckets; `None` until `window` buckets
+/// have passed
+pub fn moving_average(values: &[i64], window: usize) -> Vec<Option<f64>> {
+    (0..values.len())
+        .map(|i| {
+            (window > 0 && i + 1 >= window).then(|| {
+                let sum: i64 = values[i + 1 - window..=i].iter().sum();
+                sum as f64 / window as f64
+            })
+        })
+        .collect()
+}
+
+/// Running total through each bucket
+pub fn cumulative(values: &[i64]) -> Vec<i64> {
+    values
+        .iter()
+        .scan(0, |total, value| {
+            *total += value;
+            Some(*total)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_buckets() {
+        // 2026-01-01 is a Thursday
+        let weeks = bucket_starts(date(2026, 1, 1), date(2026, 1, 12), ReportInterval::Week);
+        assert_eq!(
+            weeks,
+            [date(2025, 12, 29), date(2026, 1, 5), date(2026, 1, 12)]
+        );
+        assert_eq!(bucket_index(&weeks, date(2026, 1, 11)), Some(1));
+        assert_eq!(bucket_index(&weeks, date(2025, 12, 1)), None);
+
+        let quarters = bucket_starts(date(2025, 11, 3), date(2026, 4, 1), ReportInterval::Quarter);
+        assert_eq!(
+            quarters,
+            [date(2025, 10, 1), date(2026, 1, 1), date(2026, 4, 1)]
+        );
+    }
+
+    #[test]
+    fn test_bucket_count() {
+        let (from, to) = (date(2025, 11, 3), date(2027, 2, 14));
+        for interval in [
+            ReportInterval::Day,
+            ReportInterval::Week,
+            ReportInterval::Month,
+            ReportInterval::Quarter,
+            ReportInterval::Year,
+        ] {
+            assert_eq!(
+                bucket_count(from, to, interval),
+                bucket_starts(from, to, interval).len() as u64,
+                "{:?}",
+                interval
+            );
+        }
+        assert_eq!(bucket_count(to, from, ReportInterval::Day), 0);
+
+        // Counted without listing the days
+        let days = bucket_count(date(1, 1, 1), date(2026, 1, 1), ReportInterval::Day);
+        assert_eq!(days, 739_617);
+        assert_eq!(next_bucket(NaiveDate::MAX, ReportInterval::Day), None);
+        assert_eq!(
+            bucket_starts(NaiveDate::MAX, NaiveDate::MAX, ReportInterval::Year).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_moving_average_and_cumulative() {
+        let values = [10, 20, 30, 40];
+        assert_eq!(
+            moving_average(&values, 3),
+            [None, None, Some(20.0), Some(30.0)]
+        );
+        assert_eq!(cumulative(&values), [10, 30, 60, 100]);
+    }
+}
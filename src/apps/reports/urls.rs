@@ -10,4 +10,9 @@ pub fn url_patterns() -> ServerRouter {
         .endpoint(views::monthly_report)
         .endpoint(views::yearly_report)
         .endpoint(views::by_category_report)
+        .endpoint(views::comparison_report)
+        .endpoint(views::timeseries_report)
+        .endpoint(views::medical_deduction_report)
+        .endpoint(views::medical_deduction_csv)
+        .endpoint(views::net_worth_report)
 }
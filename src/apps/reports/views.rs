@@ -1,6 +1,6 @@
 //! Report views (API endpoints)
 
-use chrono::Datelike;
+use chrono::{Datelike, Months, NaiveDate};
 use reinhardt::core::serde::json;
 use reinhardt::http::ViewResult;
 use reinhardt::Model;
@@ -8,18 +8,31 @@ use reinhardt::{get, Query, Response, StatusCode};
 use serde::Deserialize;
 use std::collections::HashMap;
 
-use crate::apps::categories::models::Category;
+use crate::apps::accounts::models::Account;
+use crate::apps::categories::views::active_categories;
+use crate::apps::installments::schedules;
+use crate::apps::loans::active_repayments;
+use crate::apps::medical::models::{MedicalExpense, MedicalExpenseKind};
 use crate::apps::transactions::models::{Transaction, TransactionType};
+use crate::apps::transactions::views::active_transactions;
+use super::comparison::{self, Period};
+use super::medical::{self as medical_report, MedicalPayment};
+use super::net_worth::{self, Ledger};
 use super::serializers::{
-    CategoryReportResponse, CategorySummary, MonthlyReportResponse, MonthlySummary,
+    CategoryReportResponse, CategorySummary, ComparisonReportResponse,
+    MedicalDeductionReportResponse, MonthlyReportResponse, MonthlySummary, NetWorthReportResponse,
+    ReportBasis, ReportInterval, TimeseriesGroup, TimeseriesReportResponse, TimeseriesSeries,
     YearlyReportResponse,
 };
+use super::timeseries;
 
 /// Query parameters for monthly report
 #[derive(Debug, Deserialize)]
 pub struct MonthlyReportQuery {
     pub year: Option<i32>,
     pub month: Option<u32>,
+    /// `purchase` (default) or `installment`
+    pub basis: Option<ReportBasis>,
 }
 
 /// Query parameters for yearly report
@@ -35,56 +48,133 @@ pub struct CategoryReportQuery {
     pub end_date: Option<String>,
 }
 
+/// Query parameters for the comparison report
+#[derive(Debug, Deserialize)]
+pub struct ComparisonReportQuery {
+    /// Period compared against, `YYYY-MM` or `YYYY` (default: the one
+    /// before `target`)
+    pub base: Option<String>,
+    /// Period of interest, `YYYY-MM` or `YYYY` (default: this month)
+    pub target: Option<String>,
+    /// `purchase` (default) or `installment`
+    pub basis: Option<ReportBasis>,
+}
+
+/// Query parameters for the medical expense deduction report
+#[derive(Debug, Deserialize)]
+pub struct MedicalDeductionQuery {
+    pub year: Option<i32>,
+    /// Comma-separated IDs of the categories holding medical expenses
+    pub category_ids: Option<String>,
+    /// 総所得金額等, enables the 5%-of-income threshold
+    pub total_income: Option<i64>,
+}
+
+/// Query parameters for the net worth report
+#[derive(Debug, Deserialize)]
+pub struct NetWorthQuery {
+    /// First day of the series (default: a year before `to`)
+    pub from: Option<NaiveDate>,
+    /// Last day of the series (default: today)
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub interval: ReportInterval,
+}
+
+/// Most points a net worth series may have (50 years by month)
+const MAX_NET_WORTH_POINTS: usize = 600;
+
+/// Query parameters for the time series report
+#[derive(Debug, Deserialize)]
+pub struct TimeseriesQuery {
+    /// First day counted (default: a year before `to`)
+    pub from: Option<NaiveDate>,
+    /// Last day counted (default: today)
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub interval: ReportInterval,
+    #[serde(default)]
+    pub group_by: TimeseriesGroup,
+    /// `expense` (default) or `income`
+    pub transaction_type: Option<TransactionType>,
+    /// Comma-separated IDs of the categories or accounts to include
+    /// (default: all)
+    pub ids: Option<String>,
+    /// Window in buckets of the moving average added to each series
+    pub moving_average: Option<usize>,
+    /// Add running totals to each series
+    #[serde(default)]
+    pub cumulative: bool,
+}
+
+/// Most buckets a time series may have (about three years by day)
+const MAX_TIMESERIES_BUCKETS: usize = 1100;
+
+/// Fetches what the income and expense reports count: active
+/// transactions whose category is not in the trash, with the names of the
+/// active categories
+async fn reported_transactions() -> ViewResult<(Vec<Transaction>, HashMap<i64, String>)> {
+    let category_names: HashMap<i64, String> = active_categories()
+        .await?
+        .into_iter()
+        .filter_map(|c| c.id.map(|id| (id, c.name)))
+        .collect();
+    let transactions = active_transactions()
+        .await?
+        .into_iter()
+        .filter(|t| category_names.contains_key(&t.category_id))
+        .collect();
+    Ok((transactions, category_names))
+}
+
 /// Get monthly report
 ///
-/// GET /reports/monthly/?year=2026&month=1
+/// GET /reports/monthly/?year=2026&month=1&basis=installment
+///
+/// With `basis=installment`, purchases paid in installments count as their
+/// payments (with fees) due in the month instead of in full on the
+/// purchase date.
 #[get("/monthly/", name = "reports_monthly")]
 pub async fn monthly_report(Query(params): Query<MonthlyReportQuery>) -> ViewResult<Response> {
     let year = params.year.unwrap_or_else(|| chrono::Utc::now().year());
     let month = params.month.unwrap_or_else(|| chrono::Utc::now().month());
+    let basis = params.basis.unwrap_or_default();
 
     // Fetch from database
-    let transactions = Transaction::objects().all().all().await?;
-    let categories = Category::objects().all().all().await?;
+    let (transactions, category_names) = reported_transactions().await?;
+    let schedules = match basis {
+        ReportBasis::Purchase => HashMap::new(),
+        ReportBasis::Installment => schedules().await?,
+    };
 
-    // Create category name lookup
-    let category_names: HashMap<i64, String> = categories
-        .iter()
-        .filter_map(|c| c.id.map(|id| (id, c.name.clone())))
-        .collect();
-
-    // Filter transactions for the specified month
-    let monthly_transactions: Vec<_> = transactions
-        .iter()
-        .filter(|t| {
-            t.transaction_date.year() == year && t.transaction_date.month() == month
-        })
-        .collect();
+    // Amounts counted in the specified month as (category, type, amount)
+    let monthly_entries =
+        comparison::entries(&transactions, &schedules, Period::Month { year, month });
 
     // Calculate totals
-    let total_income: i64 = monthly_transactions
+    let total_income: i64 = monthly_entries
         .iter()
-        .filter(|t| t.get_transaction_type() == TransactionType::Income)
-        .map(|t| t.amount)
+        .filter(|(_, kind, _)| *kind == TransactionType::Income)
+        .map(|(_, _, amount)| amount)
         .sum();
 
-    let total_expense: i64 = monthly_transactions
+    let total_expense: i64 = monthly_entries
         .iter()
-        .filter(|t| t.get_transaction_type() == TransactionType::Expense)
-        .map(|t| t.amount)
+        .filter(|(_, kind, _)| *kind == TransactionType::Expense)
+        .map(|(_, _, amount)| amount)
         .sum();
 
     // Group by category
     let mut income_by_category: HashMap<i64, (i64, i32)> = HashMap::new();
     let mut expense_by_category: HashMap<i64, (i64, i32)> = HashMap::new();
 
-    for t in &monthly_transactions {
-        let map = match t.get_transaction_type() {
+    for (category_id, kind, amount) in &monthly_entries {
+        let map = match kind {
             TransactionType::Income => &mut income_by_category,
             TransactionType::Expense => &mut expense_by_category,
         };
-        let entry = map.entry(t.category_id).or_insert((0, 0));
-        entry.0 += t.amount;
+        let entry = map.entry(*category_id).or_insert((0, 0));
+        entry.0 += amount;
         entry.1 += 1;
     }
 
@@ -117,6 +207,7 @@ pub async fn monthly_report(Query(params): Query<MonthlyReportQuery>) -> ViewRes
     let response = MonthlyReportResponse {
         year,
         month,
+        basis,
         total_income,
         total_expense,
         net_balance: total_income - total_expense,
@@ -134,7 +225,7 @@ pub async fn monthly_report(Query(params): Query<MonthlyReportQuery>) -> ViewRes
 pub async fn yearly_report(Query(params): Query<YearlyReportQuery>) -> ViewResult<Response> {
     let year = params.year.unwrap_or_else(|| chrono::Utc::now().year());
 
-    let transactions = Transaction::objects().all().all().await?;
+    let (transactions, _) = reported_transactions().await?;
 
     // Filter transactions for the specified year
     let yearly_transactions: Vec<_> = transactions
@@ -182,12 +273,26 @@ pub async fn yearly_report(Query(params): Query<YearlyReportQuery>) -> ViewResul
         })
         .collect();
 
+    // Split of the year's linked loan repayments
+    let (loan_principal, loan_interest) = active_repayments()
+        .await?
+        .iter()
+        .filter(|r| r.date.year() == year)
+        .fold((0, 0), |(principal, interest), r| {
+            (
+                principal + r.repayment.principal,
+                interest + r.repayment.interest,
+            )
+        });
+
     let response = YearlyReportResponse {
         year,
         total_income,
         total_expense,
         net_balance: total_income - total_expense,
         monthly_summary,
+        loan_principal,
+        loan_interest,
     };
 
     Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
@@ -201,14 +306,7 @@ pub async fn by_category_report(Query(params): Query<CategoryReportQuery>) -> Vi
     let start_date = params.start_date;
     let end_date = params.end_date;
 
-    let transactions = Transaction::objects().all().all().await?;
-    let categories = Category::objects().all().all().await?;
-
-    // Create category name lookup
-    let category_names: HashMap<i64, String> = categories
-        .iter()
-        .filter_map(|c| c.id.map(|id| (id, c.name.clone())))
-        .collect();
+    let (transactions, category_names) = reported_transactions().await?;
 
     // Filter transactions by date range if provided
     let filtered_transactions: Vec<_> = transactions
@@ -277,3 +375,312 @@ pub async fn by_category_report(Query(params): Query<CategoryReportQuery>) -> Vi
 
     Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
 }
+
+/// Compare two months or years category by category
+///
+/// GET /reports/compare/?base=2025-01&target=2026-01
+///
+/// Without `base`, the target is compared with the month or year before
+/// it. Categories are sorted by the size of their change, biggest first.
+#[get("/compare/", name = "reports_compare")]
+pub async fn comparison_report(
+    Query(params): Query<ComparisonReportQuery>,
+) -> ViewResult<Response> {
+    let today = chrono::Utc::now().date_naive();
+    let parse = |period: &Option<String>| period.as_deref().map(str::parse::<Period>).transpose();
+    let periods = match (parse(&params.target), parse(&params.base)) {
+        (Ok(target), Ok(base)) => {
+            let target = target.unwrap_or(Period::Month {
+                year: today.year(),
+                month: today.month(),
+            });
+            base.or_else(|| target.previous())
+                .map(|base| (target, base))
+                .ok_or_else(|| format!("There is no period before {}", target))
+        }
+        (Err(e), _) | (_, Err(e)) => Err(e),
+    };
+    let (target, base) = match periods {
+        Ok(periods) => periods,
+        Err(e) => {
+            let body = serde_json::json!({ "error": e });
+            return Ok(Response::new(StatusCode::BAD_REQUEST).with_body(json::to_vec(&body)?));
+        }
+    };
+    let basis = params.basis.unwrap_or_default();
+
+    let (transactions, category_names) = reported_transactions().await?;
+    let schedules = match basis {
+        ReportBasis::Purchase => HashMap::new(),
+        ReportBasis::Installment => schedules().await?,
+    };
+
+    let base_entries = comparison::entries(&transactions, &schedules, base);
+    let target_entries = comparison::entries(&transactions, &schedules, target);
+    let response = ComparisonReportResponse {
+        basis,
+        base: base.totals(&base_entries),
+        target: target.totals(&target_entries),
+        categories: comparison::compare(&base_entries, &target_entries, &category_names),
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Get amounts per category or account in aligned day, week, month,
+/// quarter or year buckets
+///
+/// GET /reports/timeseries/?group_by=category&interval=week&from=2026-01-01&to=2026-03-31&ids=4&moving_average=4
+///
+/// Every series has one value per bucket, zero when nothing was recorded,
+/// so series can be charted together.
+#[get("/timeseries/", name = "reports_timeseries")]
+pub async fn timeseries_report(Query(params): Query<TimeseriesQuery>) -> ViewResult<Response> {
+    let to = params.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = params.from.unwrap_or_else(|| {
+        to.checked_sub_months(Months::new(12))
+            .unwrap_or(NaiveDate::MIN)
+    });
+    if from > to {
+        return Ok(Response::new(StatusCode::BAD_REQUEST)
+            .with_body(br#"{"error": "from must not be after to"}"#.to_vec()));
+    }
+    if timeseries::bucket_count(from, to, params.interval) > MAX_TIMESERIES_BUCKETS as u64 {
+        return Ok(Response::new(StatusCode::BAD_REQUEST).with_body(
+            format!(
+                r#"{{"error": "The series is limited to {} buckets"}}"#,
+                MAX_TIMESERIES_BUCKETS
+            )
+            .into_bytes(),
+        ));
+    }
+    let starts = timeseries::bucket_starts(from, to, params.interval);
+    let transaction_type = params.transaction_type.unwrap_or(TransactionType::Expense);
+    let ids: Vec<i64> = params
+        .ids
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect();
+
+    let (transactions, category_names) = reported_transactions().await?;
+    let names: HashMap<i64, String> = match params.group_by {
+        TimeseriesGroup::Category => category_names,
+        TimeseriesGroup::Account => Account::objects()
+            .all()
+            .all()
+            .await?
+            .into_iter()
+            .filter_map(|a| a.id.map(|id| (id, a.name)))
+            .collect(),
+    };
+
+    // Requested series are listed even when nothing was recorded
+    let mut values: HashMap<i64, Vec<i64>> =
+        ids.iter().map(|id| (*id, vec![0; starts.len()])).collect();
+    for t in transactions {
+        let date = t.transaction_date.date_naive();
+        let id = match params.group_by {
+            TimeseriesGroup::Category => t.category_id,
+            TimeseriesGroup::Account => t.account_id.unwrap_or(0),
+        };
+        if t.get_transaction_type() != transaction_type
+            || date < from
+            || date > to
+            || (!ids.is_empty() && !ids.contains(&id))
+        {
+            continue;
+        }
+        if let Some(index) = timeseries::bucket_index(&starts, date) {
+            values.entry(id).or_insert_with(|| vec![0; starts.len()])[index] += t.amount;
+        }
+    }
+
+    let mut series: Vec<TimeseriesSeries> = values
+        .into_iter()
+        .map(|(id, values)| TimeseriesSeries {
+            id,
+            name: names
+                .get(&id)
+                .cloned()
+                .unwrap_or_else(|| "Unknown".to_string()),
+            total: values.iter().sum(),
+            moving_average: params
+                .moving_average
+                .map(|window| timeseries::moving_average(&values, window)),
+            cumulative: params.cumulative.then(|| timeseries::cumulative(&values)),
+            values,
+        })
+        .collect();
+    series.sort_by(|a, b| b.total.cmp(&a.total).then(a.id.cmp(&b.id)));
+
+    let response = TimeseriesReportResponse {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        interval: params.interval,
+        group_by: params.group_by,
+        transaction_type,
+        buckets: starts
+            .iter()
+            .map(|s| s.format("%Y-%m-%d").to_string())
+            .collect(),
+        series,
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Builds the medical expense deduction report for a query
+///
+/// Selects the year's expense transactions in the given categories plus any
+/// transaction that has medical details recorded.
+async fn build_medical_deduction_report(
+    params: &MedicalDeductionQuery,
+) -> ViewResult<MedicalDeductionReportResponse> {
+    let year = params.year.unwrap_or_else(|| chrono::Utc::now().year());
+    let category_ids: Vec<i64> = params
+        .category_ids
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|id| id.trim().parse().ok())
+        .collect();
+
+    let transactions = active_transactions().await?;
+    let details: HashMap<i64, MedicalExpense> = MedicalExpense::objects()
+        .all()
+        .all()
+        .await?
+        .into_iter()
+        .map(|d| (d.transaction_id, d))
+        .collect();
+
+    let payments: Vec<MedicalPayment> = transactions
+        .iter()
+        .filter(|t| {
+            t.transaction_date.year() == year
+                && t.get_transaction_type() == TransactionType::Expense
+        })
+        .filter_map(|t| {
+            let detail = t.id.and_then(|id| details.get(&id));
+            if detail.is_none() && !category_ids.contains(&t.category_id) {
+                return None;
+            }
+            Some(match detail {
+                Some(d) => MedicalPayment {
+                    patient: d.patient.clone(),
+                    provider: d.provider.clone(),
+                    kind: d.get_kind(),
+                    date: t.transaction_date.date_naive(),
+                    amount: t.amount,
+                    reimbursement: d.reimbursement,
+                },
+                // Without details, fall back to the memo as the provider
+                None => MedicalPayment {
+                    patient: medical_report::UNKNOWN_PATIENT.to_string(),
+                    provider: t.description.clone(),
+                    kind: MedicalExpenseKind::Treatment,
+                    date: t.transaction_date.date_naive(),
+                    amount: t.amount,
+                    reimbursement: 0,
+                },
+            })
+        })
+        .collect();
+
+    let entries = medical_report::group_entries(&payments);
+    let by_patient = medical_report::summarize_patients(&entries);
+
+    let total_paid: i64 = entries.iter().map(|e| e.total_paid).sum();
+    let total_reimbursement: i64 = entries.iter().map(|e| e.reimbursement).sum();
+    let net_medical_expense = total_paid - total_reimbursement;
+    let threshold = medical_report::threshold(params.total_income);
+
+    Ok(MedicalDeductionReportResponse {
+        year,
+        category_ids,
+        total_paid,
+        total_reimbursement,
+        net_medical_expense,
+        total_income: params.total_income,
+        threshold,
+        threshold_met: net_medical_expense > threshold,
+        deductible_amount: medical_report::deductible_amount(net_medical_expense, threshold),
+        by_patient,
+        entries,
+    })
+}
+
+/// Get medical expense deduction (医療費控除) report
+///
+/// GET /reports/medical-deduction/?year=2026&category_ids=5,6&total_income=4000000
+#[get("/medical-deduction/", name = "reports_medical_deduction")]
+pub async fn medical_deduction_report(
+    Query(params): Query<MedicalDeductionQuery>,
+) -> ViewResult<Response> {
+    let response = build_medical_deduction_report(&params).await?;
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Export the medical expense deduction report as e-Tax 医療費集計フォーム CSV
+///
+/// GET /reports/medical-deduction/csv/?year=2026&category_ids=5,6
+#[get("/medical-deduction/csv/", name = "reports_medical_deduction_csv")]
+pub async fn medical_deduction_csv(
+    Query(params): Query<MedicalDeductionQuery>,
+) -> ViewResult<Response> {
+    let report = build_medical_deduction_report(&params).await?;
+    let csv = medical_report::to_csv(&report.entries);
+
+    Ok(Response::new(StatusCode::OK)
+        .with_header("Content-Type", "text/csv; charset=utf-8")
+        .with_header(
+            "Content-Disposition",
+            &format!("attachment; filename=\"iryouhi_{}.csv\"", report.year),
+        )
+        .with_body(csv.into_bytes()))
+}
+
+/// Get assets, liabilities and net worth at the end of each interval
+///
+/// GET /reports/net-worth/?from=2025-01-01&to=2025-12-31&interval=month
+///
+/// Assets are positive account balances and valued assets; liabilities are
+/// negative balances (credit cards), loan principal still owed and valued
+/// liabilities. `items` breaks down the last point.
+#[get("/net-worth/", name = "reports_net_worth")]
+pub async fn net_worth_report(Query(params): Query<NetWorthQuery>) -> ViewResult<Response> {
+    let to = params.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let from = params.from.unwrap_or_else(|| {
+        to.checked_sub_months(Months::new(12))
+            .unwrap_or(NaiveDate::MIN)
+    });
+    if from > to {
+        return Ok(Response::new(StatusCode::BAD_REQUEST)
+            .with_body(br#"{"error": "from must not be after to"}"#.to_vec()));
+    }
+    if timeseries::bucket_count(from, to, params.interval) > MAX_NET_WORTH_POINTS as u64 {
+        return Ok(Response::new(StatusCode::BAD_REQUEST).with_body(
+            format!(
+                r#"{{"error": "The series is limited to {} points"}}"#,
+                MAX_NET_WORTH_POINTS
+            )
+            .into_bytes(),
+        ));
+    }
+    let dates = net_worth::period_ends(from, to, params.interval);
+
+    let holdings = Ledger::load().await?;
+    let points = dates
+        .iter()
+        .map(|&date| net_worth::point(date, &holdings.items_on(date)))
+        .collect();
+
+    let response = NetWorthReportResponse {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        interval: params.interval,
+        points,
+        items: holdings.items_on(to),
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
@@ -0,0 +1,15 @@
+//! Sync app for clients that keep a local copy of the data
+//!
+//! Every create, update and delete of a category or transaction appends an
+//! entry to a change log whose id is a monotonic cursor. Deletions stay in
+//! the log as tombstones, so `/api/sync/?since=<cursor>` can tell a client
+//! exactly what changed since its last sync.
+
+pub mod changes;
+pub mod models;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use models::*;
+pub use urls::url_patterns;
@@ -0,0 +1,67 @@
+//! Recording and reading the change log
+
+use chrono::Utc;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use std::collections::HashMap;
+
+use super::models::{ChangeAction, ChangeLogEntry, SyncEntity};
+
+/// Appends a change to the log
+///
+/// Called by the write views after the change has been saved.
+pub async fn record(entity: SyncEntity, object_id: i64, action: ChangeAction) -> ViewResult<()> {
+    let entry = ChangeLogEntry {
+        id: None,
+        entity: entity.to_string(),
+        object_id,
+        action: action.to_string(),
+        created_at: Utc::now(),
+    };
+    ChangeLogEntry::objects().create(&entry).await?;
+    Ok(())
+}
+
+/// Changes after a cursor, reduced to the latest action per object
+#[derive(Debug, Default)]
+pub struct ChangeSet {
+    /// Cursor of the newest change in the log (0 if the log is empty)
+    pub cursor: i64,
+    /// Latest action for each changed object
+    pub latest: HashMap<(SyncEntity, i64), ChangeAction>,
+}
+
+impl ChangeSet {
+    /// IDs of objects of `entity` whose latest change is `action`, ascending
+    pub fn ids(&self, entity: SyncEntity, action: ChangeAction) -> Vec<i64> {
+        let mut ids: Vec<i64> = self
+            .latest
+            .iter()
+            .filter(|((e, _), a)| *e == entity && **a == action)
+            .map(|((_, id), _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+/// Reads the changes made after `since`
+pub async fn since(since: i64) -> ViewResult<ChangeSet> {
+    let mut entries = ChangeLogEntry::objects().all().all().await?;
+    entries.sort_by_key(|e| e.id);
+
+    let mut changes = ChangeSet::default();
+    for entry in entries {
+        let Some(id) = entry.id else { continue };
+        changes.cursor = changes.cursor.max(id);
+        if id <= since {
+            continue;
+        }
+        if let Some(entity) = entry.get_entity() {
+            changes
+                .latest
+                .insert((entity, entry.object_id), entry.get_action());
+        }
+    }
+    Ok(changes)
+}
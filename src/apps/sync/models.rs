@@ -0,0 +1,101 @@
+//! Change log model definitions
+
+use chrono::{DateTime, Utc};
+use reinhardt::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Kind of object a change log entry refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncEntity {
+    Category,
+    Transaction,
+}
+
+impl std::fmt::Display for SyncEntity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncEntity::Category => write!(f, "category"),
+            SyncEntity::Transaction => write!(f, "transaction"),
+        }
+    }
+}
+
+impl std::str::FromStr for SyncEntity {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "category" => Ok(SyncEntity::Category),
+            "transaction" => Ok(SyncEntity::Transaction),
+            _ => Err(format!("Invalid sync entity: {}", s)),
+        }
+    }
+}
+
+/// What happened to the object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeAction {
+    /// Created or updated
+    Upsert,
+    /// Deleted (a tombstone)
+    Delete,
+}
+
+impl std::fmt::Display for ChangeAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChangeAction::Upsert => write!(f, "upsert"),
+            ChangeAction::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+impl std::str::FromStr for ChangeAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "upsert" => Ok(ChangeAction::Upsert),
+            "delete" => Ok(ChangeAction::Delete),
+            _ => Err(format!("Invalid change action: {}", s)),
+        }
+    }
+}
+
+/// One change to a category or transaction; the id is the sync cursor
+#[derive(Serialize, Deserialize)]
+#[model(app_label = "sync", table_name = "change_log")]
+pub struct ChangeLogEntry {
+    /// Unique identifier, increasing with every change
+    #[field(primary_key = true)]
+    pub id: Option<i64>,
+
+    /// Kind of object (category or transaction) - stored as string
+    #[field(max_length = 20)]
+    pub entity: String,
+
+    /// ID of the changed object
+    pub object_id: i64,
+
+    /// Upsert or delete - stored as string
+    #[field(max_length = 20)]
+    pub action: String,
+
+    /// Time of the change
+    #[field(auto_now_add = true)]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChangeLogEntry {
+    /// Gets the entity as enum
+    pub fn get_entity(&self) -> Option<SyncEntity> {
+        self.entity.parse().ok()
+    }
+
+    /// Gets the action as enum
+    pub fn get_action(&self) -> ChangeAction {
+        self.action.parse().unwrap_or(ChangeAction::Upsert)
+    }
+}
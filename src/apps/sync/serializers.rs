@@ -0,0 +1,26 @@
+//! Sync serializers for request/response handling
+
+use serde::{Deserialize, Serialize};
+
+use crate::apps::categories::serializers::CategoryResponse;
+use crate::apps::transactions::serializers::TransactionResponse;
+
+/// Objects of one kind changed since the client's cursor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncChanges<T> {
+    /// Created or updated objects, in their current state
+    pub updated: Vec<T>,
+    /// IDs of deleted objects
+    pub deleted: Vec<i64>,
+}
+
+/// Response of the delta sync endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncResponse {
+    /// Cursor to pass as `since` on the next sync
+    pub cursor: i64,
+    /// True if this is a full snapshot; the client should replace its copy
+    pub full: bool,
+    pub categories: SyncChanges<CategoryResponse>,
+    pub transactions: SyncChanges<TransactionResponse>,
+}
@@ -0,0 +1,10 @@
+//! URL routing for sync app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the sync app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new().endpoint(views::sync_changes)
+}
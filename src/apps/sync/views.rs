@@ -0,0 +1,79 @@
+//! Sync views (API endpoints)
+
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::{get, Query, Response, StatusCode};
+use serde::Deserialize;
+
+use crate::apps::categories::serializers::CategoryResponse;
+use crate::apps::categories::views::active_categories;
+use crate::apps::transactions::serializers::TransactionResponse;
+use crate::apps::transactions::views::active_transactions;
+use super::changes;
+use super::models::{ChangeAction, SyncEntity};
+use super::serializers::{SyncChanges, SyncResponse};
+
+/// Query parameters for delta sync
+#[derive(Debug, Deserialize)]
+pub struct SyncQuery {
+    /// Cursor returned by the previous sync; omit for a full snapshot
+    pub since: Option<i64>,
+}
+
+/// Changes to categories and transactions since a cursor
+///
+/// GET /sync/?since=42
+///
+/// Without `since` (or with a cursor the server does not know, e.g. after
+/// the database was reset) every category and transaction is returned with
+/// `full: true`.
+#[get("/", name = "sync_changes")]
+pub async fn sync_changes(Query(params): Query<SyncQuery>) -> ViewResult<Response> {
+    let since = params.since.unwrap_or(0);
+    let changes = changes::since(since).await?;
+    let full = since <= 0 || since > changes.cursor;
+
+    // Trashed rows are reported as deleted; restoring them records an upsert
+    let categories = active_categories().await?;
+    let transactions = active_transactions().await?;
+
+    let response = if full {
+        SyncResponse {
+            cursor: changes.cursor,
+            full,
+            categories: SyncChanges {
+                updated: categories.into_iter().map(CategoryResponse::from).collect(),
+                deleted: Vec::new(),
+            },
+            transactions: SyncChanges {
+                updated: transactions.into_iter().map(TransactionResponse::from).collect(),
+                deleted: Vec::new(),
+            },
+        }
+    } else {
+        let updated_categories = changes.ids(SyncEntity::Category, ChangeAction::Upsert);
+        let updated_transactions = changes.ids(SyncEntity::Transaction, ChangeAction::Upsert);
+        SyncResponse {
+            cursor: changes.cursor,
+            full,
+            categories: SyncChanges {
+                updated: categories
+                    .into_iter()
+                    .filter(|c| c.id.is_some_and(|id| updated_categories.contains(&id)))
+                    .map(CategoryResponse::from)
+                    .collect(),
+                deleted: changes.ids(SyncEntity::Category, ChangeAction::Delete),
+            },
+            transactions: SyncChanges {
+                updated: transactions
+                    .into_iter()
+                    .filter(|t| t.id.is_some_and(|id| updated_transactions.contains(&id)))
+                    .map(TransactionResponse::from)
+                    .collect(),
+                deleted: changes.ids(SyncEntity::Transaction, ChangeAction::Delete),
+            },
+        }
+    };
+
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
@@ -4,6 +4,7 @@
 
 pub mod models;
 pub mod serializers;
+pub mod suggest;
 pub mod views;
 pub mod urls;
 
@@ -4,36 +4,7 @@ use chrono::{DateTime, Utc};
 use reinhardt::prelude::*;
 use serde::{Deserialize, Serialize};
 
-/// Transaction type representing income or expense
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum TransactionType {
-    /// Income transaction (salary, bonus, etc.)
-    Income,
-    /// Expense transaction (food, transport, etc.)
-    Expense,
-}
-
-impl std::fmt::Display for TransactionType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            TransactionType::Income => write!(f, "income"),
-            TransactionType::Expense => write!(f, "expense"),
-        }
-    }
-}
-
-impl std::str::FromStr for TransactionType {
-    type Err = String;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s.to_lowercase().as_str() {
-            "income" => Ok(TransactionType::Income),
-            "expense" => Ok(TransactionType::Expense),
-            _ => Err(format!("Invalid transaction type: {}", s)),
-        }
-    }
-}
+pub use crate::shared::types::{ReconcileStatus, TransactionType};
 
 /// Transaction model for recording income and expenses
 #[derive(Serialize, Deserialize)]
@@ -60,6 +31,27 @@ pub struct Transaction {
     #[field(max_length = 20)]
     pub transaction_type: String,
 
+    /// Account the money moved in or out of (foreign key, optional)
+    #[field(null = true)]
+    pub account_id: Option<i64>,
+
+    /// Uncleared, cleared or reconciled - stored as string
+    #[field(max_length = 20)]
+    pub reconcile_status: String,
+
+    /// Reconciliation that reconciled this transaction
+    #[field(null = true)]
+    pub reconciliation_id: Option<i64>,
+
+    /// Client-generated key of the create request, so retried creates
+    /// (e.g. replayed from the offline queue) are not inserted twice
+    #[field(max_length = 100, null = true)]
+    pub idempotency_key: Option<String>,
+
+    /// When the transaction was moved to the trash (`None` if not deleted)
+    #[field(null = true)]
+    pub deleted_at: Option<DateTime<Utc>>,
+
     /// Creation timestamp
     #[field(auto_now_add = true)]
     pub created_at: DateTime<Utc>,
@@ -76,4 +68,27 @@ impl Transaction {
             .parse()
             .unwrap_or(TransactionType::Expense)
     }
+
+    /// Gets the reconcile status as enum
+    pub fn get_reconcile_status(&self) -> ReconcileStatus {
+        self.reconcile_status.parse().unwrap_or_default()
+    }
+
+    /// Whether the transaction is part of a finalized reconciliation
+    pub fn is_reconciled(&self) -> bool {
+        self.get_reconcile_status() == ReconcileStatus::Reconciled
+    }
+
+    /// Amount with income positive and expenses negative
+    pub fn signed_amount(&self) -> i64 {
+        match self.get_transaction_type() {
+            TransactionType::Income => self.amount,
+            TransactionType::Expense => -self.amount,
+        }
+    }
+
+    /// Whether the transaction is in the trash
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
 }
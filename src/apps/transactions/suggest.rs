@@ -0,0 +1,161 @@
+//! Category suggestion from transaction history
+//!
+//! A small multinomial naive Bayes classifier trained on the past
+//! `Transaction` rows. Descriptions are tokenized into lowercase words and,
+//! for Japanese text without spaces, character bigrams. The amount adds a
+//! single magnitude token so that "ランチ ¥800" and "ランチ会 ¥8,000" can
+//! lean towards different categories.
+
+use std::collections::{HashMap, HashSet};
+
+use super::models::Transaction;
+
+/// Naive Bayes model trained from transaction history
+#[derive(Debug, Default)]
+pub struct CategoryClassifier {
+    /// Number of training documents per category
+    doc_counts: HashMap<i64, usize>,
+    /// Token occurrence counts per category
+    token_counts: HashMap<i64, HashMap<String, usize>>,
+    /// Total token count per category
+    token_totals: HashMap<i64, usize>,
+    /// Every token seen during training
+    vocabulary: HashSet<String>,
+    /// Total number of training documents
+    total_docs: usize,
+}
+
+impl CategoryClassifier {
+    /// Trains a classifier from existing transactions
+    pub fn train(transactions: &[Transaction]) -> Self {
+        let mut classifier = Self::default();
+        for t in transactions {
+            classifier.add(t.category_id, &t.description, Some(t.amount));
+        }
+        classifier
+    }
+
+    /// Adds a single labelled example
+    pub fn add(&mut self, category_id: i64, description: &str, amount: Option<i64>) {
+        let tokens = tokenize(description, amount);
+        if tokens.is_empty() {
+            return;
+        }
+
+        *self.doc_counts.entry(category_id).or_insert(0) += 1;
+        self.total_docs += 1;
+
+        let counts = self.token_counts.entry(category_id).or_default();
+        for token in tokens {
+            *counts.entry(token.clone()).or_insert(0) += 1;
+            *self.token_totals.entry(category_id).or_insert(0) += 1;
+            self.vocabulary.insert(token);
+        }
+    }
+
+    /// Returns `(category_id, confidence)` pairs ordered by confidence
+    ///
+    /// Confidences are normalized over all known categories and sum to 1.
+    /// Returns an empty list when there is no history or the description
+    /// yields no tokens.
+    pub fn predict(&self, description: &str, amount: Option<i64>) -> Vec<(i64, f64)> {
+        let tokens = tokenize(description, amount);
+        if self.total_docs == 0 || tokens.is_empty() {
+            return Vec::new();
+        }
+
+        let vocab_size = self.vocabulary.len() as f64;
+        let mut scores: Vec<(i64, f64)> = self
+            .doc_counts
+            .iter()
+            .map(|(&category_id, &docs)| {
+                let prior = (docs as f64 / self.total_docs as f64).ln();
+                let counts = self.token_counts.get(&category_id);
+                let total = self.token_totals.get(&category_id).copied().unwrap_or(0) as f64;
+                let likelihood: f64 = tokens
+                    .iter()
+                    .map(|token| {
+                        let count = counts
+                            .and_then(|c| c.get(token))
+                            .copied()
+                            .unwrap_or(0) as f64;
+                        ((count + 1.0) / (total + vocab_size)).ln()
+                    })
+                    .sum();
+                (category_id, prior + likelihood)
+            })
+            .collect();
+
+        // Softmax over log scores to get comparable confidences
+        let max = scores
+            .iter()
+            .map(|(_, s)| *s)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let sum: f64 = scores.iter().map(|(_, s)| (s - max).exp()).sum();
+        for (_, score) in scores.iter_mut() {
+            *score = (*score - max).exp() / sum;
+        }
+
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        scores
+    }
+}
+
+/// Splits a description into classifier tokens
+fn tokenize(description: &str, amount: Option<i64>) -> Vec<String> {
+    let mut tokens = Vec::new();
+
+    for word in description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let word = word.to_lowercase();
+        let chars: Vec<char> = word.chars().collect();
+        if chars.iter().any(|c| !c.is_ascii()) && chars.len() > 1 {
+            // No word boundaries in Japanese, so use character bigrams
+            for pair in chars.windows(2) {
+                tokens.push(pair.iter().collect());
+            }
+        } else {
+            tokens.push(word);
+        }
+    }
+
+    if let Some(amount) = amount.filter(|a| *a > 0) {
+        tokens.push(format!("__amount_{}", amount.ilog10()));
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_japanese_bigrams() {
+        let tokens = tokenize("コンビニ", None);
+        assert_eq!(tokens, vec!["コン", "ンビ", "ビニ"]);
+    }
+
+    #[test]
+    fn test_predict_ranks_matching_category_first() {
+        let mut classifier = CategoryClassifier::default();
+        classifier.add(1, "ランチ 定食", Some(900));
+        classifier.add(1, "ランチ パスタ", Some(1200));
+        classifier.add(2, "電車 定期券", Some(15000));
+        classifier.add(2, "電車 切符", Some(300));
+
+        let ranked = classifier.predict("ランチ", Some(1000));
+        assert_eq!(ranked[0].0, 1);
+        assert!(ranked[0].1 > ranked[1].1);
+        let total: f64 = ranked.iter().map(|(_, c)| c).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_predict_without_history_is_empty() {
+        let classifier = CategoryClassifier::default();
+        assert!(classifier.predict("ランチ", None).is_empty());
+    }
+}
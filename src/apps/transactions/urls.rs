@@ -3,13 +3,28 @@
 use reinhardt::ServerRouter;
 
 use super::views;
+use crate::apps::attachments::views as attachment_views;
+use crate::apps::audit::views as audit_views;
+use crate::apps::installments::views as installment_views;
 
 /// Returns URL patterns for the transactions app
 pub fn url_patterns() -> ServerRouter {
     ServerRouter::new()
         .endpoint(views::list_transactions)
+        .endpoint(views::suggest_category)
         .endpoint(views::get_transaction)
         .endpoint(views::create_transaction_view)
         .endpoint(views::update_transaction_view)
         .endpoint(views::delete_transaction_view)
+        // Attachments nested under a transaction
+        .endpoint(attachment_views::list_attachments)
+        .endpoint(attachment_views::upload_attachment)
+        .endpoint(attachment_views::download_attachment)
+        .endpoint(attachment_views::delete_attachment)
+        // Installment plan of a purchase
+        .endpoint(installment_views::get_plan)
+        .endpoint(installment_views::create_plan_view)
+        .endpoint(installment_views::delete_plan_view)
+        // Change history from the audit log
+        .endpoint(audit_views::transaction_history)
 }
@@ -4,33 +4,166 @@ use chrono::Utc;
 use reinhardt::core::serde::json;
 use reinhardt::http::ViewResult;
 use reinhardt::Model;
-use reinhardt::{delete, get, post, put, Json, Path, Response, StatusCode};
+use reinhardt::{delete, get, post, put, Json, Path, Query, Request, Response, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
 use validator::Validate;
 
-use super::models::Transaction;
+use crate::apps::categories::models::{Category, CategoryType};
+use crate::apps::categories::views::active_categories;
+use crate::apps::accounts::views::account_exists;
+use crate::apps::audit::{self, AuditAction, AuditEntity};
+use crate::apps::idempotency::{idempotency_key, idempotent};
+use crate::apps::installments::views::has_plan;
+use crate::apps::loans::views::is_repayment;
+use crate::apps::periods::reject_if_locked;
+use crate::apps::sync::changes;
+use crate::apps::sync::models::{ChangeAction, SyncEntity};
+use super::models::{ReconcileStatus, Transaction, TransactionType};
 use super::serializers::{
-    CreateTransactionRequest, TransactionListResponse, TransactionResponse, UpdateTransactionRequest,
+    CategorySuggestion, CategorySuggestionResponse, CreateTransactionRequest,
+    TransactionListResponse, TransactionResponse, UpdateTransactionRequest,
 };
+use super::suggest::CategoryClassifier;
+
+/// Default number of suggestions returned by the suggest endpoint
+const DEFAULT_SUGGESTION_LIMIT: usize = 5;
+
+/// Query parameters for category suggestion
+#[derive(Debug, Deserialize)]
+pub struct SuggestCategoryQuery {
+    pub description: String,
+    pub amount: Option<i64>,
+    /// Restrict suggestions to categories of this type
+    pub transaction_type: Option<TransactionType>,
+    pub limit: Option<usize>,
+}
+
+fn account_not_found(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::BAD_REQUEST).with_body(
+        format!(r#"{{"error": "Account with id {} not found"}}"#, id).into_bytes(),
+    ))
+}
+
+fn category_not_found(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::BAD_REQUEST).with_body(
+        format!(r#"{{"error": "Category with id {} not found"}}"#, id).into_bytes(),
+    ))
+}
+
+/// Whether the category exists and is not in the trash
+async fn category_exists(id: i64) -> ViewResult<bool> {
+    let categories = active_categories().await?;
+    Ok(categories.iter().any(|c| c.id == Some(id)))
+}
+
+fn installment_conflict(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::CONFLICT).with_body(
+        format!(
+            r#"{{"error": "Transaction {} is paid in installments; cancel the plan to change its amount or account"}}"#,
+            id
+        )
+        .into_bytes(),
+    ))
+}
+
+fn repayment_conflict(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::CONFLICT).with_body(
+        format!(
+            r#"{{"error": "Transaction {} is a loan repayment; unlink it to change its amount or date"}}"#,
+            id
+        )
+        .into_bytes(),
+    ))
+}
+
+fn reconciled_conflict(id: i64) -> ViewResult<Response> {
+    Ok(Response::new(StatusCode::CONFLICT).with_body(
+        format!(
+            r#"{{"error": "Transaction {} is reconciled; only its category and description can be changed"}}"#,
+            id
+        )
+        .into_bytes(),
+    ))
+}
+
+/// Fetches all transactions that are not in the trash
+///
+/// Every list and report should use this instead of `objects().all()`.
+pub async fn active_transactions() -> ViewResult<Vec<Transaction>> {
+    let transactions = Transaction::objects().all().all().await?;
+    Ok(transactions
+        .into_iter()
+        .filter(|t| !t.is_deleted())
+        .collect())
+}
 
 /// List all transactions
 ///
 /// GET /transactions/
 #[get("/", name = "transactions_list")]
 pub async fn list_transactions() -> ViewResult<Response> {
-    let manager = Transaction::objects();
-    let transactions = manager.all().all().await?;
+    let transactions = active_transactions().await?;
 
     let response = TransactionListResponse::new(transactions);
     Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
 }
 
+/// Suggest categories for a new transaction from past transactions
+///
+/// GET /transactions/suggest-category/?description=ランチ&amount=900
+#[get("/suggest-category/", name = "transactions_suggest_category")]
+pub async fn suggest_category(Query(params): Query<SuggestCategoryQuery>) -> ViewResult<Response> {
+    let transactions = active_transactions().await?;
+    let categories = active_categories().await?;
+
+    let wanted_type = params.transaction_type.map(|t| match t {
+        TransactionType::Income => CategoryType::Income,
+        TransactionType::Expense => CategoryType::Expense,
+    });
+
+    // Create category lookup, dropping categories of the wrong type
+    let mut category_lookup: HashMap<i64, Category> = categories
+        .into_iter()
+        .filter(|c| wanted_type.is_none_or(|t| c.get_category_type() == t))
+        .filter_map(|c| c.id.map(|id| (id, c)))
+        .collect();
+
+    let history: Vec<Transaction> = transactions
+        .into_iter()
+        .filter(|t| category_lookup.contains_key(&t.category_id))
+        .collect();
+
+    let classifier = CategoryClassifier::train(&history);
+    let suggestions: Vec<CategorySuggestion> = classifier
+        .predict(&params.description, params.amount)
+        .into_iter()
+        .filter_map(|(category_id, confidence)| {
+            category_lookup
+                .remove(&category_id)
+                .map(|category| CategorySuggestion {
+                    category: category.into(),
+                    confidence,
+                })
+        })
+        .take(params.limit.unwrap_or(DEFAULT_SUGGESTION_LIMIT))
+        .collect();
+
+    let response = CategorySuggestionResponse {
+        description: params.description,
+        amount: params.amount,
+        suggestions,
+    };
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
 /// Get a single transaction by ID
 ///
 /// GET /transactions/{id}/
 #[get("/{id}/", name = "transactions_get")]
 pub async fn get_transaction(Path(id): Path<i64>) -> ViewResult<Response> {
     let manager = Transaction::objects();
-    match manager.get(id).first().await? {
+    match manager.get(id).first().await?.filter(|t| !t.is_deleted()) {
         Some(transaction) => {
             let response: TransactionResponse = transaction.into();
             Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
@@ -44,13 +177,87 @@ pub async fn get_transaction(Path(id): Path<i64>) -> ViewResult<Response> {
 /// Create a new transaction
 ///
 /// POST /transactions/
+///
+/// Besides the stored-response replay of [`idempotent`], the
+/// `Idempotency-Key` is saved on the transaction itself, so a retry after
+/// the retention window still returns the existing transaction (with 200)
+/// instead of inserting a duplicate. A key reused with a different body is
+/// rejected with 422.
 #[post("/", name = "transactions_create")]
 pub async fn create_transaction_view(
+    request: Request,
     Json(create_req): Json<CreateTransactionRequest>,
+) -> ViewResult<Response> {
+    let idempotency_key = idempotency_key(&request).map(str::to_string);
+    let actor = audit::actor(&request);
+    idempotent(
+        &request,
+        create_transaction(create_req, idempotency_key, actor),
+    )
+    .await
+}
+
+/// Whether `transaction` holds what `create_req` asks to create
+fn created_from(transaction: &Transaction, create_req: &CreateTransactionRequest) -> bool {
+    transaction.amount == create_req.amount
+        && transaction.category_id == create_req.category_id
+        && transaction.description == create_req.description
+        // The database keeps microseconds
+        && transaction.transaction_date.timestamp_micros()
+            == create_req.transaction_date.timestamp_micros()
+        && transaction.get_transaction_type() == create_req.transaction_type
+        && transaction.account_id == create_req.account_id
+}
+
+async fn create_transaction(
+    create_req: CreateTransactionRequest,
+    idempotency_key: Option<String>,
+    actor: String,
 ) -> ViewResult<Response> {
     // Validate request
     create_req.validate()?;
 
+    let manager = Transaction::objects();
+
+    // A retry of a create that already succeeded. The key stays on a
+    // trashed transaction (the column is unique), which is not handed back
+    if let Some(key) = &idempotency_key {
+        let existing = manager
+            .all()
+            .all()
+            .await?
+            .into_iter()
+            .find(|t| t.idempotency_key.as_deref() == Some(key));
+        if let Some(existing) = existing {
+            if existing.is_deleted() {
+                return Ok(Response::new(StatusCode::CONFLICT).with_body(
+                    br#"{"error": "The transaction created with this Idempotency-Key is in the trash"}"#
+                        .to_vec(),
+                ));
+            }
+            if !created_from(&existing, &create_req) {
+                return Ok(Response::new(StatusCode::UNPROCESSABLE_ENTITY).with_body(
+                    br#"{"error": "Idempotency-Key was already used for a different request"}"#
+                        .to_vec(),
+                ));
+            }
+            let response: TransactionResponse = existing.into();
+            return Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?));
+        }
+    }
+
+    if let Some(locked) = reject_if_locked(&[create_req.transaction_date]).await? {
+        return Ok(locked);
+    }
+    if !category_exists(create_req.category_id).await? {
+        return category_not_found(create_req.category_id);
+    }
+    if let Some(account_id) = create_req.account_id
+        && !account_exists(account_id).await?
+    {
+        return account_not_found(account_id);
+    }
+
     // Create transaction
     let now = Utc::now();
     let transaction = Transaction {
@@ -60,12 +267,29 @@ pub async fn create_transaction_view(
         description: create_req.description,
         transaction_date: create_req.transaction_date,
         transaction_type: create_req.transaction_type.to_string(),
+        account_id: create_req.account_id,
+        reconcile_status: ReconcileStatus::Uncleared.to_string(),
+        reconciliation_id: None,
+        idempotency_key,
+        deleted_at: None,
         created_at: now,
         updated_at: now,
     };
 
-    let manager = Transaction::objects();
     let created = manager.create(&transaction).await?;
+    if let Some(id) = created.id {
+        changes::record(SyncEntity::Transaction, id, ChangeAction::Upsert).await?;
+        let new = audit::snapshot(&created);
+        audit::record(
+            AuditEntity::Transaction,
+            id,
+            AuditAction::Create,
+            &actor,
+            None,
+            Some(&new),
+        )
+        .await?;
+    }
 
     let response: TransactionResponse = created.into();
     Ok(Response::new(StatusCode::CREATED).with_body(json::to_vec(&response)?))
@@ -74,17 +298,67 @@ pub async fn create_transaction_view(
 /// Update an existing transaction
 ///
 /// PUT /transactions/{id}/
+///
+/// `cleared` ticks the transaction off against the bank statement. Once
+/// reconciled, only the category and description can be changed.
 #[put("/{id}/", name = "transactions_update")]
 pub async fn update_transaction_view(
+    request: Request,
     Path(id): Path<i64>,
     Json(update_req): Json<UpdateTransactionRequest>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, update_transaction(id, update_req, actor)).await
+}
+
+async fn update_transaction(
+    id: i64,
+    update_req: UpdateTransactionRequest,
+    actor: String,
 ) -> ViewResult<Response> {
     // Validate request
     update_req.validate()?;
 
     let manager = Transaction::objects();
-    match manager.get(id).first().await? {
+    match manager.get(id).first().await?.filter(|t| !t.is_deleted()) {
         Some(mut transaction) => {
+            // Neither moving out of nor into a closed period is allowed
+            let mut dates = vec![transaction.transaction_date];
+            dates.extend(update_req.transaction_date);
+            if let Some(locked) = reject_if_locked(&dates).await? {
+                return Ok(locked);
+            }
+            if transaction.is_reconciled()
+                && (update_req.amount.is_some()
+                    || update_req.transaction_date.is_some()
+                    || update_req.account_id.is_some()
+                    || update_req.cleared.is_some())
+            {
+                return reconciled_conflict(id);
+            }
+            if (update_req.amount.is_some() || update_req.account_id.is_some())
+                && has_plan(id).await?
+            {
+                return installment_conflict(id);
+            }
+            if (update_req.amount.is_some() || update_req.transaction_date.is_some())
+                && is_repayment(id).await?
+            {
+                return repayment_conflict(id);
+            }
+            if let Some(category_id) = update_req.category_id
+                && !category_exists(category_id).await?
+            {
+                return category_not_found(category_id);
+            }
+            if let Some(account_id) = update_req.account_id
+                && !account_exists(account_id).await?
+            {
+                return account_not_found(account_id);
+            }
+
+            let old = audit::snapshot(&transaction);
+
             // Apply updates
             if let Some(a) = update_req.amount {
                 transaction.amount = a;
@@ -98,9 +372,31 @@ pub async fn update_transaction_view(
             if let Some(date) = update_req.transaction_date {
                 transaction.transaction_date = date;
             }
+            if let Some(account_id) = update_req.account_id {
+                transaction.account_id = Some(account_id);
+            }
+            if let Some(cleared) = update_req.cleared {
+                let status = if cleared {
+                    ReconcileStatus::Cleared
+                } else {
+                    ReconcileStatus::Uncleared
+                };
+                transaction.reconcile_status = status.to_string();
+            }
             transaction.updated_at = Utc::now();
 
             let updated = manager.update(&transaction).await?;
+            changes::record(SyncEntity::Transaction, id, ChangeAction::Upsert).await?;
+            let new = audit::snapshot(&updated);
+            audit::record(
+                AuditEntity::Transaction,
+                id,
+                AuditAction::Update,
+                &actor,
+                Some(&old),
+                Some(&new),
+            )
+            .await?;
             let response: TransactionResponse = updated.into();
             Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
         }
@@ -110,15 +406,49 @@ pub async fn update_transaction_view(
     }
 }
 
-/// Delete a transaction
+/// Move a transaction to the trash
 ///
 /// DELETE /transactions/{id}/
+///
+/// The transaction and its attachments, medical expense and donation
+/// details are kept until it is purged from the trash (see the trash app).
 #[delete("/{id}/", name = "transactions_delete")]
-pub async fn delete_transaction_view(Path(id): Path<i64>) -> ViewResult<Response> {
+pub async fn delete_transaction_view(
+    request: Request,
+    Path(id): Path<i64>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, delete_transaction(id, actor)).await
+}
+
+async fn delete_transaction(id: i64, actor: String) -> ViewResult<Response> {
     let manager = Transaction::objects();
-    match manager.delete(id).await {
-        Ok(_) => Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new())),
-        Err(_) => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
+    match manager.get(id).first().await?.filter(|t| !t.is_deleted()) {
+        Some(mut transaction) => {
+            if let Some(locked) = reject_if_locked(&[transaction.transaction_date]).await? {
+                return Ok(locked);
+            }
+            if transaction.is_reconciled() {
+                return reconciled_conflict(id);
+            }
+
+            let old = audit::snapshot(&transaction);
+            transaction.deleted_at = Some(Utc::now());
+            let deleted = manager.update(&transaction).await?;
+            changes::record(SyncEntity::Transaction, id, ChangeAction::Delete).await?;
+            let new = audit::snapshot(&deleted);
+            audit::record(
+                AuditEntity::Transaction,
+                id,
+                AuditAction::Delete,
+                &actor,
+                Some(&old),
+                Some(&new),
+            )
+            .await?;
+            Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+        }
+        None => Ok(Response::new(StatusCode::NOT_FOUND).with_body(
             format!(r#"{{"error": "Transaction with id {} not found"}}"#, id).into_bytes(),
         )),
     }
@@ -0,0 +1,13 @@
+//! Trash app for soft-deleted categories and transactions
+//!
+//! Deleting a category or transaction only sets its `deleted_at`; it stays
+//! here until it is restored or purged, either through the API or with
+//! `manage purge_trash --older-than 30d`.
+
+pub mod purge;
+pub mod serializers;
+pub mod views;
+pub mod urls;
+
+pub use serializers::*;
+pub use urls::url_patterns;
@@ -0,0 +1,143 @@
+//! Permanent deletion of trashed categories and transactions
+
+use chrono::{DateTime, Duration, Utc};
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+
+use crate::apps::audit::{self, AuditAction, AuditEntity};
+use crate::apps::categories::models::Category;
+use crate::apps::transactions::models::Transaction;
+use super::serializers::PurgeResponse;
+
+/// Parses an age such as `30d`, `12h` or `2w`
+pub fn parse_age(age: &str) -> Result<Duration, String> {
+    let age = age.trim();
+    let invalid = || format!("Invalid age '{}': expected e.g. 30d, 12h or 2w", age);
+    let unit = age.chars().last().ok_or_else(invalid)?;
+    let count: u32 = age[..age.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    let count = i64::from(count);
+    let duration = match unit {
+        'h' => Duration::try_hours(count),
+        'd' => Duration::try_days(count),
+        'w' => Duration::try_weeks(count),
+        _ => return Err(invalid()),
+    };
+    duration.ok_or_else(invalid)
+}
+
+/// Returns the moment `age` before `now`, or an error when that is
+/// earlier than any representable date
+pub fn cutoff(now: DateTime<Utc>, age: Duration) -> Result<DateTime<Utc>, String> {
+    now.checked_sub_signed(age)
+        .ok_or_else(|| "Age is too large".to_string())
+}
+
+/// Deletes a trashed transaction together with its attachments, medical
+/// expense and donation details
+pub async fn purge_transaction(id: i64) -> ViewResult<()> {
+    Transaction::objects().delete(id).await?;
+    // Drop the transaction's attachments and any files left unused
+    crate::apps::attachments::storage::delete_for_transaction(id).await?;
+    crate::apps::medical::views::delete_for_transaction(id).await?;
+    crate::apps::donations::views::delete_for_transaction(id).await?;
+    crate::apps::installments::views::delete_for_transaction(id).await?;
+    crate::apps::loans::views::delete_for_transaction(id).await?;
+    crate::apps::goals::views::delete_for_transaction(id).await?;
+    Ok(())
+}
+
+/// Whether any transaction, trashed or not, still uses the category
+pub async fn category_in_use(id: i64) -> ViewResult<bool> {
+    let transactions = Transaction::objects().all().all().await?;
+    Ok(transactions.iter().any(|t| t.category_id == id))
+}
+
+/// Purges everything moved to the trash before `cutoff` (or all of it)
+///
+/// Transactions go first so that categories whose transactions were all
+/// trashed can go too; categories still in use are kept. Each purge is
+/// audited under `actor`. Attachment files no longer referenced, including
+/// ones kept back by earlier deletes, are swept at the end.
+pub async fn purge_deleted_before(
+    cutoff: Option<DateTime<Utc>>,
+    actor: &str,
+) -> ViewResult<PurgeResponse> {
+    let expired = |deleted_at: Option<DateTime<Utc>>| {
+        deleted_at.is_some_and(|d| cutoff.is_none_or(|cutoff| d < cutoff))
+    };
+    let mut purged = PurgeResponse::default();
+
+    for transaction in Transaction::objects().all().all().await? {
+        if expired(transaction.deleted_at)
+            && let Some(id) = transaction.id
+        {
+            purge_transaction(id).await?;
+            let old = audit::snapshot(&transaction);
+            audit::record(
+                AuditEntity::Transaction,
+                id,
+                AuditAction::Purge,
+                actor,
+                Some(&old),
+                None,
+            )
+            .await?;
+            purged.transactions += 1;
+        }
+    }
+
+    let manager = Category::objects();
+    for category in manager.all().all().await? {
+        if expired(category.deleted_at)
+            && let Some(id) = category.id
+            && !category_in_use(id).await?
+        {
+            manager.delete(id).await?;
+            let old = audit::snapshot(&category);
+            audit::record(
+                AuditEntity::Category,
+                id,
+                AuditAction::Purge,
+                actor,
+                Some(&old),
+                None,
+            )
+            .await?;
+            purged.categories += 1;
+        }
+    }
+
+    crate::apps::attachments::storage::sweep_orphans().await?;
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d"), Ok(Duration::days(30)));
+        assert_eq!(parse_age("12h"), Ok(Duration::hours(12)));
+        assert_eq!(parse_age("2w"), Ok(Duration::weeks(2)));
+        assert!(parse_age("").is_err());
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("-1d").is_err());
+        assert!(parse_age("30m").is_err());
+    }
+
+    #[test]
+    fn test_cutoff_overflow() {
+        let now = Utc::now();
+        assert_eq!(
+            cutoff(now, Duration::days(30)),
+            Ok(now - Duration::days(30))
+        );
+
+        let age = parse_age("4294967295w").unwrap();
+        assert!(cutoff(now, age).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+//! Trash serializers for request/response handling
+
+use serde::{Deserialize, Serialize};
+
+use crate::apps::categories::models::Category;
+use crate::apps::categories::serializers::CategoryResponse;
+use crate::apps::transactions::models::Transaction;
+use crate::apps::transactions::serializers::TransactionResponse;
+
+/// An object in the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedItem<T> {
+    #[serde(flatten)]
+    pub item: T,
+    /// When the object was moved to the trash (RFC 3339)
+    pub deleted_at: String,
+}
+
+/// Contents of the trash, most recently deleted first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashResponse {
+    pub categories: Vec<TrashedItem<CategoryResponse>>,
+    pub transactions: Vec<TrashedItem<TransactionResponse>>,
+}
+
+/// Number of objects permanently deleted from the trash
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PurgeResponse {
+    pub categories: usize,
+    pub transactions: usize,
+}
+
+impl TrashResponse {
+    /// Builds the response from trashed rows; rows not in the trash are skipped
+    pub fn new(categories: Vec<Category>, transactions: Vec<Transaction>) -> Self {
+        let mut categories: Vec<TrashedItem<CategoryResponse>> = categories
+            .into_iter()
+            .filter_map(|c| {
+                let deleted_at = c.deleted_at?.to_rfc3339();
+                Some(TrashedItem {
+                    item: c.into(),
+                    deleted_at,
+                })
+            })
+            .collect();
+        let mut transactions: Vec<TrashedItem<TransactionResponse>> = transactions
+            .into_iter()
+            .filter_map(|t| {
+                let deleted_at = t.deleted_at?.to_rfc3339();
+                Some(TrashedItem {
+                    item: t.into(),
+                    deleted_at,
+                })
+            })
+            .collect();
+
+        // RFC 3339 strings in UTC sort chronologically
+        categories.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        transactions.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        Self {
+            categories,
+            transactions,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! URL routing for trash app
+
+use reinhardt::ServerRouter;
+
+use super::views;
+
+/// Returns URL patterns for the trash app
+pub fn url_patterns() -> ServerRouter {
+    ServerRouter::new()
+        .endpoint(views::list_trash)
+        .endpoint(views::empty_trash_view)
+        .endpoint(views::restore_category_view)
+        .endpoint(views::purge_category_view)
+        .endpoint(views::restore_transaction_view)
+        .endpoint(views::purge_transaction_view)
+}
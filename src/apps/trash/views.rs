@@ -0,0 +1,236 @@
+//! Trash views (API endpoints)
+
+use chrono::Utc;
+use reinhardt::core::serde::json;
+use reinhardt::http::ViewResult;
+use reinhardt::Model;
+use reinhardt::{delete, get, post, Path, Query, Request, Response, StatusCode};
+use serde::Deserialize;
+
+use crate::apps::audit::{self, AuditAction, AuditEntity};
+use crate::apps::categories::models::Category;
+use crate::apps::categories::serializers::CategoryResponse;
+use crate::apps::errors::error_response;
+use crate::apps::idempotency::idempotent;
+use crate::apps::periods::reject_if_locked;
+use crate::apps::sync::changes;
+use crate::apps::sync::models::{ChangeAction, SyncEntity};
+use crate::apps::transactions::models::Transaction;
+use crate::apps::transactions::serializers::TransactionResponse;
+use super::purge::{self, parse_age};
+use super::serializers::TrashResponse;
+
+/// Query parameters for emptying the trash
+#[derive(Debug, Deserialize)]
+pub struct EmptyTrashQuery {
+    /// Only purge items trashed longer ago than this (e.g. `30d`)
+    pub older_than: Option<String>,
+}
+
+async fn trashed_category(id: i64) -> ViewResult<Option<Category>> {
+    let category = Category::objects().get(id).first().await?;
+    Ok(category.filter(|c| c.is_deleted()))
+}
+
+async fn trashed_transaction(id: i64) -> ViewResult<Option<Transaction>> {
+    let transaction = Transaction::objects().get(id).first().await?;
+    Ok(transaction.filter(|t| t.is_deleted()))
+}
+
+fn not_in_trash(kind: &str, id: i64) -> ViewResult<Response> {
+    error_response(
+        StatusCode::NOT_FOUND,
+        &format!("{} with id {} is not in the trash", kind, id),
+    )
+}
+
+/// List trashed categories and transactions
+///
+/// GET /trash/
+#[get("/", name = "trash_list")]
+pub async fn list_trash() -> ViewResult<Response> {
+    let categories = Category::objects().all().all().await?;
+    let transactions = Transaction::objects().all().all().await?;
+
+    let response = TrashResponse::new(categories, transactions);
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Permanently delete everything in the trash
+///
+/// DELETE /trash/?older_than=30d
+///
+/// Categories still used by a transaction are kept.
+#[delete("/", name = "trash_empty")]
+pub async fn empty_trash_view(
+    request: Request,
+    Query(params): Query<EmptyTrashQuery>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, empty_trash(params, actor)).await
+}
+
+async fn empty_trash(params: EmptyTrashQuery, actor: String) -> ViewResult<Response> {
+    let cutoff = match params.older_than.as_deref().map(parse_age) {
+        Some(Ok(age)) => match purge::cutoff(Utc::now(), age) {
+            Ok(cutoff) => Some(cutoff),
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, &e),
+        },
+        Some(Err(e)) => return error_response(StatusCode::BAD_REQUEST, &e),
+        None => None,
+    };
+
+    let purged = purge::purge_deleted_before(cutoff, &actor).await?;
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&purged)?))
+}
+
+/// Restore a category from the trash
+///
+/// POST /trash/categories/{id}/restore/
+#[post("/categories/{id}/restore/", name = "trash_restore_category")]
+pub async fn restore_category_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, restore_category(id, actor)).await
+}
+
+async fn restore_category(id: i64, actor: String) -> ViewResult<Response> {
+    let Some(mut category) = trashed_category(id).await? else {
+        return not_in_trash("Category", id);
+    };
+
+    let old = audit::snapshot(&category);
+    category.deleted_at = None;
+    let restored = Category::objects().update(&category).await?;
+    changes::record(SyncEntity::Category, id, ChangeAction::Upsert).await?;
+    let new = audit::snapshot(&restored);
+    audit::record(
+        AuditEntity::Category,
+        id,
+        AuditAction::Restore,
+        &actor,
+        Some(&old),
+        Some(&new),
+    )
+    .await?;
+
+    let response: CategoryResponse = restored.into();
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Permanently delete a trashed category
+///
+/// DELETE /trash/categories/{id}/
+#[delete("/categories/{id}/", name = "trash_purge_category")]
+pub async fn purge_category_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, purge_category(id, actor)).await
+}
+
+async fn purge_category(id: i64, actor: String) -> ViewResult<Response> {
+    let Some(category) = trashed_category(id).await? else {
+        return not_in_trash("Category", id);
+    };
+    if purge::category_in_use(id).await? {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!("Category {} is still used by transactions", id),
+        );
+    }
+
+    Category::objects().delete(id).await?;
+    let old = audit::snapshot(&category);
+    audit::record(
+        AuditEntity::Category,
+        id,
+        AuditAction::Purge,
+        &actor,
+        Some(&old),
+        None,
+    )
+    .await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
+
+/// Restore a transaction from the trash
+///
+/// POST /trash/transactions/{id}/restore/
+///
+/// Fails with 409 while the transaction's category is in the trash or its
+/// date is in a closed period.
+#[post("/transactions/{id}/restore/", name = "trash_restore_transaction")]
+pub async fn restore_transaction_view(
+    request: Request,
+    Path(id): Path<i64>,
+) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, restore_transaction(id, actor)).await
+}
+
+async fn restore_transaction(id: i64, actor: String) -> ViewResult<Response> {
+    let Some(mut transaction) = trashed_transaction(id).await? else {
+        return not_in_trash("Transaction", id);
+    };
+    if trashed_category(transaction.category_id).await?.is_some() {
+        return error_response(
+            StatusCode::CONFLICT,
+            &format!(
+                "Category {} is in the trash; restore it first",
+                transaction.category_id
+            ),
+        );
+    }
+    if let Some(locked) = reject_if_locked(&[transaction.transaction_date]).await? {
+        return Ok(locked);
+    }
+
+    let old = audit::snapshot(&transaction);
+    transaction.deleted_at = None;
+    transaction.updated_at = Utc::now();
+    let restored = Transaction::objects().update(&transaction).await?;
+    changes::record(SyncEntity::Transaction, id, ChangeAction::Upsert).await?;
+    let new = audit::snapshot(&restored);
+    audit::record(
+        AuditEntity::Transaction,
+        id,
+        AuditAction::Restore,
+        &actor,
+        Some(&old),
+        Some(&new),
+    )
+    .await?;
+
+    let response: TransactionResponse = restored.into();
+    Ok(Response::new(StatusCode::OK).with_body(json::to_vec(&response)?))
+}
+
+/// Permanently delete a trashed transaction and its attachments, medical
+/// expense and donation details
+///
+/// DELETE /trash/transactions/{id}/
+///
+/// Allowed in closed periods, since trashed transactions no longer count
+/// towards any total.
+#[delete("/transactions/{id}/", name = "trash_purge_transaction")]
+pub async fn purge_transaction_view(request: Request, Path(id): Path<i64>) -> ViewResult<Response> {
+    let actor = audit::actor(&request);
+    idempotent(&request, purge_transaction(id, actor)).await
+}
+
+async fn purge_transaction(id: i64, actor: String) -> ViewResult<Response> {
+    let Some(transaction) = trashed_transaction(id).await? else {
+        return not_in_trash("Transaction", id);
+    };
+
+    purge::purge_transaction(id).await?;
+    let old = audit::snapshot(&transaction);
+    audit::record(
+        AuditEntity::Transaction,
+        id,
+        AuditAction::Purge,
+        &actor,
+        Some(&old),
+        None,
+    )
+    .await?;
+    Ok(Response::new(StatusCode::NO_CONTENT).with_body(Vec::new()))
+}
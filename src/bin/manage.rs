@@ -1,11 +1,150 @@
 //! Reinhardt Project Management CLI for kakeboor
 //!
 //! This is the project-specific management command interface (equivalent to Django's manage.py).
+//!
+//! Project commands handled here before falling back to Reinhardt's:
+//! - `purge_trash [--older-than 30d]`: permanently delete trashed categories
+//!   and transactions (default age 30d) and sweep unreferenced attachment
+//!   files (run periodically, e.g. from cron)
+//! - `import_prices <file or directory>...`: import security prices from
+//!   `code,date,price` CSV files (every `*.csv` of a directory)
+//! - `settle_card_payments`: record the payments of card statements whose
+//!   payment date has come (run daily, e.g. from cron)
 
+use chrono::Utc;
+use kakeboor::apps::cards::settle_card_payments as settle;
+use kakeboor::apps::investments::prices::{import_rows, parse_csv};
+use kakeboor::apps::trash::purge::{cutoff, parse_age, purge_deleted_before};
+use kakeboor::config::settings::DATABASE_URL;
 use reinhardt::commands::execute_from_command_line;
 use reinhardt::core::tokio;
+use reinhardt::db::orm::reinitialize_database;
+use std::path::{Path, PathBuf};
 use std::process;
 
+/// Trash age purged when `--older-than` is not given
+const DEFAULT_PURGE_AGE: &str = "30d";
+
+/// Actor recorded in the audit log for purges made by this command
+const PURGE_ACTOR: &str = "manage purge_trash";
+
+/// Runs `purge_trash` with the arguments after the command name
+async fn purge_trash(args: &[String]) -> Result<(), String> {
+    let mut older_than = DEFAULT_PURGE_AGE.to_string();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(value) = arg.strip_prefix("--older-than=") {
+            older_than = value.to_string();
+        } else if arg == "--older-than" {
+            older_than = args
+                .next()
+                .ok_or("--older-than needs a value, e.g. 30d")?
+                .clone();
+        } else {
+            return Err(format!("Unknown argument for purge_trash: {}", arg));
+        }
+    }
+    let cutoff = cutoff(Utc::now(), parse_age(&older_than)?)?;
+
+    reinitialize_database(DATABASE_URL)
+        .await
+        .map_err(|e| format!("Failed to initialize database: {}", e))?;
+    let purged = purge_deleted_before(Some(cutoff), PURGE_ACTOR)
+        .await
+        .map_err(|e| format!("Failed to purge trash: {}", e))?;
+
+    println!(
+        "Purged {} transaction(s) and {} categor{} trashed more than {} ago.",
+        purged.transactions,
+        purged.categories,
+        if purged.categories == 1 { "y" } else { "ies" },
+        older_than
+    );
+    Ok(())
+}
+
+/// Expands directories to the `*.csv` files in them, sorted by name
+fn price_files(args: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for arg in args {
+        let path = Path::new(arg);
+        if !path.is_dir() {
+            files.push(path.to_path_buf());
+            continue;
+        }
+        let entries =
+            std::fs::read_dir(path).map_err(|e| format!("Failed to read {}: {}", arg, e))?;
+        let mut csv_files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+            })
+            .collect();
+        csv_files.sort();
+        files.extend(csv_files);
+    }
+    Ok(files)
+}
+
+/// Runs `import_prices` with the arguments after the command name
+async fn import_prices(args: &[String]) -> Result<(), String> {
+    if args.is_empty() {
+        return Err("import_prices needs at least one CSV file or directory".to_string());
+    }
+
+    // Parse everything first so a bad file imports nothing
+    let mut rows = Vec::new();
+    for file in price_files(args)? {
+        let text = std::fs::read_to_string(&file)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        rows.extend(parse_csv(&text).map_err(|e| format!("{}: {}", file.display(), e))?);
+    }
+
+    reinitialize_database(DATABASE_URL)
+        .await
+        .map_err(|e| format!("Failed to initialize database: {}", e))?;
+    let summary = import_rows(&rows)
+        .await
+        .map_err(|e| format!("Failed to import prices: {}", e))?;
+
+    println!(
+        "Imported {} new price(s), updated {}, {} unchanged.",
+        summary.created, summary.updated, summary.unchanged
+    );
+    if !summary.unknown_codes.is_empty() {
+        let codes: Vec<&str> = summary.unknown_codes.iter().map(String::as_str).collect();
+        println!("Skipped unknown code(s): {}", codes.join(", "));
+    }
+    Ok(())
+}
+
+/// Runs `settle_card_payments`
+async fn settle_card_payments(args: &[String]) -> Result<(), String> {
+    if let Some(arg) = args.first() {
+        return Err(format!(
+            "Unknown argument for settle_card_payments: {}",
+            arg
+        ));
+    }
+
+    reinitialize_database(DATABASE_URL)
+        .await
+        .map_err(|e| format!("Failed to initialize database: {}", e))?;
+    let created = settle(Utc::now().date_naive())
+        .await
+        .map_err(|e| format!("Failed to settle card payments: {}", e))?;
+
+    println!("Recorded {} card payment(s).", created.len());
+    for payment in &created {
+        println!(
+            "  card {} closing {}: {} on {}",
+            payment.card_account_id, payment.closing_date, payment.amount, payment.payment_date
+        );
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() {
     // Set settings module environment variable
@@ -14,6 +153,29 @@ async fn main() {
         std::env::set_var("REINHARDT_SETTINGS_MODULE", "kakeboor.config.settings");
     }
 
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("purge_trash") {
+        if let Err(e) = purge_trash(&args[2..]).await {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("settle_card_payments") {
+        if let Err(e) = settle_card_payments(&args[2..]).await {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+    if args.get(1).map(String::as_str) == Some("import_prices") {
+        if let Err(e) = import_prices(&args[2..]).await {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
     // Execute command from command line
     if let Err(e) = execute_from_command_line().await {
         eprintln!("Error: {}", e);
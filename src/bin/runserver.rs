@@ -2,6 +2,7 @@
 //!
 //! Starts the HTTP server for local development.
 
+use kakeboor::config::settings::DATABASE_URL;
 use kakeboor::{get_settings, url_patterns};
 use reinhardt::core::tokio;
 use reinhardt::db::orm::reinitialize_database;
@@ -9,6 +10,15 @@ use reinhardt::db::DatabaseConnection;
 use reinhardt::server::serve;
 use std::net::SocketAddr;
 
+/// Add a column to an existing table, ignoring "duplicate column" errors
+///
+/// `CREATE TABLE IF NOT EXISTS` does not touch tables created by older
+/// versions, so columns added later are also added here.
+async fn add_column_if_missing(conn: &DatabaseConnection, table: &str, column_def: &str) {
+    let sql = format!("ALTER TABLE {} ADD COLUMN {}", table, column_def);
+    let _ = conn.execute(&sql, vec![]).await;
+}
+
 /// Create database tables if they don't exist
 async fn create_tables(conn: &DatabaseConnection) -> Result<(), Box<dyn std::error::Error>> {
     // Create categories table
@@ -20,6 +30,8 @@ async fn create_tables(conn: &DatabaseConnection) -> Result<(), Box<dyn std::err
             category_type TEXT NOT NULL,
             icon TEXT,
             color TEXT,
+            sort_order INTEGER NOT NULL DEFAULT 0,
+            deleted_at TEXT,
             created_at TEXT NOT NULL DEFAULT (datetime('now'))
         )
         "#,
@@ -27,6 +39,32 @@ async fn create_tables(conn: &DatabaseConnection) -> Result<(), Box<dyn std::err
     )
     .await?;
 
+    add_column_if_missing(conn, "categories", "sort_order INTEGER NOT NULL DEFAULT 0").await;
+    add_column_if_missing(conn, "categories", "deleted_at TEXT").await;
+
+    // Create accounts table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS accounts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            account_type TEXT NOT NULL,
+            opening_balance INTEGER NOT NULL DEFAULT 0,
+            closing_day INTEGER,
+            payment_day INTEGER,
+            payment_account_id INTEGER,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (payment_account_id) REFERENCES accounts(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    add_column_if_missing(conn, "accounts", "closing_day INTEGER").await;
+    add_column_if_missing(conn, "accounts", "payment_day INTEGER").await;
+    add_column_if_missing(conn, "accounts", "payment_account_id INTEGER").await;
+
     // Create transactions table
     conn.execute(
         r#"
@@ -37,8 +75,379 @@ async fn create_tables(conn: &DatabaseConnection) -> Result<(), Box<dyn std::err
             description TEXT NOT NULL,
             transaction_date TEXT NOT NULL,
             transaction_type TEXT NOT NULL,
+            account_id INTEGER,
+            reconcile_status TEXT NOT NULL DEFAULT 'uncleared',
+            reconciliation_id INTEGER,
+            idempotency_key TEXT,
+            deleted_at TEXT,
             created_at TEXT NOT NULL DEFAULT (datetime('now')),
             updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (category_id) REFERENCES categories(id),
+            FOREIGN KEY (account_id) REFERENCES accounts(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    add_column_if_missing(conn, "transactions", "idempotency_key TEXT").await;
+    add_column_if_missing(conn, "transactions", "deleted_at TEXT").await;
+    add_column_if_missing(conn, "transactions", "account_id INTEGER").await;
+    add_column_if_missing(
+        conn,
+        "transactions",
+        "reconcile_status TEXT NOT NULL DEFAULT 'uncleared'",
+    )
+    .await;
+    add_column_if_missing(conn, "transactions", "reconciliation_id INTEGER").await;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS transactions_idempotency_key ON transactions (idempotency_key)",
+        vec![],
+    )
+    .await?;
+
+    // Create attachments table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS attachments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaction_id INTEGER NOT NULL,
+            filename TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            sha256 TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (transaction_id) REFERENCES transactions(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create medical expenses table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS medical_expenses (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaction_id INTEGER NOT NULL UNIQUE,
+            patient TEXT NOT NULL,
+            provider TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            reimbursement INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (transaction_id) REFERENCES transactions(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create donations tables
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS donations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaction_id INTEGER NOT NULL,
+            municipality TEXT NOT NULL,
+            donation_date TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            one_stop_status TEXT NOT NULL,
+            receipt_received INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (transaction_id) REFERENCES transactions(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS donation_limits (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            year INTEGER NOT NULL UNIQUE,
+            estimated_limit INTEGER NOT NULL,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create change_log table (sync cursor and deletion tombstones)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS change_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entity TEXT NOT NULL,
+            object_id INTEGER NOT NULL,
+            action TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create audit_log table (who changed which record, with a JSON diff)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS audit_log (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entity TEXT NOT NULL,
+            object_id INTEGER NOT NULL,
+            action TEXT NOT NULL,
+            actor TEXT NOT NULL,
+            changes TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create reconciliations table (statement sessions per account)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS reconciliations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id INTEGER NOT NULL,
+            statement_date TEXT NOT NULL,
+            ending_balance INTEGER NOT NULL,
+            finalized_at TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (account_id) REFERENCES accounts(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create card_payments table (one transfer per paid card statement)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS card_payments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            card_account_id INTEGER NOT NULL,
+            payment_account_id INTEGER NOT NULL,
+            closing_date TEXT NOT NULL,
+            payment_date TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (card_account_id) REFERENCES accounts(id),
+            FOREIGN KEY (payment_account_id) REFERENCES accounts(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS card_payments_statement ON card_payments (card_account_id, closing_date)",
+        vec![],
+    )
+    .await?;
+
+    // Create installment_plans table (one plan per purchase)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS installment_plans (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaction_id INTEGER NOT NULL UNIQUE,
+            plan_type TEXT NOT NULL,
+            principal INTEGER NOT NULL,
+            interest_rate REAL,
+            monthly_principal INTEGER,
+            first_payment_month TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (transaction_id) REFERENCES transactions(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create installment_payments table (schedule of a plan)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS installment_payments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            plan_id INTEGER NOT NULL,
+            number INTEGER NOT NULL,
+            due_date TEXT NOT NULL,
+            principal INTEGER NOT NULL,
+            fee INTEGER NOT NULL,
+            FOREIGN KEY (plan_id) REFERENCES installment_plans(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create loans table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS loans (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            principal INTEGER NOT NULL,
+            annual_rate REAL NOT NULL,
+            term_months INTEGER NOT NULL,
+            start_date TEXT NOT NULL,
+            repayment_method TEXT NOT NULL,
+            account_id INTEGER,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (account_id) REFERENCES accounts(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create loan_prepayments table (繰上返済)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS loan_prepayments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            loan_id INTEGER NOT NULL,
+            prepayment_date TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            prepayment_type TEXT NOT NULL,
+            FOREIGN KEY (loan_id) REFERENCES loans(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create loan_repayments table (transactions linked to a scheduled payment)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS loan_repayments (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            loan_id INTEGER NOT NULL,
+            transaction_id INTEGER NOT NULL UNIQUE,
+            number INTEGER NOT NULL,
+            principal INTEGER NOT NULL,
+            interest INTEGER NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (loan_id) REFERENCES loans(id),
+            FOREIGN KEY (transaction_id) REFERENCES transactions(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create assets table (manually valued assets and liabilities)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS assets (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create asset_valuations table (dated value snapshots)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS asset_valuations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            asset_id INTEGER NOT NULL,
+            valuation_date TEXT NOT NULL,
+            value INTEGER NOT NULL,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (asset_id) REFERENCES assets(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS asset_valuations_day ON asset_valuations (asset_id, valuation_date)",
+        vec![],
+    )
+    .await?;
+
+    // Create securities table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS securities (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            code TEXT NOT NULL UNIQUE,
+            name TEXT NOT NULL,
+            price_unit INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create security_prices table (filled by manage import_prices)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS security_prices (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            security_id INTEGER NOT NULL,
+            price_date TEXT NOT NULL,
+            price REAL NOT NULL,
+            FOREIGN KEY (security_id) REFERENCES securities(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS security_prices_day ON security_prices (security_id, price_date)",
+        vec![],
+    )
+    .await?;
+
+    // Create investment_transactions table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS investment_transactions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            account_id INTEGER NOT NULL,
+            security_id INTEGER NOT NULL,
+            transaction_type TEXT NOT NULL,
+            trade_date TEXT NOT NULL,
+            quantity REAL NOT NULL DEFAULT 0,
+            price REAL NOT NULL DEFAULT 0,
+            amount INTEGER NOT NULL,
+            fee INTEGER NOT NULL DEFAULT 0,
+            description TEXT NOT NULL DEFAULT '',
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (account_id) REFERENCES accounts(id),
+            FOREIGN KEY (security_id) REFERENCES securities(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create recurring_items table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS recurring_items (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            account_id INTEGER NOT NULL,
+            category_id INTEGER,
+            transaction_type TEXT NOT NULL,
+            amount INTEGER NOT NULL,
+            frequency TEXT NOT NULL DEFAULT 'monthly',
+            start_date TEXT NOT NULL,
+            end_date TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (account_id) REFERENCES accounts(id),
             FOREIGN KEY (category_id) REFERENCES categories(id)
         )
         "#,
@@ -46,6 +455,75 @@ async fn create_tables(conn: &DatabaseConnection) -> Result<(), Box<dyn std::err
     )
     .await?;
 
+    // Create goals table
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS goals (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            target_amount INTEGER NOT NULL,
+            deadline TEXT,
+            account_id INTEGER,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (account_id) REFERENCES accounts(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create goal_contributions table (a transaction counts for one goal at most)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS goal_contributions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            goal_id INTEGER NOT NULL,
+            transaction_id INTEGER NOT NULL UNIQUE,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            FOREIGN KEY (goal_id) REFERENCES goals(id),
+            FOREIGN KEY (transaction_id) REFERENCES transactions(id)
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create period_locks table (single row: books closed through a day)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS period_locks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            locked_through TEXT,
+            updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+
+    // Create idempotency_keys table (stored responses of retried writes)
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS idempotency_keys (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            key TEXT NOT NULL UNIQUE,
+            request_hash TEXT NOT NULL,
+            status_code INTEGER NOT NULL,
+            response_body TEXT NOT NULL,
+            response_headers TEXT NOT NULL DEFAULT '[]',
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        )
+        "#,
+        vec![],
+    )
+    .await?;
+    add_column_if_missing(
+        conn,
+        "idempotency_keys",
+        "response_headers TEXT NOT NULL DEFAULT '[]'",
+    )
+    .await;
+
     Ok(())
 }
 
@@ -59,14 +537,13 @@ async fn main() {
 
     let _settings = get_settings();
 
-    // Initialize database with SQLite (mode=rwc creates file if not exists)
-    let db_url = "sqlite:db.sqlite3?mode=rwc";
-    if let Err(e) = reinitialize_database(db_url).await {
+    // Initialize database with SQLite
+    if let Err(e) = reinitialize_database(DATABASE_URL).await {
         eprintln!("Warning: Failed to initialize database: {}", e);
         eprintln!("Continuing without database...");
     } else {
         // Create tables if they don't exist
-        match DatabaseConnection::connect(db_url).await {
+        match DatabaseConnection::connect(DATABASE_URL).await {
             Ok(conn) => {
                 if let Err(e) = create_tables(&conn).await {
                     eprintln!("Warning: Failed to create tables: {}", e);
@@ -84,6 +561,11 @@ async fn main() {
 
     let addr: SocketAddr = "127.0.0.1:8000".parse().expect("Invalid address");
 
+    if !std::path::Path::new("dist-wasm/kakeboor_bg.wasm").exists() {
+        eprintln!("Warning: WASM client bundle not found in dist-wasm/.");
+        eprintln!("Build it with `cargo make build-wasm` to use the web UI.");
+    }
+
     println!("Starting development server at http://127.0.0.1:8000/");
     println!("Quit the server with CONTROL-C.");
 
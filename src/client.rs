@@ -1,17 +1,14 @@
 //! WASM client module for kakebooR
 //!
-//! This module contains the frontend code that runs in the browser.
+//! This module contains the frontend code that runs in the browser. It is
+//! only compiled for `wasm32` targets (see `build.rs`).
 
-#[cfg(target_arch = "wasm32")]
 pub mod api;
-#[cfg(target_arch = "wasm32")]
 pub mod components;
-#[cfg(target_arch = "wasm32")]
 pub mod lib;
-#[cfg(target_arch = "wasm32")]
 pub mod pages;
-#[cfg(target_arch = "wasm32")]
 pub mod router;
+pub mod store;
+pub mod sync;
 
-#[cfg(target_arch = "wasm32")]
 pub use lib::{init_global_router, with_router};
@@ -1,51 +1,189 @@
 //! API client for kakebooR
 //!
-//! Provides functions to fetch data from the REST API.
+//! Provides functions to fetch data from the REST API. Categories and
+//! transactions fall back to the IndexedDB cache when the server cannot be
+//! reached, and transaction changes are queued (see [`super::sync`]).
 
+use crate::client::{store, sync};
 use crate::shared::types::{
-    CategoryInfo, CategoryListResponse, MonthlyReportInfo, TransactionInfo,
-    TransactionListResponse,
+    CategoryListResponse, CategoryResponse, CategorySuggestion, CategorySuggestionResponse,
+    ComparisonReportResponse, CreateCategoryRequest, CreateTransactionRequest,
+    MonthlyReportResponse, TransactionListResponse, TransactionResponse, TransactionType,
+    UpdateCategoryRequest, UpdateTransactionRequest, YearlyReportResponse,
 };
 use gloo_net::http::Request;
 
 /// API base URL
 const API_BASE: &str = "/api";
 
-/// Fetch all categories
-pub async fn get_categories() -> Result<Vec<CategoryInfo>, String> {
-    let response = Request::get(&format!("{}/categories/", API_BASE))
+/// Builds an error message from a failed response
+///
+/// Uses the server's `{"error": "..."}` message or, for validation errors,
+/// the messages of each invalid field, falling back to the status code.
+async fn error_message(response: gloo_net::http::Response, context: &str) -> String {
+    let status = response.status();
+    let Ok(body) = response.json::<serde_json::Value>().await else {
+        return format!("{}: {}", context, status);
+    };
+
+    if let Some(error) = body.get("error").and_then(|e| e.as_str()) {
+        return error.to_string();
+    }
+
+    // validator-style errors: {"field": [{"message": "..."}]}
+    let messages: Vec<String> = body
+        .as_object()
+        .into_iter()
+        .flat_map(|fields| fields.values())
+        .filter_map(|errors| errors.as_array())
+        .flatten()
+        .filter_map(|e| e.get("message").and_then(|m| m.as_str()))
+        .map(str::to_string)
+        .collect();
+
+    if messages.is_empty() {
+        format!("{}: {}", context, status)
+    } else {
+        messages.join(" / ")
+    }
+}
+
+/// Fetch all categories, from the local cache when offline
+pub async fn get_categories() -> Result<Vec<CategoryResponse>, String> {
+    let response = match Request::get(&format!("{}/categories/", API_BASE))
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+    {
+        Ok(response) => response,
+        Err(e) => {
+            return store::get(store::CATEGORIES_KEY)
+                .await
+                .ok_or_else(|| e.to_string());
+        }
+    };
 
     if !response.ok() {
         return Err(format!("Failed to fetch categories: {}", response.status()));
     }
 
     let data: CategoryListResponse = response.json().await.map_err(|e| e.to_string())?;
+    let _ = store::set(store::CATEGORIES_KEY, &data.results).await;
     Ok(data.results)
 }
 
-/// Fetch all transactions
-pub async fn get_transactions() -> Result<Vec<TransactionInfo>, String> {
-    let response = Request::get(&format!("{}/transactions/", API_BASE))
+/// Create a new category
+pub async fn create_category(request: &CreateCategoryRequest) -> Result<CategoryResponse, String> {
+    let response = Request::post(&format!("{}/categories/", API_BASE))
+        .json(request)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.ok() {
+        return Err(error_message(response, "Failed to create category").await);
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Update a category; `None` fields are left unchanged
+pub async fn update_category(
+    id: i64,
+    request: &UpdateCategoryRequest,
+) -> Result<CategoryResponse, String> {
+    let response = Request::put(&format!("{}/categories/{}/", API_BASE, id))
+        .json(request)
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.ok() {
+        return Err(error_message(response, "Failed to update category").await);
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Delete a category
+pub async fn delete_category(id: i64) -> Result<(), String> {
+    let response = Request::delete(&format!("{}/categories/{}/", API_BASE, id))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.ok() && response.status() != 204 {
+        return Err(error_message(response, "Failed to delete category").await);
+    }
+
+    Ok(())
+}
+
+/// Fetch all transactions, including changes not yet sent
+///
+/// Falls back to the locally cached recent transactions when offline.
+pub async fn get_transactions() -> Result<Vec<TransactionResponse>, String> {
+    let transactions = match Request::get(&format!("{}/transactions/", API_BASE))
+        .send()
+        .await
+    {
+        Ok(response) => {
+            if !response.ok() {
+                return Err(format!(
+                    "Failed to fetch transactions: {}",
+                    response.status()
+                ));
+            }
+            let data: TransactionListResponse =
+                response.json().await.map_err(|e| e.to_string())?;
+            sync::cache_transactions(&data.results).await;
+            data.results
+        }
+        Err(e) => store::get(store::TRANSACTIONS_KEY)
+            .await
+            .ok_or_else(|| e.to_string())?,
+    };
+
+    Ok(sync::with_pending_changes(transactions).await)
+}
+
+/// Fetch ranked category suggestions for a transaction description
+pub async fn suggest_category(
+    description: &str,
+    amount: Option<i64>,
+    transaction_type: TransactionType,
+) -> Result<Vec<CategorySuggestion>, String> {
+    let amount = amount.map(|a| a.to_string()).unwrap_or_default();
+    let transaction_type = transaction_type.to_string();
+    let mut params = vec![
+        ("description", description),
+        ("transaction_type", transaction_type.as_str()),
+    ];
+    if !amount.is_empty() {
+        params.push(("amount", amount.as_str()));
+    }
+
+    let response = Request::get(&format!("{}/transactions/suggest-category/", API_BASE))
+        .query(params)
         .send()
         .await
         .map_err(|e| e.to_string())?;
 
     if !response.ok() {
         return Err(format!(
-            "Failed to fetch transactions: {}",
+            "Failed to fetch category suggestions: {}",
             response.status()
         ));
     }
 
-    let data: TransactionListResponse = response.json().await.map_err(|e| e.to_string())?;
-    Ok(data.results)
+    let data: CategorySuggestionResponse =
+        response.json().await.map_err(|e| e.to_string())?;
+    Ok(data.suggestions)
 }
 
 /// Fetch monthly report
-pub async fn get_monthly_report(year: i32, month: u32) -> Result<MonthlyReportInfo, String> {
+pub async fn get_monthly_report(year: i32, month: u32) -> Result<MonthlyReportResponse, String> {
     let response = Request::get(&format!(
         "{}/reports/monthly/?year={}&month={}",
         API_BASE, year, month
@@ -61,53 +199,203 @@ pub async fn get_monthly_report(year: i32, month: u32) -> Result<MonthlyReportIn
     response.json().await.map_err(|e| e.to_string())
 }
 
-/// Create a new transaction
-pub async fn create_transaction(
-    amount: i64,
-    category_id: i64,
-    description: &str,
-    transaction_date: &str,
-    transaction_type: &str,
-) -> Result<TransactionInfo, String> {
-    let body = serde_json::json!({
-        "amount": amount,
-        "category_id": category_id,
-        "description": description,
-        "transaction_date": transaction_date,
-        "transaction_type": transaction_type
-    });
+/// Fetch the comparison of two periods (`YYYY-MM` or `YYYY`)
+pub async fn get_comparison_report(
+    base: &str,
+    target: &str,
+) -> Result<ComparisonReportResponse, String> {
+    let response = Request::get(&format!(
+        "{}/reports/compare/?base={}&target={}",
+        API_BASE, base, target
+    ))
+    .send()
+    .await
+    .map_err(|e| e.to_string())?;
 
-    let response = Request::post(&format!("{}/transactions/", API_BASE))
-        .header("Content-Type", "application/json")
-        .body(body.to_string())
-        .map_err(|e| e.to_string())?
+    if !response.ok() {
+        return Err(format!("Failed to fetch report: {}", response.status()));
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Fetch yearly report
+pub async fn get_yearly_report(year: i32) -> Result<YearlyReportResponse, String> {
+    let response = Request::get(&format!("{}/reports/yearly/?year={}", API_BASE, year))
         .send()
         .await
         .map_err(|e| e.to_string())?;
 
     if !response.ok() {
-        return Err(format!(
-            "Failed to create transaction: {}",
-            response.status()
-        ));
+        return Err(format!("Failed to fetch report: {}", response.status()));
     }
 
     response.json().await.map_err(|e| e.to_string())
 }
 
-/// Delete a transaction
-pub async fn delete_transaction(id: i64) -> Result<(), String> {
+/// Error from a request whose failure may be temporary
+#[derive(Debug, Clone)]
+pub enum RequestError {
+    /// The server could not be reached
+    Offline,
+    /// The server answered with an error status
+    Rejected { status: u16, message: String },
+}
+
+impl RequestError {
+    async fn from_response(response: gloo_net::http::Response, context: &str) -> Self {
+        let status = response.status();
+        RequestError::Rejected {
+            status,
+            message: error_message(response, context).await,
+        }
+    }
+
+    fn into_message(self) -> String {
+        match self {
+            RequestError::Offline => "Failed to reach the server".to_string(),
+            RequestError::Rejected { message, .. } => message,
+        }
+    }
+}
+
+/// Send a create request, tagged with a key so that retries are not
+/// inserted twice
+pub async fn send_create_transaction(
+    request: &CreateTransactionRequest,
+    idempotency_key: &str,
+) -> Result<TransactionResponse, RequestError> {
+    let response = Request::post(&format!("{}/transactions/", API_BASE))
+        .header("Idempotency-Key", idempotency_key)
+        .json(request)
+        .map_err(|_| RequestError::Offline)?
+        .send()
+        .await
+        .map_err(|_| RequestError::Offline)?;
+
+    if !response.ok() {
+        return Err(RequestError::from_response(response, "Failed to create transaction").await);
+    }
+
+    response.json().await.map_err(|_| RequestError::Offline)
+}
+
+/// Fetch a single transaction from the server
+pub async fn fetch_transaction(id: i64) -> Result<TransactionResponse, RequestError> {
+    let response = Request::get(&format!("{}/transactions/{}/", API_BASE, id))
+        .send()
+        .await
+        .map_err(|_| RequestError::Offline)?;
+
+    if !response.ok() {
+        return Err(RequestError::from_response(response, "Failed to fetch transaction").await);
+    }
+
+    response.json().await.map_err(|_| RequestError::Offline)
+}
+
+/// Send an update request
+pub async fn send_update_transaction(
+    id: i64,
+    request: &UpdateTransactionRequest,
+) -> Result<TransactionResponse, RequestError> {
+    let response = Request::put(&format!("{}/transactions/{}/", API_BASE, id))
+        .json(request)
+        .map_err(|_| RequestError::Offline)?
+        .send()
+        .await
+        .map_err(|_| RequestError::Offline)?;
+
+    if !response.ok() {
+        return Err(RequestError::from_response(response, "Failed to update transaction").await);
+    }
+
+    response.json().await.map_err(|_| RequestError::Offline)
+}
+
+/// Send a delete request
+pub async fn send_delete_transaction(id: i64) -> Result<(), RequestError> {
     let response = Request::delete(&format!("{}/transactions/{}/", API_BASE, id))
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|_| RequestError::Offline)?;
 
     if !response.ok() && response.status() != 204 {
-        return Err(format!(
-            "Failed to delete transaction: {}",
-            response.status()
-        ));
+        return Err(RequestError::from_response(response, "Failed to delete transaction").await);
     }
 
     Ok(())
 }
+
+/// Create a new transaction, queueing it if the server is unreachable
+pub async fn create_transaction(
+    request: &CreateTransactionRequest,
+) -> Result<TransactionResponse, String> {
+    let key = sync::new_idempotency_key();
+    // Keep queued changes in order
+    if !sync::replay().await {
+        return sync::queue_create(request, key).await;
+    }
+    match send_create_transaction(request, &key).await {
+        Err(RequestError::Offline) => sync::queue_create(request, key).await,
+        result => result.map_err(RequestError::into_message),
+    }
+}
+
+/// Fetch a single transaction, from the local cache when offline
+pub async fn get_transaction(id: i64) -> Result<TransactionResponse, String> {
+    // Negative ids are creates that have not been sent yet
+    let fetched = if id < 0 {
+        Err(RequestError::Offline)
+    } else {
+        fetch_transaction(id).await
+    };
+
+    match fetched {
+        Ok(transaction) => {
+            sync::remember_transaction(&transaction).await;
+            sync::with_pending_changes(vec![transaction])
+                .await
+                .into_iter()
+                .find(|t| t.id == id)
+                .ok_or_else(|| "This transaction is waiting to be deleted".to_string())
+        }
+        Err(RequestError::Offline) => sync::cached_with_pending_changes()
+            .await
+            .into_iter()
+            .find(|t| t.id == id)
+            .ok_or_else(|| RequestError::Offline.into_message()),
+        Err(e) => Err(e.into_message()),
+    }
+}
+
+/// Update an existing transaction, queueing it if the server is unreachable
+pub async fn update_transaction(
+    id: i64,
+    request: &UpdateTransactionRequest,
+) -> Result<TransactionResponse, String> {
+    if id < 0 {
+        return sync::update_queued_create(id, request).await;
+    }
+    if !sync::replay().await {
+        return sync::queue_update(id, request).await;
+    }
+    match send_update_transaction(id, request).await {
+        Err(RequestError::Offline) => sync::queue_update(id, request).await,
+        result => result.map_err(RequestError::into_message),
+    }
+}
+
+/// Delete a transaction, queueing it if the server is unreachable
+pub async fn delete_transaction(id: i64) -> Result<(), String> {
+    if id < 0 {
+        return sync::delete_queued_create(id).await;
+    }
+    if !sync::replay().await {
+        return sync::queue_delete(id).await;
+    }
+    match send_delete_transaction(id).await {
+        Err(RequestError::Offline) => sync::queue_delete(id).await,
+        result => result.map_err(RequestError::into_message),
+    }
+}
@@ -2,5 +2,11 @@
 //!
 //! This module contains reusable UI components.
 
+pub mod categories;
+pub mod charts;
 pub mod dashboard;
+pub mod dom;
+pub mod reports;
+pub mod sync_status;
 pub mod transactions;
+pub mod transaction_form;
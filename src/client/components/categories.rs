@@ -0,0 +1,516 @@
+//! Categories component
+//!
+//! Lists categories and allows creating, renaming, recoloring, reordering
+//! and deleting them.
+
+use crate::client::api;
+use crate::client::components::dom::{confirm, event_value};
+use crate::shared::types::{
+    CategoryResponse, CategoryType, CreateCategoryRequest, UpdateCategoryRequest,
+};
+use reinhardt_pages::component::View;
+use reinhardt_pages::page;
+use reinhardt_pages::reactive::hooks::use_state;
+use reinhardt_pages::spawn::spawn_task;
+use reinhardt_pages::Signal;
+
+/// Icons offered by the icon picker
+const ICON_PRESETS: &[&str] = &[
+    "🍚", "🛒", "🍺", "🚃", "🚗", "🏠", "💡", "📱", "🏥", "💊", "🎓", "👕", "🎮", "🎁",
+    "✈️", "🐶", "💰", "💴", "📈", "🧾",
+];
+
+/// Colors offered next to the free color input
+const COLOR_PRESETS: &[&str] = &[
+    "#EF4444", "#F97316", "#EAB308", "#22C55E", "#14B8A6", "#3B82F6", "#8B5CF6", "#EC4899",
+    "#6B7280",
+];
+
+/// Fallback badge color for categories without a color
+const DEFAULT_BADGE_COLOR: &str = "#6B7280";
+
+/// Values of the new-category form
+#[derive(Debug, Clone, PartialEq)]
+struct NewCategory {
+    name: String,
+    category_type: CategoryType,
+    icon: Option<String>,
+    color: String,
+}
+
+impl Default for NewCategory {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            category_type: CategoryType::Expense,
+            icon: None,
+            color: COLOR_PRESETS[5].to_string(),
+        }
+    }
+}
+
+/// Colored badge with a category's icon and name
+///
+/// Shows "未分類" when the category is unknown (e.g. it was deleted).
+pub fn category_badge(category: Option<&CategoryResponse>) -> View {
+    let label = match category {
+        Some(c) => match &c.icon {
+            Some(icon) => format!("{} {}", icon, c.name),
+            None => c.name.clone(),
+        },
+        None => "未分類".to_string(),
+    };
+    let color = category
+        .and_then(|c| c.color.clone())
+        .unwrap_or_else(|| DEFAULT_BADGE_COLOR.to_string());
+    let style = format!("background-color: {}", color);
+
+    page!(|label: String, style: String| {
+        span {
+            class: "inline-block px-2 py-1 rounded-full text-xs font-semibold text-white",
+            style: style,
+            { label }
+        }
+    })(label, style)
+}
+
+/// Category management component
+pub fn categories_manager() -> View {
+    let (categories, set_categories) = use_state(Vec::<CategoryResponse>::new());
+    let (new_category, set_new_category) = use_state(NewCategory::default());
+    let (loading, set_loading) = use_state(true);
+    let (error, set_error) = use_state(None::<String>);
+
+    // Reloads the list from the server after every change
+    let reload = {
+        let set_categories = set_categories.clone();
+        let set_loading = set_loading.clone();
+        let set_error = set_error.clone();
+        move || {
+            let set_categories = set_categories.clone();
+            let set_loading = set_loading.clone();
+            let set_error = set_error.clone();
+            spawn_task(async move {
+                match api::get_categories().await {
+                    Ok(cats) => {
+                        set_categories(cats);
+                        set_loading(false);
+                    }
+                    Err(e) => {
+                        set_error(Some(e));
+                        set_loading(false);
+                    }
+                }
+            });
+        }
+    };
+    reload();
+
+    let on_new_name = {
+        let new_category = new_category.clone();
+        let set_new_category = set_new_category.clone();
+        move |event: web_sys::Event| {
+            let mut c = new_category.get();
+            c.name = event_value(&event);
+            set_new_category(c);
+        }
+    };
+
+    let on_new_type = {
+        let new_category = new_category.clone();
+        let set_new_category = set_new_category.clone();
+        move |event: web_sys::Event| {
+            let mut c = new_category.get();
+            c.category_type = match event_value(&event).as_str() {
+                "income" => CategoryType::Income,
+                _ => CategoryType::Expense,
+            };
+            set_new_category(c);
+        }
+    };
+
+    let on_new_icon = {
+        let new_category = new_category.clone();
+        let set_new_category = set_new_category.clone();
+        move |event: web_sys::Event| {
+            let mut c = new_category.get();
+            let icon = event_value(&event);
+            c.icon = if icon.is_empty() { None } else { Some(icon) };
+            set_new_category(c);
+        }
+    };
+
+    let on_new_color = {
+        let new_category = new_category.clone();
+        let set_new_category = set_new_category.clone();
+        move |event: web_sys::Event| {
+            let mut c = new_category.get();
+            c.color = event_value(&event);
+            set_new_category(c);
+        }
+    };
+
+    let on_create = {
+        let new_category = new_category.clone();
+        let set_new_category = set_new_category.clone();
+        let set_error = set_error.clone();
+        let reload = reload.clone();
+        move |event: web_sys::Event| {
+            event.prevent_default();
+            let c = new_category.get();
+            let set_new_category = set_new_category.clone();
+            let set_error = set_error.clone();
+            let reload = reload.clone();
+            spawn_task(async move {
+                let result = api::create_category(&CreateCategoryRequest {
+                    name: c.name.clone(),
+                    category_type: c.category_type,
+                    icon: c.icon.clone(),
+                    color: Some(c.color.clone()),
+                    sort_order: None,
+                })
+                .await;
+                match result {
+                    Ok(_) => {
+                        set_error(None);
+                        set_new_category(NewCategory::default());
+                        reload();
+                    }
+                    Err(e) => set_error(Some(e)),
+                }
+            });
+        }
+    };
+
+    // Runs an update for one category and reloads the list
+    let update = {
+        let set_error = set_error.clone();
+        let reload = reload.clone();
+        move |id: i64, name: Option<String>, icon: Option<String>, color: Option<String>| {
+            let set_error = set_error.clone();
+            let reload = reload.clone();
+            spawn_task(async move {
+                let request = UpdateCategoryRequest {
+                    name,
+                    icon,
+                    color,
+                    sort_order: None,
+                };
+                let result = api::update_category(id, &request).await;
+                match result {
+                    Ok(_) => reload(),
+                    Err(e) => set_error(Some(e)),
+                }
+            });
+        }
+    };
+
+    // Moves the category at `index` by `offset` and renumbers sort orders
+    let reorder = {
+        let categories = categories.clone();
+        let set_error = set_error.clone();
+        let reload = reload.clone();
+        move |index: usize, offset: isize| {
+            let mut list = categories.get();
+            let Some(target) = index.checked_add_signed(offset).filter(|t| *t < list.len()) else {
+                return;
+            };
+            list.swap(index, target);
+            let set_error = set_error.clone();
+            let reload = reload.clone();
+            spawn_task(async move {
+                for (position, category) in list.iter().enumerate() {
+                    let position = position as i32;
+                    if category.sort_order == position {
+                        continue;
+                    }
+                    let request = UpdateCategoryRequest {
+                        sort_order: Some(position),
+                        ..Default::default()
+                    };
+                    if let Err(e) = api::update_category(category.id, &request).await {
+                        set_error(Some(e));
+                        break;
+                    }
+                }
+                reload();
+            });
+        }
+    };
+
+    let delete = {
+        let set_error = set_error.clone();
+        let reload = reload.clone();
+        move |id: i64, name: String| {
+            if !confirm(&format!("カテゴリ「{}」を削除しますか？", name)) {
+                return;
+            }
+            let set_error = set_error.clone();
+            let reload = reload.clone();
+            spawn_task(async move {
+                match api::delete_category(id).await {
+                    Ok(()) => reload(),
+                    Err(e) => set_error(Some(e)),
+                }
+            });
+        }
+    };
+
+    page!(|categories: Signal<Vec<CategoryResponse>>, new_category: Signal<NewCategory>, loading: Signal<bool>, error: Signal<Option<String>>| {
+        div {
+            class: "container mx-auto px-4 py-8",
+            h1 {
+                class: "text-3xl font-bold mb-8",
+                "カテゴリ管理"
+            }
+            watch {
+                if let Some(err) = error.get() {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded mb-4",
+                        { format!("エラー: {}", err) }
+                    }
+                }
+            }
+            // New category form
+            form {
+                class: "bg-white shadow rounded-lg p-6 mb-8 grid grid-cols-1 md:grid-cols-5 gap-4 items-end",
+                @submit: on_create.clone(),
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 mb-1",
+                        "名前"
+                    }
+                    input {
+                        r#type: "text",
+                        required: true,
+                        maxlength: "100",
+                        class: "w-full border rounded px-3 py-2",
+                        value: new_category.get().name,
+                        @input: on_new_name.clone(),
+                    }
+                }
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 mb-1",
+                        "種別"
+                    }
+                    select {
+                        class: "w-full border rounded px-3 py-2",
+                        @change: on_new_type.clone(),
+                        option {
+                            value: "expense",
+                            selected: new_category.get().category_type == CategoryType::Expense,
+                            "支出"
+                        }
+                        option {
+                            value: "income",
+                            selected: new_category.get().category_type == CategoryType::Income,
+                            "収入"
+                        }
+                    }
+                }
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 mb-1",
+                        "アイコン"
+                    }
+                    { icon_picker(new_category.get().icon.clone(), on_new_icon.clone()) }
+                }
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 mb-1",
+                        "色"
+                    }
+                    { color_picker(new_category.get().color.clone(), on_new_color.clone()) }
+                }
+                button {
+                    r#type: "submit",
+                    class: "bg-green-500 hover:bg-green-700 text-white font-bold py-2 px-4 rounded",
+                    "+ 追加"
+                }
+            }
+            watch {
+                if loading.get() {
+                    div {
+                        class: "text-center py-8",
+                        div {
+                            class: "inline-block animate-spin rounded-full h-8 w-8 border-b-2 border-blue-500",
+                        }
+                        p {
+                            class: "mt-2 text-gray-600",
+                            "読み込み中..."
+                        }
+                    }
+                } else if categories.get().is_empty() {
+                    div {
+                        class: "text-center py-8 text-gray-500",
+                        "カテゴリがありません"
+                    }
+                } else {
+                    div {
+                        class: "bg-white shadow overflow-hidden rounded-lg divide-y divide-gray-200",
+                        { View::fragment(categories.get().iter().enumerate().map(|(index, cat)| {
+                            let id = cat.id;
+                            let name = cat.name.clone();
+                            let type_label = match cat.category_type {
+                                CategoryType::Income => "収入",
+                                CategoryType::Expense => "支出",
+                            };
+                            let badge = category_badge(Some(cat));
+                            let on_rename = {
+                                let update = update.clone();
+                                move |event: web_sys::Event| update(id, Some(event_value(&event)), None, None)
+                            };
+                            let on_icon = {
+                                let update = update.clone();
+                                move |event: web_sys::Event| update(id, None, Some(event_value(&event)), None)
+                            };
+                            let on_color = {
+                                let update = update.clone();
+                                move |event: web_sys::Event| update(id, None, None, Some(event_value(&event)))
+                            };
+                            let on_up = {
+                                let reorder = reorder.clone();
+                                move |_event: web_sys::Event| reorder(index, -1)
+                            };
+                            let on_down = {
+                                let reorder = reorder.clone();
+                                move |_event: web_sys::Event| reorder(index, 1)
+                            };
+                            let on_delete = {
+                                let delete = delete.clone();
+                                let name = name.clone();
+                                move |_event: web_sys::Event| delete(id, name.clone())
+                            };
+                            let icon_select = icon_picker(cat.icon.clone(), on_icon);
+                            let color_input = color_picker(
+                                cat.color.clone().unwrap_or_else(|| DEFAULT_BADGE_COLOR.to_string()),
+                                on_color,
+                            );
+
+                            page!(|name: String, type_label: &str, badge: View, icon_select: View, color_input: View| {
+                                div {
+                                    class: "flex items-center gap-4 px-6 py-4",
+                                    div {
+                                        class: "flex flex-col",
+                                        button {
+                                            r#type: "button",
+                                            class: "text-gray-500 hover:text-gray-800",
+                                            @click: on_up,
+                                            "▲"
+                                        }
+                                        button {
+                                            r#type: "button",
+                                            class: "text-gray-500 hover:text-gray-800",
+                                            @click: on_down,
+                                            "▼"
+                                        }
+                                    }
+                                    div {
+                                        class: "w-40",
+                                        { badge }
+                                    }
+                                    input {
+                                        r#type: "text",
+                                        maxlength: "100",
+                                        class: "border rounded px-3 py-2 flex-1",
+                                        value: name,
+                                        @change: on_rename,
+                                    }
+                                    span {
+                                        class: "text-sm text-gray-500 w-12",
+                                        { type_label }
+                                    }
+                                    { icon_select }
+                                    { color_input }
+                                    button {
+                                        r#type: "button",
+                                        class: "bg-red-500 hover:bg-red-700 text-white font-bold py-2 px-4 rounded",
+                                        @click: on_delete,
+                                        "削除"
+                                    }
+                                }
+                            })(name, type_label, badge, icon_select, color_input)
+                        }).collect::<Vec<_>>()) }
+                    }
+                }
+            }
+            // Back to dashboard link
+            div {
+                class: "mt-8",
+                a {
+                    href: "/",
+                    class: "text-blue-500 hover:underline",
+                    "← ダッシュボードに戻る"
+                }
+            }
+        }
+    })(categories, new_category, loading, error)
+}
+
+/// Select box offering the preset icons
+fn icon_picker(selected: Option<String>, on_change: impl Fn(web_sys::Event) + Clone + 'static) -> View {
+    let options = View::fragment(
+        ICON_PRESETS
+            .iter()
+            .map(|icon| {
+                let is_selected = selected.as_deref() == Some(*icon);
+                let icon = icon.to_string();
+                page!(|icon: String, is_selected: bool| {
+                    option {
+                        value: icon.clone(),
+                        selected: is_selected,
+                        { icon }
+                    }
+                })(icon, is_selected)
+            })
+            .collect::<Vec<_>>(),
+    );
+    let none_selected = selected.is_none();
+
+    page!(|options: View, none_selected: bool| {
+        select {
+            class: "border rounded px-3 py-2 text-xl",
+            @change: on_change,
+            option {
+                value: "",
+                selected: none_selected,
+                "なし"
+            }
+            { options }
+        }
+    })(options, none_selected)
+}
+
+/// Color input with a datalist of the preset colors
+fn color_picker(value: String, on_change: impl Fn(web_sys::Event) + Clone + 'static) -> View {
+    let swatches = View::fragment(
+        COLOR_PRESETS
+            .iter()
+            .map(|color| {
+                let color = color.to_string();
+                page!(|color: String| {
+                    option {
+                        value: color,
+                    }
+                })(color)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    page!(|value: String, swatches: View| {
+        span {
+            input {
+                r#type: "color",
+                list: "category-color-presets",
+                class: "h-10 w-14 border rounded",
+                value: value,
+                @change: on_change,
+            }
+            datalist {
+                id: "category-color-presets",
+                { swatches }
+            }
+        }
+    })(value, swatches)
+}
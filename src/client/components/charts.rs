@@ -0,0 +1,218 @@
+//! Inline SVG charts
+//!
+//! Charts are generated as SVG markup in Rust and inserted into the page
+//! inline, so they need no JavaScript charting library.
+
+use crate::client::components::dom::set_inner_html;
+use crate::shared::types::MonthlySummary;
+use reinhardt_pages::component::View;
+use reinhardt_pages::page;
+use reinhardt_pages::spawn::spawn_task;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Colors for slices whose category has no color
+const PALETTE: &[&str] = &[
+    "#3B82F6", "#EF4444", "#22C55E", "#F97316", "#8B5CF6", "#14B8A6", "#EAB308", "#EC4899",
+    "#6B7280",
+];
+
+const INCOME_COLOR: &str = "#22C55E";
+const EXPENSE_COLOR: &str = "#EF4444";
+
+/// Source of unique ids for chart containers
+static NEXT_CHART_ID: AtomicU64 = AtomicU64::new(0);
+
+/// One slice of a donut chart
+#[derive(Debug, Clone)]
+pub struct Slice {
+    pub label: String,
+    pub value: i64,
+    pub color: Option<String>,
+}
+
+/// Returns the fallback color for the `index`-th series
+pub fn palette_color(index: usize) -> &'static str {
+    PALETTE[index % PALETTE.len()]
+}
+
+/// Renders a donut chart with the total in the middle
+pub fn donut_chart(slices: &[Slice], center_label: &str) -> String {
+    const SIZE: f64 = 240.0;
+    const RADIUS: f64 = 100.0;
+    const INNER_RADIUS: f64 = 60.0;
+    let center = SIZE / 2.0;
+
+    let total: i64 = slices.iter().map(|s| s.value.max(0)).sum();
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {s} {s}" width="{s}" height="{s}">"#,
+        s = SIZE
+    );
+
+    if total == 0 {
+        svg.push_str(&format!(
+            r##"<circle cx="{c}" cy="{c}" r="{r}" fill="#E5E7EB"/>"##,
+            c = center,
+            r = RADIUS
+        ));
+    } else {
+        let mut angle = -std::f64::consts::FRAC_PI_2;
+        for (i, slice) in slices.iter().filter(|s| s.value > 0).enumerate() {
+            let color = slice
+                .color
+                .clone()
+                .unwrap_or_else(|| palette_color(i).to_string());
+            let fraction = slice.value as f64 / total as f64;
+
+            // A single full-circle arc cannot be drawn as a path
+            if fraction >= 0.9999 {
+                svg.push_str(&format!(
+                    r#"<circle cx="{c}" cy="{c}" r="{r}" fill="{color}"><title>{label}</title></circle>"#,
+                    c = center,
+                    r = RADIUS,
+                    color = escape(&color),
+                    label = escape(&slice.label)
+                ));
+                continue;
+            }
+
+            let sweep = fraction * std::f64::consts::TAU;
+            let (x1, y1) = polar(center, RADIUS, angle);
+            let (x2, y2) = polar(center, RADIUS, angle + sweep);
+            let large_arc = if sweep > std::f64::consts::PI { 1 } else { 0 };
+            svg.push_str(&format!(
+                r#"<path d="M {c} {c} L {x1:.2} {y1:.2} A {r} {r} 0 {large_arc} 1 {x2:.2} {y2:.2} Z" fill="{color}"><title>{label} {pct:.1}%</title></path>"#,
+                c = center,
+                r = RADIUS,
+                color = escape(&color),
+                label = escape(&slice.label),
+                pct = fraction * 100.0
+            ));
+            angle += sweep;
+        }
+    }
+
+    svg.push_str(&format!(
+        r##"<circle cx="{c}" cy="{c}" r="{r}" fill="#FFFFFF"/><text x="{c}" y="{c}" text-anchor="middle" dominant-baseline="middle" font-size="16" font-weight="bold" fill="#1F2937">{label}</text></svg>"##,
+        c = center,
+        r = INNER_RADIUS,
+        label = escape(center_label)
+    ));
+    svg
+}
+
+/// Renders grouped income/expense bars for each month
+pub fn monthly_bar_chart(months: &[MonthlySummary]) -> String {
+    const WIDTH: f64 = 720.0;
+    const HEIGHT: f64 = 280.0;
+    const MARGIN_LEFT: f64 = 70.0;
+    const MARGIN_BOTTOM: f64 = 30.0;
+    const MARGIN_TOP: f64 = 10.0;
+
+    let plot_width = WIDTH - MARGIN_LEFT;
+    let plot_height = HEIGHT - MARGIN_BOTTOM - MARGIN_TOP;
+    let max = months
+        .iter()
+        .flat_map(|m| [m.total_income, m.total_expense])
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="{w}" height="{h}" font-size="11">"#,
+        w = WIDTH,
+        h = HEIGHT
+    );
+
+    // Horizontal grid lines with amount labels
+    for step in 0..=4 {
+        let value = max * step as f64 / 4.0;
+        let y = MARGIN_TOP + plot_height - plot_height * step as f64 / 4.0;
+        svg.push_str(&format!(
+            r##"<line x1="{x1}" y1="{y:.1}" x2="{x2}" y2="{y:.1}" stroke="#E5E7EB"/><text x="{tx}" y="{y:.1}" text-anchor="end" dominant-baseline="middle" fill="#6B7280">¥{label}</text>"##,
+            x1 = MARGIN_LEFT,
+            x2 = WIDTH,
+            tx = MARGIN_LEFT - 6.0,
+            label = format_amount(value.round() as i64)
+        ));
+    }
+
+    let group_width = plot_width / months.len().max(1) as f64;
+    let bar_width = group_width * 0.35;
+    for (i, m) in months.iter().enumerate() {
+        let x = MARGIN_LEFT + group_width * i as f64 + group_width * 0.15;
+        for (offset, value, color, kind) in [
+            (0.0, m.total_income, INCOME_COLOR, "収入"),
+            (bar_width, m.total_expense, EXPENSE_COLOR, "支出"),
+        ] {
+            let height = plot_height * value.max(0) as f64 / max;
+            svg.push_str(&format!(
+                r#"<rect x="{x:.1}" y="{y:.1}" width="{w:.1}" height="{h:.1}" fill="{color}"><title>{month}月 {kind} ¥{amount}</title></rect>"#,
+                x = x + offset,
+                y = MARGIN_TOP + plot_height - height,
+                w = bar_width,
+                h = height,
+                month = m.month,
+                amount = format_amount(value)
+            ));
+        }
+        svg.push_str(&format!(
+            r##"<text x="{x:.1}" y="{y}" text-anchor="middle" fill="#374151">{month}月</text>"##,
+            x = x + bar_width,
+            y = HEIGHT - 10.0,
+            month = m.month
+        ));
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Container that receives `svg` as inline markup once mounted
+///
+/// The markup goes into the DOM itself rather than an `<img>`, so the
+/// `<title>` elements show as tooltips. Labels are escaped when the chart
+/// is generated.
+pub fn svg_view(svg: String) -> View {
+    let id = format!("chart-{}", NEXT_CHART_ID.fetch_add(1, Ordering::Relaxed));
+    {
+        let id = id.clone();
+        // Runs after the current render has been mounted
+        spawn_task(async move {
+            set_inner_html(&id, &svg);
+        });
+    }
+    page!(|id: String| {
+        div {
+            id: id,
+        }
+    })(id)
+}
+
+/// Formats an amount with thousand separators
+pub fn format_amount(amount: i64) -> String {
+    let digits = amount.unsigned_abs().to_string();
+    let mut formatted = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            formatted.push(',');
+        }
+        formatted.push(c);
+    }
+    if amount < 0 {
+        format!("-{}", formatted)
+    } else {
+        formatted
+    }
+}
+
+fn polar(center: f64, radius: f64, angle: f64) -> (f64, f64) {
+    (center + radius * angle.cos(), center + radius * angle.sin())
+}
+
+/// Escapes text for use in SVG markup
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
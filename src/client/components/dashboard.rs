@@ -1,9 +1,11 @@
 //! Dashboard component
 //!
-//! Shows overview of income, expenses, and balance.
+//! Shows overview of income, expenses, and balance, each with its change
+//! from last month.
 
 use crate::client::api;
-use crate::shared::types::MonthlyReportInfo;
+use crate::client::components::sync_status::sync_status;
+use crate::shared::types::{ComparisonReportResponse, MonthlyReportResponse, PeriodTotals};
 use reinhardt_pages::component::View;
 use reinhardt_pages::page;
 use reinhardt_pages::reactive::hooks::use_state;
@@ -12,9 +14,10 @@ use reinhardt_pages::Signal;
 
 /// Dashboard overview component
 pub fn dashboard_overview() -> View {
-    let (report, set_report) = use_state(None::<MonthlyReportInfo>);
+    let (report, set_report) = use_state(None::<MonthlyReportResponse>);
     let (loading, set_loading) = use_state(true);
     let (error, set_error) = use_state(None::<String>);
+    let (comparison, set_comparison) = use_state(None::<ComparisonReportResponse>);
 
     // Get current year and month
     let now = chrono::Utc::now();
@@ -40,17 +43,34 @@ pub fn dashboard_overview() -> View {
         });
     }
 
+    // The cards stay usable without the comparison, so its errors are ignored
+    {
+        let this_month = format!("{:04}-{:02}", year, month);
+        let last_month = match month {
+            1 => format!("{:04}-12", year - 1),
+            _ => format!("{:04}-{:02}", year, month - 1),
+        };
+        spawn_task(async move {
+            if let Ok(c) = api::get_comparison_report(&last_month, &this_month).await {
+                set_comparison(Some(c));
+            }
+        });
+    }
+
     let report_signal = report.clone();
     let loading_signal = loading.clone();
     let error_signal = error.clone();
+    let comparison_signal = comparison.clone();
+    let sync_banner = sync_status();
 
-    page!(|report_signal: Signal<Option<MonthlyReportInfo>>, loading_signal: Signal<bool>, error_signal: Signal<Option<String>>| {
+    page!(|report_signal: Signal<Option<MonthlyReportResponse>>, loading_signal: Signal<bool>, error_signal: Signal<Option<String>>, comparison_signal: Signal<Option<ComparisonReportResponse>>, sync_banner: View| {
         div {
             class: "container mx-auto px-4 py-8",
             h1 {
                 class: "text-3xl font-bold mb-8 text-center",
                 "家計簿ダッシュボード"
             }
+            { sync_banner }
             watch {
                 if loading_signal.get() {
                     div {
@@ -82,6 +102,10 @@ pub fn dashboard_overview() -> View {
                                 class: "text-3xl font-bold text-green-600 mt-2",
                                 { format!("¥{}", format_amount(r.total_income)) }
                             }
+                            p {
+                                class: "text-sm text-gray-600 mt-1",
+                                { vs_last_month(&comparison_signal.get(), |t| t.total_income) }
+                            }
                         }
                         // Expense card
                         div {
@@ -94,6 +118,10 @@ pub fn dashboard_overview() -> View {
                                 class: "text-3xl font-bold text-red-600 mt-2",
                                 { format!("¥{}", format_amount(r.total_expense)) }
                             }
+                            p {
+                                class: "text-sm text-gray-600 mt-1",
+                                { vs_last_month(&comparison_signal.get(), |t| t.total_expense) }
+                            }
                         }
                         // Balance card
                         div {
@@ -106,6 +134,10 @@ pub fn dashboard_overview() -> View {
                                 class: if r.net_balance >= 0 { "text-3xl font-bold text-blue-600 mt-2" } else { "text-3xl font-bold text-yellow-600 mt-2" },
                                 { format!("¥{}", format_amount(r.net_balance)) }
                             }
+                            p {
+                                class: "text-sm text-gray-600 mt-1",
+                                { vs_last_month(&comparison_signal.get(), |t| t.net_balance) }
+                            }
                         }
                     }
                 }
@@ -123,9 +155,54 @@ pub fn dashboard_overview() -> View {
                     class: "bg-green-500 hover:bg-green-700 text-white font-bold py-2 px-4 rounded",
                     "新規取引"
                 }
+                a {
+                    href: "/reports",
+                    class: "bg-purple-500 hover:bg-purple-700 text-white font-bold py-2 px-4 rounded",
+                    "レポート"
+                }
+                a {
+                    href: "/categories",
+                    class: "bg-gray-500 hover:bg-gray-700 text-white font-bold py-2 px-4 rounded",
+                    "カテゴリ管理"
+                }
             }
         }
-    })(report_signal, loading_signal, error_signal)
+    })(
+        report_signal,
+        loading_signal,
+        error_signal,
+        comparison_signal,
+        sync_banner,
+    )
+}
+
+/// Arrow and amount of the change of one total from last month, e.g.
+/// "先月比 ↑ ¥12,000 (+8.5%)"; empty until the comparison has loaded
+fn vs_last_month(
+    comparison: &Option<ComparisonReportResponse>,
+    total: fn(&PeriodTotals) -> i64,
+) -> String {
+    let Some(c) = comparison else {
+        return String::new();
+    };
+    let (base, target) = (total(&c.base), total(&c.target));
+    let change = target - base;
+    let arrow = match change.signum() {
+        1 => "↑",
+        -1 => "↓",
+        _ => "→",
+    };
+    let percent = if base == 0 {
+        String::new()
+    } else {
+        format!(" ({:+.1}%)", change as f64 / base.abs() as f64 * 100.0)
+    };
+    format!(
+        "先月比 {} ¥{}{}",
+        arrow,
+        format_amount(change.abs()),
+        percent
+    )
 }
 
 /// Format amount with thousand separators
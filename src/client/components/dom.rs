@@ -0,0 +1,44 @@
+//! DOM helpers shared by components
+
+use wasm_bindgen::JsCast;
+
+/// Reads the value of the input, select or textarea that fired an event
+pub fn event_value(event: &web_sys::Event) -> String {
+    let Some(target) = event.target() else {
+        return String::new();
+    };
+    if let Some(input) = target.dyn_ref::<web_sys::HtmlInputElement>() {
+        input.value()
+    } else if let Some(select) = target.dyn_ref::<web_sys::HtmlSelectElement>() {
+        select.value()
+    } else if let Some(textarea) = target.dyn_ref::<web_sys::HtmlTextAreaElement>() {
+        textarea.value()
+    } else {
+        String::new()
+    }
+}
+
+/// Shows a browser confirmation dialog
+pub fn confirm(message: &str) -> bool {
+    web_sys::window()
+        .and_then(|w| w.confirm_with_message(message).ok())
+        .unwrap_or(false)
+}
+
+/// Navigates to another client route with a full page load
+pub fn navigate(path: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().set_href(path);
+    }
+}
+
+/// Replaces the contents of the element with `id` by `html`; does nothing
+/// when no such element is mounted
+pub fn set_inner_html(id: &str, html: &str) {
+    if let Some(element) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.get_element_by_id(id))
+    {
+        element.set_inner_html(html);
+    }
+}
@@ -0,0 +1,263 @@
+//! Reports component
+//!
+//! Shows the monthly category breakdown as donut charts and the yearly
+//! income/expense trend as a bar chart, with month and year navigation.
+
+use crate::client::api;
+use crate::client::components::charts::{
+    donut_chart, format_amount, monthly_bar_chart, palette_color, svg_view, Slice,
+};
+use crate::shared::types::{CategoryResponse, CategorySummary, MonthlyReportResponse, YearlyReportResponse};
+use chrono::Datelike;
+use reinhardt_pages::component::View;
+use reinhardt_pages::page;
+use reinhardt_pages::reactive::hooks::use_state;
+use reinhardt_pages::spawn::spawn_task;
+use reinhardt_pages::Signal;
+use std::collections::HashMap;
+
+/// Selected report period
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Period {
+    year: i32,
+    month: u32,
+}
+
+impl Period {
+    fn current() -> Self {
+        let today = chrono::Local::now().date_naive();
+        Self {
+            year: today.year(),
+            month: today.month(),
+        }
+    }
+
+    fn shift_months(self, offset: i32) -> Self {
+        let index = self.year * 12 + self.month as i32 - 1 + offset;
+        Self {
+            year: index.div_euclid(12),
+            month: index.rem_euclid(12) as u32 + 1,
+        }
+    }
+}
+
+/// Loaded report data for a period
+#[derive(Debug, Clone)]
+struct ReportData {
+    monthly: MonthlyReportResponse,
+    yearly: YearlyReportResponse,
+}
+
+/// Reports page component
+pub fn reports_view() -> View {
+    let (period, set_period) = use_state(Period::current());
+    let (data, set_data) = use_state(None::<ReportData>);
+    let (categories, set_categories) = use_state(HashMap::<i64, CategoryResponse>::new());
+    let (loading, set_loading) = use_state(true);
+    let (error, set_error) = use_state(None::<String>);
+
+    {
+        let set_categories = set_categories.clone();
+        spawn_task(async move {
+            // Only used for slice colors
+            if let Ok(cats) = api::get_categories().await {
+                set_categories(cats.into_iter().map(|c| (c.id, c)).collect());
+            }
+        });
+    }
+
+    let load = {
+        let set_period = set_period.clone();
+        let set_data = set_data.clone();
+        let set_loading = set_loading.clone();
+        let set_error = set_error.clone();
+        move |p: Period| {
+            set_period(p);
+            set_loading(true);
+            let set_data = set_data.clone();
+            let set_loading = set_loading.clone();
+            let set_error = set_error.clone();
+            spawn_task(async move {
+                let monthly = api::get_monthly_report(p.year, p.month).await;
+                let yearly = api::get_yearly_report(p.year).await;
+                match (monthly, yearly) {
+                    (Ok(monthly), Ok(yearly)) => {
+                        set_data(Some(ReportData { monthly, yearly }));
+                        set_error(None);
+                    }
+                    (Err(e), _) | (_, Err(e)) => set_error(Some(e)),
+                }
+                set_loading(false);
+            });
+        }
+    };
+    load(period.get());
+
+    let navigate_by = |offset: i32| {
+        let period = period.clone();
+        let load = load.clone();
+        move |_event: web_sys::Event| load(period.get().shift_months(offset))
+    };
+    let on_prev_year = navigate_by(-12);
+    let on_prev_month = navigate_by(-1);
+    let on_next_month = navigate_by(1);
+    let on_next_year = navigate_by(12);
+
+    page!(|period: Signal<Period>, data: Signal<Option<ReportData>>, categories: Signal<HashMap<i64, CategoryResponse>>, loading: Signal<bool>, error: Signal<Option<String>>| {
+        div {
+            class: "container mx-auto px-4 py-8",
+            h1 {
+                class: "text-3xl font-bold mb-8 text-center",
+                "レポート"
+            }
+            // Period navigation
+            div {
+                class: "flex justify-center items-center space-x-2 mb-8",
+                button {
+                    r#type: "button",
+                    class: "bg-gray-200 hover:bg-gray-300 py-1 px-3 rounded",
+                    @click: on_prev_year.clone(),
+                    "« 前年"
+                }
+                button {
+                    r#type: "button",
+                    class: "bg-gray-200 hover:bg-gray-300 py-1 px-3 rounded",
+                    @click: on_prev_month.clone(),
+                    "‹ 前月"
+                }
+                watch {
+                    span {
+                        class: "text-xl font-semibold px-4",
+                        { format!("{}年{}月", period.get().year, period.get().month) }
+                    }
+                }
+                button {
+                    r#type: "button",
+                    class: "bg-gray-200 hover:bg-gray-300 py-1 px-3 rounded",
+                    @click: on_next_month.clone(),
+                    "翌月 ›"
+                }
+                button {
+                    r#type: "button",
+                    class: "bg-gray-200 hover:bg-gray-300 py-1 px-3 rounded",
+                    @click: on_next_year.clone(),
+                    "翌年 »"
+                }
+            }
+            watch {
+                if loading.get() {
+                    div {
+                        class: "text-center py-8",
+                        div {
+                            class: "inline-block animate-spin rounded-full h-8 w-8 border-b-2 border-blue-500",
+                        }
+                        p {
+                            class: "mt-2 text-gray-600",
+                            "読み込み中..."
+                        }
+                    }
+                } else if let Some(err) = error.get() {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                        { format!("エラー: {}", err) }
+                    }
+                } else if let Some(d) = data.get() {
+                    div {
+                        class: "grid grid-cols-1 md:grid-cols-2 gap-6 mb-8",
+                        { category_donut("支出の内訳", &d.monthly.expense_by_category, d.monthly.total_expense, &categories.get()) }
+                        { category_donut("収入の内訳", &d.monthly.income_by_category, d.monthly.total_income, &categories.get()) }
+                    }
+                    div {
+                        class: "bg-white rounded-lg p-6 shadow overflow-x-auto",
+                        h2 {
+                            class: "text-lg font-semibold mb-4",
+                            { format!("{}年の月別収支", d.yearly.year) }
+                        }
+                        { svg_view(monthly_bar_chart(&d.yearly.monthly_summary)) }
+                        div {
+                            class: "flex space-x-4 mt-2 text-sm",
+                            span { class: "text-green-600", "■ 収入" }
+                            span { class: "text-red-600", "■ 支出" }
+                        }
+                    }
+                }
+            }
+            // Back to dashboard link
+            div {
+                class: "mt-8",
+                a {
+                    href: "/",
+                    class: "text-blue-500 hover:underline",
+                    "← ダッシュボードに戻る"
+                }
+            }
+        }
+    })(period, data, categories, loading, error)
+}
+
+/// Card with a donut chart and legend for one side of the monthly report
+fn category_donut(
+    title: &str,
+    summaries: &[CategorySummary],
+    total: i64,
+    categories: &HashMap<i64, CategoryResponse>,
+) -> View {
+    let mut summaries = summaries.to_vec();
+    summaries.sort_by(|a, b| b.total_amount.cmp(&a.total_amount));
+
+    let slices: Vec<Slice> = summaries
+        .iter()
+        .enumerate()
+        .map(|(i, s)| Slice {
+            label: s.category_name.clone(),
+            value: s.total_amount,
+            color: Some(
+                categories
+                    .get(&s.category_id)
+                    .and_then(|c| c.color.clone())
+                    .unwrap_or_else(|| palette_color(i).to_string()),
+            ),
+        })
+        .collect();
+
+    let chart = svg_view(donut_chart(&slices, &format!("¥{}", format_amount(total))));
+    let title = title.to_string();
+    let legend = View::fragment(
+        slices
+            .into_iter()
+            .map(|slice| {
+                let style = format!("color: {}", slice.color.unwrap_or_default());
+                let label = slice.label;
+                let amount = format!("¥{}", format_amount(slice.value));
+                page!(|style: String, label: String, amount: String| {
+                    li {
+                        class: "flex justify-between text-sm",
+                        span {
+                            span { style: style, "● " }
+                            { label }
+                        }
+                        span { { amount } }
+                    }
+                })(style, label, amount)
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    page!(|title: String, chart: View, legend: View| {
+        div {
+            class: "bg-white rounded-lg p-6 shadow",
+            h2 {
+                class: "text-lg font-semibold mb-4",
+                { title }
+            }
+            div {
+                class: "flex flex-col md:flex-row items-center gap-6",
+                { chart }
+                ul {
+                    class: "flex-1 w-full space-y-1",
+                    { legend }
+                }
+            }
+        }
+    })(title, chart, legend)
+}
@@ -0,0 +1,88 @@
+//! Offline sync status banner
+//!
+//! Shows how many changes are waiting to be sent and lists changes the
+//! server rejected during replay.
+
+use crate::client::sync::{self, SyncStatus};
+use reinhardt_pages::component::View;
+use reinhardt_pages::page;
+use reinhardt_pages::reactive::hooks::use_state;
+use reinhardt_pages::spawn::spawn_task;
+use reinhardt_pages::Signal;
+
+/// Sync status banner; renders nothing when there is nothing to report
+pub fn sync_status() -> View {
+    let (status, set_status) = use_state(SyncStatus::default());
+
+    {
+        let set_status = set_status.clone();
+        spawn_task(async move {
+            set_status(sync::status().await);
+        });
+    }
+
+    let on_sync = {
+        let set_status = set_status.clone();
+        move |_event: web_sys::Event| {
+            let set_status = set_status.clone();
+            spawn_task(async move {
+                sync::replay().await;
+                set_status(sync::status().await);
+            });
+        }
+    };
+
+    let on_dismiss = {
+        let set_status = set_status.clone();
+        move |_event: web_sys::Event| {
+            let set_status = set_status.clone();
+            spawn_task(async move {
+                let _ = sync::clear_conflicts().await;
+                set_status(sync::status().await);
+            });
+        }
+    };
+
+    page!(|status: Signal<SyncStatus>| {
+        div {
+            watch {
+                if status.get().pending > 0 {
+                    div {
+                        class: "bg-yellow-100 border border-yellow-400 text-yellow-800 px-4 py-3 rounded mb-4 flex justify-between items-center",
+                        span { { format!("未送信の変更が{}件あります（オンラインになると自動で送信されます）", status.get().pending) } }
+                        button {
+                            r#type: "button",
+                            class: "bg-yellow-500 hover:bg-yellow-600 text-white py-1 px-3 rounded",
+                            @click: on_sync.clone(),
+                            "今すぐ同期"
+                        }
+                    }
+                }
+                if !status.get().conflicts.is_empty() {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded mb-4",
+                        p {
+                            class: "font-semibold mb-2",
+                            "反映できなかった変更があります"
+                        }
+                        ul {
+                            class: "list-disc list-inside text-sm mb-2",
+                            { View::fragment(status.get().conflicts.iter().map(|c| {
+                                let text = format!("{}: {}", c.summary, c.reason);
+                                page!(|text: String| {
+                                    li { { text } }
+                                })(text)
+                            }).collect::<Vec<_>>()) }
+                        }
+                        button {
+                            r#type: "button",
+                            class: "text-sm text-red-700 hover:underline",
+                            @click: on_dismiss.clone(),
+                            "閉じる"
+                        }
+                    }
+                }
+            }
+        }
+    })(status)
+}
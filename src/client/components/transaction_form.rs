@@ -0,0 +1,418 @@
+//! Transaction form component
+//!
+//! Creates a new transaction or edits and deletes an existing one.
+
+use crate::client::api;
+use crate::client::components::dom::{confirm, event_value, navigate};
+use crate::shared::types::{
+    CategoryResponse, CategoryType, CreateTransactionRequest, TransactionType,
+    UpdateTransactionRequest,
+};
+use chrono::{NaiveDate, NaiveTime};
+use reinhardt_pages::component::View;
+use reinhardt_pages::page;
+use reinhardt_pages::reactive::hooks::use_state;
+use reinhardt_pages::spawn::spawn_task;
+use reinhardt_pages::Signal;
+
+/// Editable form values
+#[derive(Debug, Clone, PartialEq)]
+struct FormValues {
+    transaction_type: TransactionType,
+    amount: String,
+    category_id: Option<i64>,
+    description: String,
+    /// Date as `YYYY-MM-DD` (the `<input type="date">` format)
+    date: String,
+}
+
+impl Default for FormValues {
+    fn default() -> Self {
+        Self {
+            transaction_type: TransactionType::Expense,
+            amount: String::new(),
+            category_id: None,
+            description: String::new(),
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Transaction create/edit form component
+///
+/// With `transaction_id` set, loads that transaction for editing and shows a
+/// delete button. The type of an existing transaction cannot be changed.
+pub fn transaction_form(transaction_id: Option<i64>) -> View {
+    let (values, set_values) = use_state(FormValues::default());
+    let (categories, set_categories) = use_state(Vec::<CategoryResponse>::new());
+    // Set once the user picks a category, so suggestions stop overriding it
+    let (category_touched, set_category_touched) = use_state(transaction_id.is_some());
+    let (loading, set_loading) = use_state(true);
+    let (saving, set_saving) = use_state(false);
+    let (error, set_error) = use_state(None::<String>);
+
+    {
+        let set_values = set_values.clone();
+        let set_categories = set_categories.clone();
+        let set_loading = set_loading.clone();
+        let set_error = set_error.clone();
+
+        spawn_task(async move {
+            match api::get_categories().await {
+                Ok(cats) => set_categories(cats),
+                Err(e) => set_error(Some(e)),
+            }
+            if let Some(id) = transaction_id {
+                match api::get_transaction(id).await {
+                    Ok(tx) => set_values(FormValues {
+                        transaction_type: tx.transaction_type,
+                        amount: tx.amount.to_string(),
+                        category_id: Some(tx.category_id),
+                        description: tx.description,
+                        date: tx.transaction_date,
+                    }),
+                    Err(e) => set_error(Some(e)),
+                }
+            }
+            set_loading(false);
+        });
+    }
+
+    let on_type_change = {
+        let values = values.clone();
+        let set_values = set_values.clone();
+        move |event: web_sys::Event| {
+            let transaction_type = match event_value(&event).as_str() {
+                "income" => TransactionType::Income,
+                _ => TransactionType::Expense,
+            };
+            let mut v = values.get();
+            if v.transaction_type != transaction_type {
+                v.transaction_type = transaction_type;
+                // The picked category belongs to the other type
+                v.category_id = None;
+                set_values(v);
+            }
+        }
+    };
+
+    let on_amount_input = {
+        let values = values.clone();
+        let set_values = set_values.clone();
+        move |event: web_sys::Event| {
+            let mut v = values.get();
+            v.amount = event_value(&event);
+            set_values(v);
+        }
+    };
+
+    let on_category_change = {
+        let values = values.clone();
+        let set_values = set_values.clone();
+        let set_category_touched = set_category_touched.clone();
+        move |event: web_sys::Event| {
+            let mut v = values.get();
+            v.category_id = event_value(&event).parse().ok();
+            set_values(v);
+            set_category_touched(true);
+        }
+    };
+
+    let on_description_change = {
+        let values = values.clone();
+        let set_values = set_values.clone();
+        let category_touched = category_touched.clone();
+        move |event: web_sys::Event| {
+            let mut v = values.get();
+            v.description = event_value(&event);
+            set_values(v.clone());
+
+            if category_touched.get() || v.description.trim().is_empty() {
+                return;
+            }
+            // Pre-select the top suggestion learned from past transactions
+            let values = values.clone();
+            let set_values = set_values.clone();
+            spawn_task(async move {
+                let amount = v.amount.trim().parse().ok();
+                if let Ok(suggestions) =
+                    api::suggest_category(&v.description, amount, v.transaction_type).await
+                    && let Some(top) = suggestions.first()
+                {
+                    let mut current = values.get();
+                    if current.transaction_type == v.transaction_type {
+                        current.category_id = Some(top.category.id);
+                        set_values(current);
+                    }
+                }
+            });
+        }
+    };
+
+    let on_date_change = {
+        let values = values.clone();
+        let set_values = set_values.clone();
+        move |event: web_sys::Event| {
+            let mut v = values.get();
+            v.date = event_value(&event);
+            set_values(v);
+        }
+    };
+
+    let on_submit = {
+        let values = values.clone();
+        let set_saving = set_saving.clone();
+        let set_error = set_error.clone();
+        move |event: web_sys::Event| {
+            event.prevent_default();
+            let v = values.get();
+
+            let Ok(amount) = v.amount.trim().parse::<i64>() else {
+                set_error(Some("金額を数値で入力してください".to_string()));
+                return;
+            };
+            let Some(category_id) = v.category_id else {
+                set_error(Some("カテゴリを選択してください".to_string()));
+                return;
+            };
+            // The API takes a full timestamp; the form only has a date
+            let Ok(date) = NaiveDate::parse_from_str(&v.date, "%Y-%m-%d") else {
+                set_error(Some("日付を入力してください".to_string()));
+                return;
+            };
+            let transaction_date = date.and_time(NaiveTime::MIN).and_utc();
+
+            set_saving(true);
+            set_error(None);
+            let set_saving = set_saving.clone();
+            let set_error = set_error.clone();
+            spawn_task(async move {
+                let result = match transaction_id {
+                    Some(id) => api::update_transaction(
+                        id,
+                        &UpdateTransactionRequest {
+                            amount: Some(amount),
+                            category_id: Some(category_id),
+                            description: Some(v.description.clone()),
+                            transaction_date: Some(transaction_date),
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .map(|_| ()),
+                    None => api::create_transaction(&CreateTransactionRequest {
+                        amount,
+                        category_id,
+                        description: v.description.clone(),
+                        transaction_date,
+                        transaction_type: v.transaction_type,
+                        account_id: None,
+                    })
+                    .await
+                    .map(|_| ()),
+                };
+                match result {
+                    Ok(()) => navigate("/transactions"),
+                    Err(e) => {
+                        // Server validation messages are shown as-is
+                        set_error(Some(e));
+                        set_saving(false);
+                    }
+                }
+            });
+        }
+    };
+
+    let on_delete = {
+        let set_error = set_error.clone();
+        move |_event: web_sys::Event| {
+            let Some(id) = transaction_id else {
+                return;
+            };
+            if !confirm("この取引を削除しますか？") {
+                return;
+            }
+            let set_error = set_error.clone();
+            spawn_task(async move {
+                match api::delete_transaction(id).await {
+                    Ok(()) => navigate("/transactions"),
+                    Err(e) => set_error(Some(e)),
+                }
+            });
+        }
+    };
+
+    let title = if transaction_id.is_some() { "取引の編集" } else { "新規取引" };
+    let is_edit = transaction_id.is_some();
+
+    page!(|values: Signal<FormValues>, categories: Signal<Vec<CategoryResponse>>, loading: Signal<bool>, saving: Signal<bool>, error: Signal<Option<String>>, title: &str, is_edit: bool| {
+        div {
+            class: "container mx-auto px-4 py-8 max-w-xl",
+            h1 {
+                class: "text-3xl font-bold mb-8",
+                { title }
+            }
+            watch {
+                if let Some(err) = error.get() {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded mb-4",
+                        { err }
+                    }
+                }
+            }
+            watch {
+                if loading.get() {
+                    div {
+                        class: "text-center py-8",
+                        div {
+                            class: "inline-block animate-spin rounded-full h-8 w-8 border-b-2 border-blue-500",
+                        }
+                        p {
+                            class: "mt-2 text-gray-600",
+                            "読み込み中..."
+                        }
+                    }
+                } else {
+                    form {
+                        class: "bg-white shadow rounded-lg p-6 space-y-4",
+                        @submit: on_submit.clone(),
+                        // Type
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 mb-1",
+                                "種別"
+                            }
+                            select {
+                                class: "w-full border rounded px-3 py-2",
+                                disabled: is_edit,
+                                @change: on_type_change.clone(),
+                                option {
+                                    value: "expense",
+                                    selected: values.get().transaction_type == TransactionType::Expense,
+                                    "支出"
+                                }
+                                option {
+                                    value: "income",
+                                    selected: values.get().transaction_type == TransactionType::Income,
+                                    "収入"
+                                }
+                            }
+                        }
+                        // Amount
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 mb-1",
+                                "金額（円）"
+                            }
+                            input {
+                                r#type: "number",
+                                min: "1",
+                                step: "1",
+                                required: true,
+                                class: "w-full border rounded px-3 py-2",
+                                value: values.get().amount,
+                                @input: on_amount_input.clone(),
+                            }
+                        }
+                        // Description
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 mb-1",
+                                "説明"
+                            }
+                            input {
+                                r#type: "text",
+                                maxlength: "500",
+                                class: "w-full border rounded px-3 py-2",
+                                value: values.get().description,
+                                @change: on_description_change.clone(),
+                            }
+                        }
+                        // Category, filtered by the selected type
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 mb-1",
+                                "カテゴリ"
+                            }
+                            select {
+                                class: "w-full border rounded px-3 py-2",
+                                required: true,
+                                @change: on_category_change.clone(),
+                                option {
+                                    value: "",
+                                    selected: values.get().category_id.is_none(),
+                                    "選択してください"
+                                }
+                                { View::fragment(categories_for(&categories.get(), values.get().transaction_type).into_iter().map(|cat| {
+                                    let selected = values.get().category_id == Some(cat.id);
+                                    let id = cat.id.to_string();
+                                    let name = cat.name.clone();
+                                    page!(|id: String, name: String, selected: bool| {
+                                        option {
+                                            value: id,
+                                            selected: selected,
+                                            { name }
+                                        }
+                                    })(id, name, selected)
+                                }).collect::<Vec<_>>()) }
+                            }
+                        }
+                        // Date
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 mb-1",
+                                "日付"
+                            }
+                            input {
+                                r#type: "date",
+                                required: true,
+                                class: "w-full border rounded px-3 py-2",
+                                value: values.get().date,
+                                @change: on_date_change.clone(),
+                            }
+                        }
+                        div {
+                            class: "flex justify-between items-center pt-4",
+                            button {
+                                r#type: "submit",
+                                disabled: saving.get(),
+                                class: "bg-blue-500 hover:bg-blue-700 text-white font-bold py-2 px-4 rounded disabled:opacity-50",
+                                { if saving.get() { "保存中..." } else { "保存" } }
+                            }
+                            if is_edit {
+                                button {
+                                    r#type: "button",
+                                    class: "bg-red-500 hover:bg-red-700 text-white font-bold py-2 px-4 rounded",
+                                    @click: on_delete.clone(),
+                                    "削除"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            // Back to list link
+            div {
+                class: "mt-8",
+                a {
+                    href: "/transactions",
+                    class: "text-blue-500 hover:underline",
+                    "← 取引一覧に戻る"
+                }
+            }
+        }
+    })(values, categories, loading, saving, error, title, is_edit)
+}
+
+/// Returns the categories matching a transaction type
+fn categories_for(categories: &[CategoryResponse], transaction_type: TransactionType) -> Vec<CategoryResponse> {
+    let category_type = match transaction_type {
+        TransactionType::Income => CategoryType::Income,
+        TransactionType::Expense => CategoryType::Expense,
+    };
+    categories
+        .iter()
+        .filter(|c| c.category_type == category_type)
+        .cloned()
+        .collect()
+}
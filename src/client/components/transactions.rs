@@ -3,25 +3,34 @@
 //! Shows list of transactions and allows creating new ones.
 
 use crate::client::api;
-use crate::shared::types::{TransactionInfo, TransactionType};
+use crate::client::components::categories::category_badge;
+use crate::client::components::sync_status::sync_status;
+use crate::shared::types::{CategoryResponse, TransactionResponse, TransactionType};
 use reinhardt_pages::component::View;
 use reinhardt_pages::page;
 use reinhardt_pages::reactive::hooks::use_state;
 use reinhardt_pages::spawn::spawn_task;
 use reinhardt_pages::Signal;
+use std::collections::HashMap;
 
 /// Transaction list component
 pub fn transactions_list() -> View {
-    let (transactions, set_transactions) = use_state(Vec::<TransactionInfo>::new());
+    let (transactions, set_transactions) = use_state(Vec::<TransactionResponse>::new());
+    let (categories, set_categories) = use_state(HashMap::<i64, CategoryResponse>::new());
     let (loading, set_loading) = use_state(true);
     let (error, set_error) = use_state(None::<String>);
 
     {
         let set_transactions = set_transactions.clone();
+        let set_categories = set_categories.clone();
         let set_loading = set_loading.clone();
         let set_error = set_error.clone();
 
         spawn_task(async move {
+            // Categories only decorate the table, so a failure is not fatal
+            if let Ok(cats) = api::get_categories().await {
+                set_categories(cats.into_iter().map(|c| (c.id, c)).collect());
+            }
             match api::get_transactions().await {
                 Ok(txs) => {
                     set_transactions(txs);
@@ -36,10 +45,12 @@ pub fn transactions_list() -> View {
     }
 
     let transactions_signal = transactions.clone();
+    let categories_signal = categories.clone();
     let loading_signal = loading.clone();
     let error_signal = error.clone();
+    let sync_banner = sync_status();
 
-    page!(|transactions_signal: Signal<Vec<TransactionInfo>>, loading_signal: Signal<bool>, error_signal: Signal<Option<String>>| {
+    page!(|transactions_signal: Signal<Vec<TransactionResponse>>, categories_signal: Signal<HashMap<i64, CategoryResponse>>, loading_signal: Signal<bool>, error_signal: Signal<Option<String>>, sync_banner: View| {
         div {
             class: "container mx-auto px-4 py-8",
             div {
@@ -54,6 +65,7 @@ pub fn transactions_list() -> View {
                     "+ 新規取引"
                 }
             }
+            { sync_banner }
             watch {
                 if loading_signal.get() {
                     div {
@@ -99,7 +111,7 @@ pub fn transactions_list() -> View {
                                     }
                                     th {
                                         class: "px-6 py-3 text-left text-xs font-medium text-gray-500 uppercase tracking-wider",
-                                        "種別"
+                                        "カテゴリ"
                                     }
                                     th {
                                         class: "px-6 py-3 text-right text-xs font-medium text-gray-500 uppercase tracking-wider",
@@ -110,15 +122,20 @@ pub fn transactions_list() -> View {
                             tbody {
                                 class: "bg-white divide-y divide-gray-200",
                                 { View::fragment(transactions_signal.get().iter().map(|tx| {
+                                    let edit_href = format!("/transactions/{}", tx.id);
                                     let date = tx.transaction_date.clone();
-                                    let desc = tx.description.clone();
+                                    // Negative ids are creates waiting in the offline queue
+                                    let desc = if tx.id < 0 {
+                                        format!("{}（未送信）", tx.description)
+                                    } else {
+                                        tx.description.clone()
+                                    };
                                     let amount = tx.amount;
                                     let is_income = matches!(tx.transaction_type, TransactionType::Income);
-                                    let type_label = if is_income { "収入" } else { "支出" };
-                                    let type_class = if is_income { "text-green-600" } else { "text-red-600" };
+                                    let badge = category_badge(categories_signal.get().get(&tx.category_id));
                                     let amount_class = if is_income { "text-green-600 font-semibold" } else { "text-red-600 font-semibold" };
 
-                                    page!(|date: String, desc: String, amount: i64, type_label: &str, type_class: &str, amount_class: &str| {
+                                    page!(|edit_href: String, date: String, desc: String, amount: i64, badge: View, amount_class: &str| {
                                         tr {
                                             td {
                                                 class: "px-6 py-4 whitespace-nowrap text-sm text-gray-900",
@@ -126,18 +143,22 @@ pub fn transactions_list() -> View {
                                             }
                                             td {
                                                 class: "px-6 py-4 whitespace-nowrap text-sm text-gray-900",
-                                                { desc }
+                                                a {
+                                                    href: edit_href,
+                                                    class: "text-blue-600 hover:underline",
+                                                    { desc }
+                                                }
                                             }
                                             td {
-                                                class: format!("px-6 py-4 whitespace-nowrap text-sm {}", type_class),
-                                                { type_label }
+                                                class: "px-6 py-4 whitespace-nowrap text-sm",
+                                                { badge }
                                             }
                                             td {
                                                 class: format!("px-6 py-4 whitespace-nowrap text-sm text-right {}", amount_class),
                                                 { format!("¥{}", amount) }
                                             }
                                         }
-                                    })(date, desc, amount, type_label, type_class, amount_class)
+                                    })(edit_href, date, desc, amount, badge, amount_class)
                                 }).collect::<Vec<_>>()) }
                             }
                         }
@@ -154,5 +175,5 @@ pub fn transactions_list() -> View {
                 }
             }
         }
-    })(transactions_signal, loading_signal, error_signal)
+    })(transactions_signal, categories_signal, loading_signal, error_signal, sync_banner)
 }
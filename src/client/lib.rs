@@ -5,7 +5,7 @@
 use reinhardt_pages::dom::Element;
 use wasm_bindgen::prelude::*;
 
-use super::router;
+use super::{router, sync};
 
 pub use router::{init_global_router, with_router};
 
@@ -14,6 +14,9 @@ pub fn main() -> Result<(), JsValue> {
     // Set panic hook for better error messages in browser console
     console_error_panic_hook::set_once();
 
+    // Send changes queued while offline, now and on reconnect
+    sync::start();
+
     // Initialize router
     router::init_global_router();
 
@@ -13,3 +13,27 @@ pub fn dashboard_page() -> View {
 pub fn transactions_page() -> View {
     crate::client::components::transactions::transactions_list()
 }
+
+/// Category management page
+pub fn categories_page() -> View {
+    crate::client::components::categories::categories_manager()
+}
+
+/// Reports page
+pub fn reports_page() -> View {
+    crate::client::components::reports::reports_view()
+}
+
+/// New transaction page
+pub fn transaction_new_page() -> View {
+    crate::client::components::transaction_form::transaction_form(None)
+}
+
+/// Edit transaction page (`/transactions/{id}`)
+pub fn transaction_edit_page() -> View {
+    let id = crate::client::router::path_param("id").and_then(|id| id.parse().ok());
+    match id {
+        Some(id) => crate::client::components::transaction_form::transaction_form(Some(id)),
+        None => crate::client::router::not_found_page(),
+    }
+}
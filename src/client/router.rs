@@ -23,6 +23,15 @@ pub fn init_global_router() {
         Route::new(PathPattern::new("/transactions/"), || {
             pages::transactions_page()
         }),
+        // "new" must come before the parameterized route
+        Route::new(PathPattern::new("/transactions/new"), || {
+            pages::transaction_new_page()
+        }),
+        Route::new(PathPattern::new("/transactions/{id}"), || {
+            pages::transaction_edit_page()
+        }),
+        Route::new(PathPattern::new("/categories"), || pages::categories_page()),
+        Route::new(PathPattern::new("/reports"), || pages::reports_page()),
     ]);
 
     ROUTER.with(|r| {
@@ -42,6 +51,11 @@ where
     })
 }
 
+/// Returns the `{name}` parameter matched by the current route
+pub fn path_param(name: &str) -> Option<String> {
+    with_router(|router| router.current_params().get().get(name).cloned())
+}
+
 /// 404 Not Found page
 pub fn not_found_page() -> View {
     page!(|| {
@@ -0,0 +1,96 @@
+//! IndexedDB-backed local store
+//!
+//! A small key-value store holding JSON documents (cached categories and
+//! transactions, the offline outbox and sync conflicts). All values go
+//! through one object store so no schema migration is needed when new
+//! keys are added.
+
+use js_sys::{Function, Promise};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbRequest, IdbTransactionMode};
+
+const DB_NAME: &str = "kakeboor";
+const DB_VERSION: u32 = 1;
+const STORE_NAME: &str = "kv";
+
+/// Cached category list
+pub const CATEGORIES_KEY: &str = "categories";
+/// Cached recent transactions
+pub const TRANSACTIONS_KEY: &str = "transactions";
+/// Queued offline changes
+pub const OUTBOX_KEY: &str = "outbox";
+/// Changes that could not be replayed
+pub const CONFLICTS_KEY: &str = "conflicts";
+
+/// Waits for an IndexedDB request to finish and returns its result
+async fn wait(request: &IdbRequest) -> Result<JsValue, String> {
+    let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+        let req = request.clone();
+        let on_success = Closure::once_into_js(move |_: web_sys::Event| {
+            let _ = resolve.call1(&JsValue::NULL, &req.result().unwrap_or(JsValue::UNDEFINED));
+        });
+        let on_error = Closure::once_into_js(move |_: web_sys::Event| {
+            let _ = reject.call0(&JsValue::NULL);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    });
+    JsFuture::from(promise)
+        .await
+        .map_err(|_| "IndexedDB request failed".to_string())
+}
+
+/// Opens the database, creating the object store on first use
+async fn open() -> Result<IdbDatabase, String> {
+    let factory = web_sys::window()
+        .and_then(|w| w.indexed_db().ok().flatten())
+        .ok_or_else(|| "IndexedDB is not available".to_string())?;
+    let request = factory
+        .open_with_u32(DB_NAME, DB_VERSION)
+        .map_err(|_| "Failed to open IndexedDB".to_string())?;
+
+    let upgrade_request = request.clone();
+    let on_upgrade = Closure::once_into_js(move |_: web_sys::Event| {
+        if let Ok(db) = upgrade_request.result().and_then(|r| r.dyn_into::<IdbDatabase>())
+            && !db.object_store_names().contains(STORE_NAME)
+        {
+            let _ = db.create_object_store(STORE_NAME);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+    wait(&request)
+        .await?
+        .dyn_into::<IdbDatabase>()
+        .map_err(|_| "Failed to open IndexedDB".to_string())
+}
+
+/// Reads and deserializes a value; `None` if it is missing or unreadable
+pub async fn get<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let db = open().await.ok()?;
+    let store = db
+        .transaction_with_str(STORE_NAME)
+        .and_then(|tx| tx.object_store(STORE_NAME))
+        .ok()?;
+    let request = store.get(&JsValue::from_str(key)).ok()?;
+    let value = wait(&request).await.ok()?.as_string()?;
+    serde_json::from_str(&value).ok()
+}
+
+/// Serializes and stores a value
+pub async fn set<T: Serialize>(key: &str, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    let db = open().await?;
+    let store = db
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)
+        .and_then(|tx| tx.object_store(STORE_NAME))
+        .map_err(|_| "Failed to write to IndexedDB".to_string())?;
+    let request = store
+        .put_with_key(&JsValue::from_str(&json), &JsValue::from_str(key))
+        .map_err(|_| "Failed to write to IndexedDB".to_string())?;
+    wait(&request).await.map(|_| ())
+}
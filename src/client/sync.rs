@@ -0,0 +1,468 @@
+//! Offline queue and sync
+//!
+//! Transaction changes made while the server is unreachable are kept in an
+//! outbox in IndexedDB and replayed in order when the connection returns.
+//! Queued creates carry an idempotency key so a replay that the server
+//! already applied is not inserted twice. Edits and deletes remember the
+//! `updated_at` they were based on; if the transaction changed or vanished
+//! on the server in the meantime the change is not applied and is reported
+//! as a conflict instead. Once an edit is applied, later queued changes of
+//! the same transaction are rebased onto the server's new `updated_at`, so
+//! the user's own edits do not conflict with each other.
+
+use crate::client::api::{self, RequestError};
+use crate::client::store;
+use crate::shared::types::{
+    CreateTransactionRequest, TransactionResponse, UpdateTransactionRequest,
+};
+use reinhardt_pages::spawn::spawn_task;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+
+/// Number of transactions kept in the local cache
+const CACHED_TRANSACTION_LIMIT: usize = 200;
+
+thread_local! {
+    /// Set while the outbox is being replayed
+    static REPLAYING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A change made while offline, waiting to be sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum PendingChange {
+    Create {
+        /// Negative id shown in the UI until the server assigns one
+        local_id: i64,
+        idempotency_key: String,
+        request: CreateTransactionRequest,
+    },
+    Update {
+        /// Identifies the change in the outbox
+        #[serde(default)]
+        key: String,
+        id: i64,
+        /// Description at the time of the edit, for conflict messages
+        label: String,
+        base_updated_at: Option<String>,
+        request: UpdateTransactionRequest,
+    },
+    Delete {
+        #[serde(default)]
+        key: String,
+        id: i64,
+        label: String,
+        base_updated_at: Option<String>,
+    },
+}
+
+impl PendingChange {
+    fn key(&self) -> &str {
+        match self {
+            PendingChange::Create {
+                idempotency_key, ..
+            } => idempotency_key,
+            PendingChange::Update { key, .. } | PendingChange::Delete { key, .. } => key,
+        }
+    }
+
+    /// Moves an edit or delete queued on the same version as an applied
+    /// earlier edit onto the version that edit produced
+    fn rebase(&mut self, rebased: &HashMap<i64, (Option<String>, String)>) {
+        if let PendingChange::Update {
+            id,
+            base_updated_at,
+            ..
+        }
+        | PendingChange::Delete {
+            id,
+            base_updated_at,
+            ..
+        } = self
+            && let Some((old, new)) = rebased.get(id)
+            && base_updated_at == old
+        {
+            *base_updated_at = Some(new.clone());
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            PendingChange::Create { request, .. } => format!(
+                "「{}」¥{} の登録",
+                request.description, request.amount
+            ),
+            PendingChange::Update { label, .. } => format!("「{}」の編集", label),
+            PendingChange::Delete { label, .. } => format!("「{}」の削除", label),
+        }
+    }
+}
+
+/// A queued change the server did not accept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub summary: String,
+    pub reason: String,
+}
+
+/// Outbox state shown in the UI
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SyncStatus {
+    pub pending: usize,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+/// Generates a key identifying one create request across retries
+pub fn new_idempotency_key() -> String {
+    let random = (js_sys::Math::random() * u32::MAX as f64) as u32;
+    format!("{:x}-{:08x}", js_sys::Date::now() as u64, random)
+}
+
+/// Replays the outbox now and again whenever the browser comes back online
+pub fn start() {
+    spawn_task(async {
+        replay().await;
+    });
+
+    let on_online = Closure::<dyn FnMut()>::new(|| {
+        spawn_task(async {
+            replay().await;
+        });
+    });
+    if let Some(window) = web_sys::window() {
+        let _ = window
+            .add_event_listener_with_callback("online", on_online.as_ref().unchecked_ref());
+    }
+    // The listener lives as long as the page
+    on_online.forget();
+}
+
+async fn outbox() -> Vec<PendingChange> {
+    store::get(store::OUTBOX_KEY).await.unwrap_or_default()
+}
+
+async fn save_outbox(outbox: &[PendingChange]) -> Result<(), String> {
+    store::set(store::OUTBOX_KEY, &outbox).await
+}
+
+async fn cached_transactions() -> Vec<TransactionResponse> {
+    store::get(store::TRANSACTIONS_KEY).await.unwrap_or_default()
+}
+
+/// Returns the number of queued changes and the unresolved conflicts
+pub async fn status() -> SyncStatus {
+    SyncStatus {
+        pending: outbox().await.len(),
+        conflicts: store::get(store::CONFLICTS_KEY).await.unwrap_or_default(),
+    }
+}
+
+/// Forgets the reported conflicts
+pub async fn clear_conflicts() -> Result<(), String> {
+    store::set(store::CONFLICTS_KEY, &Vec::<SyncConflict>::new()).await
+}
+
+/// Gives changes queued before they had keys one, so they can be told
+/// apart from changes queued during a replay
+async fn assign_keys() {
+    let mut outbox = outbox().await;
+    let mut changed = false;
+    for change in &mut outbox {
+        if let PendingChange::Update { key, .. } | PendingChange::Delete { key, .. } = change
+            && key.is_empty()
+        {
+            *key = new_idempotency_key();
+            changed = true;
+        }
+    }
+    if changed {
+        let _ = save_outbox(&outbox).await;
+    }
+}
+
+/// Sends queued changes in order
+///
+/// Stops at the first change that fails because the server is unreachable.
+/// Changes queued while the replay runs are kept: only the ones that were
+/// sent or rejected are removed from the outbox afterwards. Returns `true`
+/// if the outbox is empty afterwards.
+pub async fn replay() -> bool {
+    if REPLAYING.with(|r| r.replace(true)) {
+        return false;
+    }
+
+    assign_keys().await;
+    let mut processed: HashSet<String> = HashSet::new();
+    // Transaction id -> (`updated_at` before the applied edit, after it)
+    let mut rebased: HashMap<i64, (Option<String>, String)> = HashMap::new();
+    let mut conflicts: Vec<SyncConflict> = store::get(store::CONFLICTS_KEY).await.unwrap_or_default();
+    for mut change in outbox().await {
+        // Keyed by the base the change was queued with, which later changes
+        // of the same transaction share
+        let queued_base = match &change {
+            PendingChange::Update {
+                base_updated_at, ..
+            } => base_updated_at.clone(),
+            _ => None,
+        };
+        change.rebase(&rebased);
+        match replay_one(&change).await {
+            Ok(Some(updated)) => {
+                rebased.insert(updated.id, (queued_base, updated.updated_at.clone()));
+                remember_transaction(&updated).await;
+            }
+            Ok(None) => {}
+            Err(RequestError::Offline) => break,
+            Err(RequestError::Rejected { message, .. }) => conflicts.push(SyncConflict {
+                summary: change.summary(),
+                reason: message,
+            }),
+        }
+        processed.insert(change.key().to_string());
+    }
+
+    // Re-read: changes may have been queued while the requests were out
+    let mut remaining = outbox().await;
+    remaining.retain(|change| !processed.contains(change.key()));
+    for change in &mut remaining {
+        change.rebase(&rebased);
+    }
+    let _ = save_outbox(&remaining).await;
+    let _ = store::set(store::CONFLICTS_KEY, &conflicts).await;
+    REPLAYING.with(|r| r.set(false));
+    remaining.is_empty()
+}
+
+/// Sends one change; returns the transaction as the server has it after an
+/// edit
+async fn replay_one(change: &PendingChange) -> Result<Option<TransactionResponse>, RequestError> {
+    match change {
+        PendingChange::Create {
+            idempotency_key,
+            request,
+            ..
+        } => api::send_create_transaction(request, idempotency_key)
+            .await
+            .map(|_| None),
+        PendingChange::Update {
+            id,
+            base_updated_at,
+            request,
+            ..
+        } => {
+            check_unchanged(*id, base_updated_at.as_deref()).await?;
+            api::send_update_transaction(*id, request).await.map(Some)
+        }
+        PendingChange::Delete {
+            id,
+            base_updated_at,
+            ..
+        } => match check_unchanged(*id, base_updated_at.as_deref()).await {
+            // Already gone, which is what the delete wanted
+            Err(RequestError::Rejected { status: 404, .. }) => Ok(None),
+            Err(e) => Err(e),
+            Ok(()) => api::send_delete_transaction(*id).await.map(|_| None),
+        },
+    }
+}
+
+/// Fails if the transaction was deleted or edited on the server since the
+/// offline change was made
+async fn check_unchanged(id: i64, base_updated_at: Option<&str>) -> Result<(), RequestError> {
+    let current = match api::fetch_transaction(id).await {
+        Err(RequestError::Rejected { status: 404, .. }) => {
+            return Err(RequestError::Rejected {
+                status: 404,
+                message: "サーバー側で削除されています".to_string(),
+            });
+        }
+        result => result?,
+    };
+    match base_updated_at {
+        Some(base) if base != current.updated_at => Err(RequestError::Rejected {
+            status: 409,
+            message: "サーバー側で変更されています".to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Stores the most recent transactions for offline use
+pub async fn cache_transactions(transactions: &[TransactionResponse]) {
+    let mut recent = transactions.to_vec();
+    recent.sort_by(|a, b| b.transaction_date.cmp(&a.transaction_date));
+    recent.truncate(CACHED_TRANSACTION_LIMIT);
+    let _ = store::set(store::TRANSACTIONS_KEY, &recent).await;
+}
+
+/// Adds or refreshes one transaction in the local cache
+pub async fn remember_transaction(transaction: &TransactionResponse) {
+    let mut cached = cached_transactions().await;
+    cached.retain(|t| t.id != transaction.id);
+    cached.push(transaction.clone());
+    cache_transactions(&cached).await;
+}
+
+/// The cached transactions as they will look once the outbox is sent
+pub async fn cached_with_pending_changes() -> Vec<TransactionResponse> {
+    with_pending_changes(cached_transactions().await).await
+}
+
+/// Applies queued changes on top of transactions loaded from the server
+pub async fn with_pending_changes(
+    mut transactions: Vec<TransactionResponse>,
+) -> Vec<TransactionResponse> {
+    for change in outbox().await {
+        match change {
+            PendingChange::Create {
+                local_id, request, ..
+            } => transactions.insert(0, provisional(local_id, &request)),
+            PendingChange::Update { id, request, .. } => {
+                if let Some(t) = transactions.iter_mut().find(|t| t.id == id) {
+                    apply_update(t, &request);
+                }
+            }
+            PendingChange::Delete { id, .. } => transactions.retain(|t| t.id != id),
+        }
+    }
+    transactions
+}
+
+/// Queues a create and returns the transaction as it will be shown until
+/// it is sent
+pub async fn queue_create(
+    request: &CreateTransactionRequest,
+    idempotency_key: String,
+) -> Result<TransactionResponse, String> {
+    let local_id = -(js_sys::Date::now() as i64);
+    let mut outbox = outbox().await;
+    outbox.push(PendingChange::Create {
+        local_id,
+        idempotency_key,
+        request: request.clone(),
+    });
+    save_outbox(&outbox).await?;
+    Ok(provisional(local_id, request))
+}
+
+/// Queues an edit of a transaction in the local cache
+pub async fn queue_update(
+    id: i64,
+    request: &UpdateTransactionRequest,
+) -> Result<TransactionResponse, String> {
+    let mut transaction = cached_with_pending_changes()
+        .await
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| "Failed to update transaction: offline".to_string())?;
+
+    let mut outbox = outbox().await;
+    outbox.push(PendingChange::Update {
+        key: new_idempotency_key(),
+        id,
+        label: transaction.description.clone(),
+        base_updated_at: Some(transaction.updated_at.clone()),
+        request: request.clone(),
+    });
+    save_outbox(&outbox).await?;
+
+    apply_update(&mut transaction, request);
+    Ok(transaction)
+}
+
+/// Queues a delete
+pub async fn queue_delete(id: i64) -> Result<(), String> {
+    let cached = cached_transactions().await.into_iter().find(|t| t.id == id);
+    let mut outbox = outbox().await;
+    outbox.push(PendingChange::Delete {
+        key: new_idempotency_key(),
+        id,
+        label: cached
+            .as_ref()
+            .map(|t| t.description.clone())
+            .unwrap_or_else(|| format!("#{}", id)),
+        base_updated_at: cached.map(|t| t.updated_at),
+    });
+    save_outbox(&outbox).await
+}
+
+/// Edits a create that has not been sent yet
+pub async fn update_queued_create(
+    local_id: i64,
+    update: &UpdateTransactionRequest,
+) -> Result<TransactionResponse, String> {
+    let mut outbox = outbox().await;
+    let request = outbox
+        .iter_mut()
+        .find_map(|change| match change {
+            PendingChange::Create {
+                local_id: id,
+                request,
+                ..
+            } if *id == local_id => Some(request),
+            _ => None,
+        })
+        .ok_or_else(|| "Transaction not found".to_string())?;
+
+    if let Some(a) = update.amount {
+        request.amount = a;
+    }
+    if let Some(c) = update.category_id {
+        request.category_id = c;
+    }
+    if let Some(d) = &update.description {
+        request.description = d.clone();
+    }
+    if let Some(date) = update.transaction_date {
+        request.transaction_date = date;
+    }
+    let transaction = provisional(local_id, request);
+    save_outbox(&outbox).await?;
+    Ok(transaction)
+}
+
+/// Drops a create that has not been sent yet
+pub async fn delete_queued_create(local_id: i64) -> Result<(), String> {
+    let mut outbox = outbox().await;
+    outbox.retain(|change| {
+        !matches!(change, PendingChange::Create { local_id: id, .. } if *id == local_id)
+    });
+    save_outbox(&outbox).await
+}
+
+fn provisional(local_id: i64, request: &CreateTransactionRequest) -> TransactionResponse {
+    let now = chrono::Utc::now().to_rfc3339();
+    TransactionResponse {
+        id: local_id,
+        amount: request.amount,
+        category_id: request.category_id,
+        description: request.description.clone(),
+        transaction_date: request.transaction_date.format("%Y-%m-%d").to_string(),
+        transaction_type: request.transaction_type,
+        account_id: request.account_id,
+        reconcile_status: Default::default(),
+        created_at: now.clone(),
+        updated_at: now,
+    }
+}
+
+fn apply_update(transaction: &mut TransactionResponse, request: &UpdateTransactionRequest) {
+    if let Some(a) = request.amount {
+        transaction.amount = a;
+    }
+    if let Some(c) = request.category_id {
+        transaction.category_id = c;
+    }
+    if let Some(d) = &request.description {
+        transaction.description = d.clone();
+    }
+    if let Some(date) = request.transaction_date {
+        transaction.transaction_date = date.format("%Y-%m-%d").to_string();
+    }
+    if let Some(account_id) = request.account_id {
+        transaction.account_id = Some(account_id);
+    }
+}
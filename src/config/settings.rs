@@ -8,6 +8,10 @@ use reinhardt::conf::settings::sources::{DefaultSource, LowPriorityEnvSource, To
 use reinhardt::Settings;
 use std::env;
 
+/// SQLite database used by the server and management commands
+/// (mode=rwc creates the file if it does not exist)
+pub const DATABASE_URL: &str = "sqlite:db.sqlite3?mode=rwc";
+
 /// Get settings based on environment variable
 pub fn get_settings() -> Settings {
     let profile_str = env::var("REINHARDT_ENV").unwrap_or_else(|_| "local".to_string());
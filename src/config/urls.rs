@@ -11,6 +11,34 @@ pub fn url_patterns() -> ServerRouter {
         .mount("/api/categories/", crate::apps::categories::url_patterns())
         // Transactions API endpoints
         .mount("/api/transactions/", crate::apps::transactions::url_patterns())
+        // Accounts and their statement reconciliations
+        .mount("/api/accounts/", crate::apps::accounts::url_patterns())
+        // Installment and revolving plans with the remaining liability
+        .mount("/api/installments/", crate::apps::installments::url_patterns())
+        // Loans with amortization schedules and linked repayments
+        .mount("/api/loans/", crate::apps::loans::url_patterns())
+        // Manually valued assets and liabilities for net worth
+        .mount("/api/assets/", crate::apps::assets::url_patterns())
+        // Securities, investment transactions and holdings
+        .mount("/api/investments/", crate::apps::investments::url_patterns())
+        // Recurring items and cash flow forecast
+        .mount("/api/forecast/", crate::apps::forecast::url_patterns())
+        // Savings goals and their progress
+        .mount("/api/goals/", crate::apps::goals::url_patterns())
         // Reports API endpoints
         .mount("/api/reports/", crate::apps::reports::url_patterns())
+        // Medical expense details for 医療費控除
+        .mount("/api/medical/", crate::apps::medical::url_patterns())
+        // Furusato nozei donations
+        .mount("/api/donations/", crate::apps::donations::url_patterns())
+        // Delta sync for clients with a local copy
+        .mount("/api/sync/", crate::apps::sync::url_patterns())
+        // Audit feed of all changes
+        .mount("/api/audit/", crate::apps::audit::url_patterns())
+        // Closing the books (period lock)
+        .mount("/api/periods/", crate::apps::periods::url_patterns())
+        // Trashed categories and transactions: restore or purge
+        .mount("/api/trash/", crate::apps::trash::url_patterns())
+        // WASM client, static assets and client-side route fallback
+        .mount("/", crate::apps::frontend::url_patterns())
 }
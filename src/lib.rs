@@ -1,10 +1,19 @@
 //! kakebooR - Household Budget Application
 //!
-//! A REST API for managing household finances built with Reinhardt.
+//! A REST API for managing household finances built with Reinhardt, and a
+//! WASM client for the browser. The server modules build for native targets
+//! (`cfg(native)`), the client for `wasm32-unknown-unknown` (`cfg(wasm)`).
 
+#[cfg(native)]
 pub mod config;
+#[cfg(native)]
 pub mod apps;
+#[cfg(wasm)]
+pub mod client;
+pub mod shared;
 
 // Re-export commonly used items
+#[cfg(native)]
 pub use config::settings::get_settings;
+#[cfg(native)]
 pub use config::urls::url_patterns;
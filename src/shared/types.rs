@@ -1,75 +1,506 @@
 //! Shared type definitions for kakebooR
 //!
-//! These types are used by both WASM frontend and server.
+//! These are the request/response DTOs of the REST API that the WASM
+//! client uses, and the enums they contain. The server views serialize them
+//! and the client deserializes them, so this module must stay free of
+//! server-only dependencies. DTOs only the server uses live in each app's
+//! `serializers.rs`, and the conversions from ORM models live there too.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
-/// Category type enumeration
+// ============================================================================
+// Enums
+// ============================================================================
+
+/// Category type representing whether it's for income or expense
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CategoryType {
+    /// Income category (salary, bonus, etc.)
     Income,
+    /// Expense category (food, transport, etc.)
     Expense,
 }
 
-/// Transaction type enumeration
+impl std::fmt::Display for CategoryType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CategoryType::Income => write!(f, "income"),
+            CategoryType::Expense => write!(f, "expense"),
+        }
+    }
+}
+
+impl std::str::FromStr for CategoryType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "income" => Ok(CategoryType::Income),
+            "expense" => Ok(CategoryType::Expense),
+            _ => Err(format!("Invalid category type: {}", s)),
+        }
+    }
+}
+
+/// Transaction type representing income or expense
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TransactionType {
+    /// Income transaction (salary, bonus, etc.)
     Income,
+    /// Expense transaction (food, transport, etc.)
     Expense,
 }
 
-/// Category information for display
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CategoryInfo {
+impl std::fmt::Display for TransactionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransactionType::Income => write!(f, "income"),
+            TransactionType::Expense => write!(f, "expense"),
+        }
+    }
+}
+
+impl std::str::FromStr for TransactionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "income" => Ok(TransactionType::Income),
+            "expense" => Ok(TransactionType::Expense),
+            _ => Err(format!("Invalid transaction type: {}", s)),
+        }
+    }
+}
+
+/// Where a transaction stands against the account's statements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReconcileStatus {
+    /// Not yet seen on a statement
+    #[default]
+    Uncleared,
+    /// Ticked off against the statement being reconciled
+    Cleared,
+    /// Part of a finalized reconciliation; amount, date and account are fixed
+    Reconciled,
+}
+
+impl std::fmt::Display for ReconcileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReconcileStatus::Uncleared => write!(f, "uncleared"),
+            ReconcileStatus::Cleared => write!(f, "cleared"),
+            ReconcileStatus::Reconciled => write!(f, "reconciled"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReconcileStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "uncleared" => Ok(ReconcileStatus::Uncleared),
+            "cleared" => Ok(ReconcileStatus::Cleared),
+            "reconciled" => Ok(ReconcileStatus::Reconciled),
+            _ => Err(format!("Invalid reconcile status: {}", s)),
+        }
+    }
+}
+
+/// When purchases paid in installments count in reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportBasis {
+    /// The full purchase on the purchase date
+    #[default]
+    Purchase,
+    /// Each scheduled payment (with its fee) on its payment date
+    Installment,
+}
+
+// ============================================================================
+// Categories
+// ============================================================================
+
+/// Request payload for creating a new category
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateCategoryRequest {
+    /// Category name (required, 1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: String,
+    /// Category type (income or expense)
+    pub category_type: CategoryType,
+    /// Optional icon identifier
+    #[validate(length(max = 50, message = "Icon must be at most 50 characters"))]
+    pub icon: Option<String>,
+    /// Optional color code (hex format)
+    #[validate(length(max = 7, message = "Color must be at most 7 characters"))]
+    pub color: Option<String>,
+    /// Display position (defaults to after the last category)
+    pub sort_order: Option<i32>,
+}
+
+/// Request payload for updating an existing category
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateCategoryRequest {
+    /// Category name (optional, 1-100 characters if provided)
+    #[validate(length(min = 1, max = 100, message = "Name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    /// Optional icon identifier
+    #[validate(length(max = 50, message = "Icon must be at most 50 characters"))]
+    pub icon: Option<String>,
+    /// Optional color code (hex format)
+    #[validate(length(max = 7, message = "Color must be at most 7 characters"))]
+    pub color: Option<String>,
+    /// Display position (optional)
+    pub sort_order: Option<i32>,
+}
+
+/// Response payload for a category
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryResponse {
     pub id: i64,
     pub name: String,
     pub category_type: CategoryType,
     pub icon: Option<String>,
     pub color: Option<String>,
+    pub sort_order: i32,
+    pub created_at: String,
 }
 
-/// Transaction information for display
+/// Response wrapper for list of categories
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TransactionInfo {
+pub struct CategoryListResponse {
+    pub count: usize,
+    pub results: Vec<CategoryResponse>,
+}
+
+// ============================================================================
+// Transactions
+// ============================================================================
+
+/// Request payload for creating a new transaction
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateTransactionRequest {
+    /// Amount in yen (must be positive)
+    #[validate(range(min = 1, message = "Amount must be positive"))]
+    pub amount: i64,
+    /// Category ID
+    pub category_id: i64,
+    /// Description or memo (max 500 characters)
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    pub description: String,
+    /// Date of the transaction (ISO 8601 format)
+    pub transaction_date: DateTime<Utc>,
+    /// Type of transaction (income or expense)
+    pub transaction_type: TransactionType,
+    /// Account the money moved in or out of (optional)
+    #[serde(default)]
+    pub account_id: Option<i64>,
+}
+
+/// Request payload for updating an existing transaction
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateTransactionRequest {
+    /// Amount in yen (optional, must be positive if provided)
+    #[validate(range(min = 1, message = "Amount must be positive"))]
+    pub amount: Option<i64>,
+    /// Category ID (optional)
+    pub category_id: Option<i64>,
+    /// Description or memo (optional, max 500 characters if provided)
+    #[validate(length(max = 500, message = "Description must be at most 500 characters"))]
+    pub description: Option<String>,
+    /// Date of the transaction (optional)
+    pub transaction_date: Option<DateTime<Utc>>,
+    /// Account (optional)
+    pub account_id: Option<i64>,
+    /// Tick off (`true`) or untick (`false`) against the bank statement
+    pub cleared: Option<bool>,
+}
+
+/// Response payload for a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransactionResponse {
     pub id: i64,
     pub amount: i64,
     pub category_id: i64,
     pub description: String,
+    /// Transaction date as `YYYY-MM-DD`
     pub transaction_date: String,
     pub transaction_type: TransactionType,
+    #[serde(default)]
+    pub account_id: Option<i64>,
+    #[serde(default)]
+    pub reconcile_status: ReconcileStatus,
+    pub created_at: String,
+    pub updated_at: String,
 }
 
-/// Category list response
+/// Response wrapper for list of transactions
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CategoryListResponse {
+pub struct TransactionListResponse {
     pub count: usize,
-    pub results: Vec<CategoryInfo>,
+    pub results: Vec<TransactionResponse>,
 }
 
-/// Transaction list response
+/// A suggested category with the classifier's confidence (0.0 - 1.0)
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct TransactionListResponse {
-    pub count: usize,
-    pub results: Vec<TransactionInfo>,
+pub struct CategorySuggestion {
+    pub category: CategoryResponse,
+    pub confidence: f64,
+}
+
+/// Ranked category suggestions for a description
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySuggestionResponse {
+    pub description: String,
+    pub amount: Option<i64>,
+    pub suggestions: Vec<CategorySuggestion>,
+}
+
+// ============================================================================
+// Reports
+// ============================================================================
+
+/// Category summary in reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorySummary {
+    pub category_id: i64,
+    pub category_name: String,
+    pub total_amount: i64,
+    pub transaction_count: i32,
 }
 
 /// Monthly report response
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MonthlyReportInfo {
+pub struct MonthlyReportResponse {
     pub year: i32,
     pub month: u32,
+    /// Whether installment purchases count in full or per payment
+    #[serde(default)]
+    pub basis: ReportBasis,
     pub total_income: i64,
     pub total_expense: i64,
     pub net_balance: i64,
+    pub income_by_category: Vec<CategorySummary>,
+    pub expense_by_category: Vec<CategorySummary>,
 }
 
-/// Category summary in reports
+/// Yearly report response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearlyReportResponse {
+    pub year: i32,
+    pub total_income: i64,
+    pub total_expense: i64,
+    pub net_balance: i64,
+    pub monthly_summary: Vec<MonthlySummary>,
+    /// Part of the year's expenses that repaid loan principal
+    #[serde(default)]
+    pub loan_principal: i64,
+    /// Part of the year's expenses that was loan interest
+    #[serde(default)]
+    pub loan_interest: i64,
+}
+
+/// Monthly summary within yearly report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlySummary {
+    pub month: u32,
+    pub total_income: i64,
+    pub total_expense: i64,
+    pub net_balance: i64,
+}
+
+/// Totals of one period of a comparison report
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CategorySummaryInfo {
+pub struct PeriodTotals {
+    /// `YYYY-MM` for a month, `YYYY` for a year
+    pub period: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub total_income: i64,
+    pub total_expense: i64,
+    pub net_balance: i64,
+}
+
+/// Amounts of one category in both periods of a comparison report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryComparison {
     pub category_id: i64,
     pub category_name: String,
-    pub total_amount: i64,
-    pub transaction_count: i32,
+    pub transaction_type: TransactionType,
+    pub base_amount: i64,
+    pub target_amount: i64,
+    /// `target_amount - base_amount`
+    pub change: i64,
+    /// Change relative to the base amount in percent (one decimal), `None`
+    /// when the base amount is zero
+    pub change_percent: Option<f64>,
+}
+
+/// Comparison report response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonReportResponse {
+    /// Whether installment purchases count in full or per payment
+    #[serde(default)]
+    pub basis: ReportBasis,
+    pub base: PeriodTotals,
+    pub target: PeriodTotals,
+    /// Biggest change first
+    pub categories: Vec<CategoryComparison>,
+}
+
+/// Asserts that each value serializes to its `Display` string and parses
+/// back from it, so the JSON and query string forms of an enum agree
+#[cfg(test)]
+pub fn assert_wire_values<T>(values: &[T])
+where
+    T: Serialize + std::fmt::Display + std::str::FromStr + PartialEq + std::fmt::Debug,
+    T::Err: std::fmt::Debug,
+{
+    for value in values {
+        assert_eq!(
+            serde_json::to_value(value).unwrap(),
+            serde_json::Value::String(value.to_string())
+        );
+        assert_eq!(&value.to_string().parse::<T>().unwrap(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    /// Returns the sorted top-level keys of a serialized value
+    fn keys(value: &impl Serialize) -> Vec<String> {
+        let Value::Object(map) = serde_json::to_value(value).unwrap() else {
+            panic!("expected a JSON object");
+        };
+        let mut keys: Vec<String> = map.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn sample_category() -> CategoryResponse {
+        CategoryResponse {
+            id: 1,
+            name: "食費".to_string(),
+            category_type: CategoryType::Expense,
+            icon: Some("🍚".to_string()),
+            color: Some("#EF4444".to_string()),
+            sort_order: 0,
+            created_at: "2026-01-01T00:00:00+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_category_response_schema() {
+        assert_eq!(
+            keys(&sample_category()),
+            ["category_type", "color", "created_at", "icon", "id", "name", "sort_order"]
+        );
+    }
+
+    #[test]
+    fn test_transaction_response_schema() {
+        let transaction = TransactionResponse {
+            id: 1,
+            amount: 1500,
+            category_id: 1,
+            description: "ランチ".to_string(),
+            transaction_date: "2026-01-27".to_string(),
+            transaction_type: TransactionType::Expense,
+            account_id: Some(1),
+            reconcile_status: ReconcileStatus::Cleared,
+            created_at: "2026-01-27T12:00:00+00:00".to_string(),
+            updated_at: "2026-01-27T12:00:00+00:00".to_string(),
+        };
+        assert_eq!(
+            keys(&transaction),
+            [
+                "account_id",
+                "amount",
+                "category_id",
+                "created_at",
+                "description",
+                "id",
+                "reconcile_status",
+                "transaction_date",
+                "transaction_type",
+                "updated_at"
+            ]
+        );
+
+        let round_trip: TransactionResponse =
+            serde_json::from_value(serde_json::to_value(&transaction).unwrap()).unwrap();
+        assert_eq!(round_trip, transaction);
+    }
+
+    #[test]
+    fn test_monthly_report_schema() {
+        let report = MonthlyReportResponse {
+            year: 2026,
+            month: 1,
+            basis: ReportBasis::Purchase,
+            total_income: 0,
+            total_expense: 0,
+            net_balance: 0,
+            income_by_category: Vec::new(),
+            expense_by_category: Vec::new(),
+        };
+        assert_eq!(
+            keys(&report),
+            [
+                "basis",
+                "expense_by_category",
+                "income_by_category",
+                "month",
+                "net_balance",
+                "total_expense",
+                "total_income",
+                "year"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_transaction_request_wire_format() {
+        // The format documented in the README and sent by static/app.js
+        let request: CreateTransactionRequest = serde_json::from_value(json!({
+            "amount": 1500,
+            "category_id": 1,
+            "description": "ランチ",
+            "transaction_date": "2026-01-27T12:00:00Z",
+            "transaction_type": "expense"
+        }))
+        .unwrap();
+        assert_eq!(request.transaction_type, TransactionType::Expense);
+        assert_eq!(request.account_id, None);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn test_category_type_wire_values() {
+        assert_wire_values(&[CategoryType::Income, CategoryType::Expense]);
+    }
+
+    #[test]
+    fn test_transaction_type_wire_values() {
+        assert_wire_values(&[TransactionType::Income, TransactionType::Expense]);
+    }
+
+    #[test]
+    fn test_reconcile_status_wire_values() {
+        assert_wire_values(&[
+            ReconcileStatus::Uncleared,
+            ReconcileStatus::Cleared,
+            ReconcileStatus::Reconciled,
+        ]);
+    }
 }
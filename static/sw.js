@@ -0,0 +1,57 @@
+// Service worker for kakebooR
+//
+// Keeps the app shell (HTML, WASM bundle, styles) available offline. Data
+// is not cached here: API requests go straight to the network so the WASM
+// client can detect that it is offline and use its IndexedDB store instead.
+//
+// Served from /sw.js so that its scope covers the whole site.
+
+const CACHE_NAME = 'kakeboor-shell-v1';
+
+const SHELL = [
+    '/',
+    '/dist-wasm/kakeboor.js',
+    '/dist-wasm/kakeboor_bg.wasm',
+];
+
+self.addEventListener('install', (event) => {
+    event.waitUntil(
+        caches.open(CACHE_NAME)
+            .then((cache) => cache.addAll(SHELL))
+            .then(() => self.skipWaiting())
+    );
+});
+
+self.addEventListener('activate', (event) => {
+    event.waitUntil(
+        caches.keys()
+            .then((keys) => Promise.all(
+                keys.filter((key) => key !== CACHE_NAME).map((key) => caches.delete(key))
+            ))
+            .then(() => self.clients.claim())
+    );
+});
+
+self.addEventListener('fetch', (event) => {
+    const request = event.request;
+    const url = new URL(request.url);
+
+    if (request.method !== 'GET' || url.pathname.startsWith('/api/')) {
+        return;
+    }
+
+    // Network first so a rebuilt bundle is picked up, cache when offline.
+    // Every client-side route is served by the same HTML shell.
+    event.respondWith(
+        fetch(request)
+            .then((response) => {
+                if (response.ok || response.type === 'opaque') {
+                    const copy = response.clone();
+                    const key = request.mode === 'navigate' ? '/' : request;
+                    caches.open(CACHE_NAME).then((cache) => cache.put(key, copy));
+                }
+                return response;
+            })
+            .catch(() => caches.match(request.mode === 'navigate' ? '/' : request))
+    );
+});